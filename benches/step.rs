@@ -0,0 +1,37 @@
+use std::hint::black_box;
+
+use conway_gol_bevy::{
+    board::{step_board, Board},
+    rules::Ruleset,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+/// the width and height of the boards stepped
+const SIZES: [u32; 3] = [64, 128, 256];
+
+/// steps randomly seeded boards of each size, the same seed keeps the runs comparable
+fn step(c: &mut Criterion) {
+    let rules = Ruleset::default();
+    let mut group = c.benchmark_group("step_board");
+    for size in SIZES {
+        let board = Board {
+            width: size,
+            height: size,
+            ..Default::default()
+        };
+        let mut rng = fastrand::Rng::with_seed(42);
+        let current = (0..board.cell_count())
+            .map(|_| rng.bool())
+            .collect::<Vec<_>>();
+        let mut next = vec![false; board.cell_count()];
+
+        group.throughput(Throughput::Elements(board.cell_count() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &current, |b, current| {
+            b.iter(|| step_board(black_box(current), &mut next, &board, &rules));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, step);
+criterion_main!(benches);
@@ -0,0 +1,298 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::pattern;
+
+/// Logical actions that can be triggered from the keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// start running from setup, or stop / resume the running simulation
+    StartStop,
+    /// stop the simulation and go back to setup for editing the board, cancels the stamp preview
+    /// while in setup
+    EditBoard,
+    /// advance a single generation
+    Step,
+    /// go back to the generation before the last step
+    StepBack,
+    SlowDown,
+    SpeedUp,
+    /// advance the generations on the beat of the last few taps
+    TapTempo,
+    /// randomize the selected cells, or the whole board without a selection
+    Randomize,
+    /// fewer live cells when randomizing
+    DecreaseDensity,
+    /// more live cells when randomizing
+    IncreaseDensity,
+    /// use the next seed for randomizing the board
+    NextSeed,
+    /// lower chance of cells flipping against the rules, see `life::Noise`
+    DecreaseNoise,
+    /// higher chance of cells flipping against the rules
+    IncreaseNoise,
+    Clear,
+    /// undo the last edit, together with ctrl
+    Undo,
+    /// redo the last undone edit, together with ctrl
+    Redo,
+    /// save the board, or with shift the selected / live cells as a plaintext pattern
+    Save,
+    /// load the latest saved board, or with shift stamp the latest plaintext pattern
+    Load,
+    ExportPng,
+    /// add a thumbnail of the board to the gallery, clicking it restores the board
+    CaptureThumbnail,
+    /// save the starting board and the generations run since as a session
+    SaveSession,
+    /// run the most recently saved session again
+    ReplaySession,
+    /// preview the pattern from `pattern::LIBRARY` with this index, clicking stamps it
+    StampPattern(usize),
+    /// copy the selected cells into the clipboard
+    CopySelection,
+    /// preview the clipboard at the hovered cell, clicking stamps it
+    PasteClipboard,
+    /// rotate the stamped patterns clockwise by 90°
+    RotateStamp,
+    /// mirror the stamped patterns horizontally
+    FlipStamp,
+    FlipHorizontal,
+    FlipVertical,
+    Rotate,
+    GrowBoard,
+    ShrinkBoard,
+    GrowCells,
+    ShrinkCells,
+    GrowBrush,
+    ShrinkBrush,
+    /// cycle through the symmetry modes of painting
+    CycleSymmetry,
+    /// cycle between wrapping on both axes, only horizontally, only vertically and not at all
+    ToggleWrap,
+    /// switch between counting the 8 surrounding cells and only the 4 adjacent ones
+    ToggleNeighbourhood,
+    /// switch between the fixed board and an unbounded one that scrolls with the camera
+    ToggleUnbounded,
+    ToggleWrapIndicator,
+    /// show / hide the border around the board
+    ToggleBorder,
+    ThinnerBorder,
+    ThickerBorder,
+    ToggleStasisDetection,
+    ToggleGrid,
+    ToggleHeatmap,
+    /// hide dead cells instead of drawing them in the background color
+    ToggleDeadCells,
+    /// draw live cells and the keyboard cursor's cell over the dead cells
+    ToggleCellLayers,
+    ToggleRenderMode,
+    /// switch to the next color theme
+    CycleTheme,
+    /// fade cells between being dead and alive
+    ToggleFades,
+    TogglePopulationGraph,
+    /// tint the cells that are born / die in the next generation, while not running
+    ToggleFuturePreview,
+    /// log the live neighbours of the hovered cell
+    ToggleNeighbourCount,
+    /// log the full state of cells clicked on together with alt
+    ToggleProbe,
+    /// outline the gliders on the board for a moment
+    FindGliders,
+    /// show / hide a cursor on the board that's moved with the keyboard
+    ToggleKeyboardCursor,
+    MoveCursorUp,
+    MoveCursorDown,
+    MoveCursorLeft,
+    MoveCursorRight,
+    /// paint the cells under the keyboard cursor, the opposite of the cell's current life status
+    PaintCursorCell,
+    FitCamera,
+    /// center and zoom the camera onto the live cells
+    FrameLiveCells,
+    /// show / hide the frame rate
+    ToggleDiagnostics,
+    /// show / hide the generation, population, rule and stabilization of the board
+    ToggleStatus,
+    /// switch to the next present mode of the window, see `diagnostics::PRESENT_MODES`
+    CyclePresentMode,
+    /// switch to the next anti-aliasing setting of the camera, see `camera::MSAA_SETTINGS`
+    CycleMsaa,
+    /// switch to the next of the `rules::Ruleset::PRESETS`
+    CycleRule,
+    /// re-read `config::CONFIG_FILE_PATH`, see `config::ConfigFile`
+    ReloadConfig,
+    /// quit the game, saving unsaved edits to `save::RECOVERY_SAVE_PATH`
+    Quit,
+}
+
+/// Maps actions to the keys that trigger them, actions without a key can't be triggered.
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Deref, DerefMut)]
+pub struct KeyBindings(pub HashMap<Action, KeyCode>);
+
+impl KeyBindings {
+    pub fn key(&self, action: Action) -> Option<KeyCode> {
+        self.get(&action).copied()
+    }
+
+    pub fn just_pressed(&self, keyboard_input: &ButtonInput<KeyCode>, action: Action) -> bool {
+        self.key(action)
+            .is_some_and(|key| keyboard_input.just_pressed(key))
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let pattern_keys = [
+            KeyCode::Digit1,
+            KeyCode::Digit2,
+            KeyCode::Digit3,
+            KeyCode::Digit4,
+        ];
+        let stamp_bindings = pattern_keys
+            .into_iter()
+            .take(pattern::LIBRARY.len())
+            .enumerate()
+            .map(|(i, key)| (Action::StampPattern(i), key));
+
+        Self(
+            [
+                (Action::StartStop, KeyCode::Space),
+                (Action::EditBoard, KeyCode::Escape),
+                (Action::Step, KeyCode::Period),
+                (Action::StepBack, KeyCode::Backspace),
+                (Action::SlowDown, KeyCode::BracketLeft),
+                (Action::SpeedUp, KeyCode::BracketRight),
+                (Action::TapTempo, KeyCode::Enter),
+                (Action::Randomize, KeyCode::KeyR),
+                (Action::NextSeed, KeyCode::KeyE),
+                (Action::DecreaseDensity, KeyCode::Digit9),
+                (Action::IncreaseDensity, KeyCode::Digit0),
+                (Action::DecreaseNoise, KeyCode::Digit5),
+                (Action::IncreaseNoise, KeyCode::Digit6),
+                (Action::Clear, KeyCode::KeyC),
+                (Action::Undo, KeyCode::KeyZ),
+                (Action::Redo, KeyCode::KeyY),
+                (Action::Save, KeyCode::KeyS),
+                (Action::Load, KeyCode::KeyL),
+                (Action::ExportPng, KeyCode::KeyP),
+                (Action::CaptureThumbnail, KeyCode::PageUp),
+                (Action::SaveSession, KeyCode::F5),
+                (Action::ReplaySession, KeyCode::F6),
+                (Action::CopySelection, KeyCode::Comma),
+                (Action::PasteClipboard, KeyCode::Slash),
+                (Action::RotateStamp, KeyCode::KeyQ),
+                (Action::FlipStamp, KeyCode::KeyJ),
+                (Action::FlipHorizontal, KeyCode::KeyX),
+                (Action::FlipVertical, KeyCode::KeyV),
+                (Action::Rotate, KeyCode::KeyT),
+                (Action::GrowBoard, KeyCode::NumpadAdd),
+                (Action::ShrinkBoard, KeyCode::NumpadSubtract),
+                (Action::GrowCells, KeyCode::Equal),
+                (Action::ShrinkCells, KeyCode::Minus),
+                (Action::GrowBrush, KeyCode::Quote),
+                (Action::ShrinkBrush, KeyCode::Semicolon),
+                (Action::CycleSymmetry, KeyCode::KeyM),
+                (Action::ToggleWrap, KeyCode::KeyW),
+                (Action::ToggleNeighbourhood, KeyCode::F8),
+                (Action::ToggleUnbounded, KeyCode::KeyU),
+                (Action::ToggleWrapIndicator, KeyCode::KeyI),
+                (Action::ToggleBorder, KeyCode::F9),
+                (Action::ThinnerBorder, KeyCode::Digit7),
+                (Action::ThickerBorder, KeyCode::Digit8),
+                (Action::ToggleStasisDetection, KeyCode::KeyA),
+                (Action::ToggleGrid, KeyCode::KeyG),
+                (Action::ToggleHeatmap, KeyCode::KeyH),
+                (Action::ToggleDeadCells, KeyCode::Backslash),
+                (Action::ToggleCellLayers, KeyCode::PageDown),
+                (Action::ToggleRenderMode, KeyCode::KeyO),
+                (Action::CycleTheme, KeyCode::KeyB),
+                (Action::ToggleFades, KeyCode::KeyD),
+                (Action::TogglePopulationGraph, KeyCode::KeyN),
+                (Action::ToggleFuturePreview, KeyCode::Backquote),
+                (Action::ToggleNeighbourCount, KeyCode::KeyK),
+                (Action::ToggleProbe, KeyCode::F4),
+                (Action::FindGliders, KeyCode::F7),
+                (Action::ToggleKeyboardCursor, KeyCode::F2),
+                (Action::MoveCursorUp, KeyCode::ArrowUp),
+                (Action::MoveCursorDown, KeyCode::ArrowDown),
+                (Action::MoveCursorLeft, KeyCode::ArrowLeft),
+                (Action::MoveCursorRight, KeyCode::ArrowRight),
+                (Action::PaintCursorCell, KeyCode::Tab),
+                (Action::FitCamera, KeyCode::KeyF),
+                (Action::FrameLiveCells, KeyCode::F11),
+                (Action::ToggleDiagnostics, KeyCode::F3),
+                (Action::ToggleStatus, KeyCode::Insert),
+                (Action::CyclePresentMode, KeyCode::F10),
+                (Action::CycleMsaa, KeyCode::F1),
+                (Action::CycleRule, KeyCode::End),
+                (Action::ReloadConfig, KeyCode::Home),
+                (Action::Quit, KeyCode::F12),
+            ]
+            .into_iter()
+            .chain(stamp_bindings)
+            .collect(),
+        )
+    }
+}
+
+/// run condition that is true when the key bound to the action was just pressed
+pub fn action_just_pressed(
+    action: Action,
+) -> impl Fn(Res<ButtonInput<KeyCode>>, Res<KeyBindings>) -> bool + Clone {
+    move |keyboard_input, bindings| bindings.just_pressed(&keyboard_input, action)
+}
+
+/// run condition that is true when the key bound to any of the actions was just pressed
+pub fn any_action_just_pressed<const N: usize>(
+    actions: [Action; N],
+) -> impl Fn(Res<ButtonInput<KeyCode>>, Res<KeyBindings>) -> bool + Clone {
+    move |keyboard_input, bindings| {
+        actions
+            .iter()
+            .any(|&action| bindings.just_pressed(&keyboard_input, action))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::utils::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn default_bindings() {
+        let bindings = KeyBindings::default();
+        assert_eq!(Some(KeyCode::Space), bindings.key(Action::StartStop));
+        assert_eq!(Some(KeyCode::Escape), bindings.key(Action::EditBoard));
+        assert_eq!(Some(KeyCode::KeyR), bindings.key(Action::Randomize));
+        assert_eq!(Some(KeyCode::KeyC), bindings.key(Action::Clear));
+        assert_eq!(Some(KeyCode::Period), bindings.key(Action::Step));
+        assert_eq!(Some(KeyCode::BracketRight), bindings.key(Action::SpeedUp));
+        assert_eq!(Some(KeyCode::Digit1), bindings.key(Action::StampPattern(0)));
+        assert_eq!(
+            None,
+            bindings.key(Action::StampPattern(pattern::LIBRARY.len()))
+        );
+
+        // every action has its own key
+        let keys = bindings.values().collect::<HashSet<_>>();
+        assert_eq!(bindings.len(), keys.len());
+    }
+
+    #[test]
+    fn bound_keys_trigger_actions() {
+        let mut bindings = KeyBindings::default();
+        let mut keyboard_input = ButtonInput::<KeyCode>::default();
+        keyboard_input.press(KeyCode::KeyR);
+        assert!(bindings.just_pressed(&keyboard_input, Action::Randomize));
+        assert!(!bindings.just_pressed(&keyboard_input, Action::Clear));
+
+        // remapping
+        bindings.insert(Action::Randomize, KeyCode::KeyQ);
+        assert!(!bindings.just_pressed(&keyboard_input, Action::Randomize));
+        bindings.remove(&Action::Clear);
+        keyboard_input.press(KeyCode::KeyC);
+        assert!(!bindings.just_pressed(&keyboard_input, Action::Clear));
+    }
+}
@@ -0,0 +1,1022 @@
+use bevy::{
+    math::{ivec2, uvec2},
+    prelude::*,
+};
+
+use crate::{
+    prelude::*,
+    rules::{Neighbourhood, Ruleset},
+};
+
+/// Computes the generation following `current` into `next`, without any of the rendering or
+/// input handling of `life::LifePlugin`.
+///
+/// Both buffers hold one cell per cell of the board, in row-major order with the bottom row
+/// first: the cell at `(x, y)` is at index `y * width + x`, see `Board::cell_coord_to_idx`.
+///
+/// # Panics
+///
+/// if either of the buffers doesn't match the amount of cells on the board
+pub fn step_board(current: &[bool], next: &mut [bool], board: &Board, rules: &Ruleset) {
+    assert_eq!(board.cell_count(), current.len(), "current buffer size");
+    assert_eq!(board.cell_count(), next.len(), "next buffer size");
+    for (idx, next_alive) in next.iter_mut().enumerate() {
+        *next_alive = cell_alive_next(current, board, rules, idx);
+    }
+}
+
+/// whether the cell at `idx` of the `current` buffer is alive in the next generation, see
+/// `step_board`
+pub fn cell_alive_next(current: &[bool], board: &Board, rules: &Ruleset, idx: usize) -> bool {
+    let nval = live_neighbours(current, board, rules.neighbourhood, idx);
+    if current[idx] {
+        rules.survive[nval]
+    } else {
+        rules.birth[nval]
+    }
+}
+
+/// the amount of live neighbours of the cell at `idx` of the `cells` buffer, from 0 to the size
+/// of the neighbourhood, see `step_board`
+pub fn live_neighbours(
+    cells: &[bool],
+    board: &Board,
+    neighbourhood: Neighbourhood,
+    idx: usize,
+) -> usize {
+    board
+        .neighbour_indices(board.idx_to_cell_coord(idx), neighbourhood)
+        .into_iter()
+        .flatten()
+        .filter(|&neigh_idx| cells[neigh_idx])
+        .count()
+}
+
+/// The state of the simulation, the cell entities only mirror it for rendering.
+///
+/// Double buffered: the next generation is computed into a back buffer, which is then swapped
+/// with the current one.
+#[derive(Resource, Debug, Clone, Default)]
+pub(crate) struct LifeGrid {
+    /// life status of every cell, indexed by `Board::cell_coord_to_idx`
+    pub(crate) cells: Vec<bool>,
+    pub(crate) next: Vec<bool>,
+    /// the amount of generations each cell has been continuously alive for
+    pub(crate) ages: Vec<u32>,
+    /// the generation in which each cell last changed its state
+    pub(crate) last_changed: Vec<Option<u64>>,
+    /// how many generations ago each decaying cell died under Generations rules, 0 for the cells
+    /// that aren't decaying, see `Ruleset::states`
+    pub(crate) decay: Vec<u8>,
+}
+
+impl LifeGrid {
+    /// creates a grid of dead cells
+    pub(crate) fn new(cell_count: usize) -> Self {
+        Self {
+            cells: vec![false; cell_count],
+            next: vec![false; cell_count],
+            ages: vec![0; cell_count],
+            last_changed: vec![None; cell_count],
+            decay: vec![0; cell_count],
+        }
+    }
+
+    /// the bottom left and top right corners of the smallest region containing all of the live
+    /// cells, `None` if there are none
+    pub(crate) fn live_bounds(&self, board: &Board) -> Option<(UVec2, UVec2)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, alive)| **alive)
+            .map(|(idx, _)| board.idx_to_cell_coord(idx))
+            .fold(None, |bounds, cell_coord| match bounds {
+                Some((min, max)) => Some((cell_coord.min(min), cell_coord.max(max))),
+                None => Some((cell_coord, cell_coord)),
+            })
+    }
+
+    /// Sets a single cell outside of stepping, a cell that changes starts over with an age of 0.
+    pub(crate) fn set_alive(&mut self, idx: usize, alive: bool) {
+        if self.cells[idx] != alive {
+            self.cells[idx] = alive;
+            self.ages[idx] = 0;
+        }
+    }
+
+    /// replaces all of the cells like `set_alive`, returning the previous ones
+    pub(crate) fn replace(&mut self, cells: Vec<bool>) -> Vec<bool> {
+        for ((age, &alive), &was_alive) in self.ages.iter_mut().zip(&cells).zip(&self.cells) {
+            if alive != was_alive {
+                *age = 0;
+            }
+        }
+        std::mem::replace(&mut self.cells, cells)
+    }
+
+    /// Replaces the cells with the ones of a window moved by `offset` cells, the ages and
+    /// changes of the cells that stay on the board move along with them.
+    pub(crate) fn scroll(&mut self, board: &Board, offset: IVec2, cells: Vec<bool>) {
+        fn shifted<T: Copy>(board: &Board, offset: IVec2, values: &[T], default: T) -> Vec<T> {
+            (0..values.len())
+                .map(|idx| {
+                    let cell_coord = board.idx_to_cell_coord(idx).as_ivec2() + offset;
+                    if board.contains(cell_coord) {
+                        values[board.cell_coord_to_idx(cell_coord.as_uvec2())]
+                    } else {
+                        default
+                    }
+                })
+                .collect()
+        }
+        self.ages = shifted(board, offset, &self.ages, 0);
+        self.last_changed = shifted(board, offset, &self.last_changed, None);
+        self.decay = shifted(board, offset, &self.decay, 0);
+        self.cells = cells;
+    }
+
+    /// computes the next generation into the back buffer
+    pub(crate) fn compute_next(&mut self, board: &Board, rules: &Ruleset) {
+        self.compute_next_with(|cells, next| step_board(cells, next, board, rules));
+    }
+
+    /// computes the next generation into the back buffer with `step`, like `step_board`
+    pub(crate) fn compute_next_with(&mut self, step: impl FnOnce(&[bool], &mut [bool])) {
+        step(&self.cells, &mut self.next);
+        // decaying cells can't be born, see `is_decaying`
+        let cells = self.cells.iter().zip(&self.decay);
+        for (next_alive, (&alive, &decay)) in self.next.iter_mut().zip(cells) {
+            *next_alive &= alive || decay == 0;
+        }
+    }
+
+    /// whether the cell at `idx` is alive in the next generation
+    pub(crate) fn next_alive(&self, board: &Board, rules: &Ruleset, idx: usize) -> bool {
+        cell_alive_next(&self.cells, board, rules, idx) && !self.is_decaying(idx)
+    }
+
+    /// whether the cell at `idx` died and is passing through the decaying states of Generations
+    /// rules, see `Ruleset::states`
+    pub(crate) fn is_decaying(&self, idx: usize) -> bool {
+        !self.cells[idx] && self.decay[idx] > 0
+    }
+
+    /// the amount of live neighbours of the cell at `idx`, from 0 to the size of the neighbourhood
+    pub(crate) fn live_neighbours(
+        &self,
+        board: &Board,
+        neighbourhood: Neighbourhood,
+        idx: usize,
+    ) -> usize {
+        live_neighbours(&self.cells, board, neighbourhood, idx)
+    }
+
+    /// Restores the cells of an earlier generation, `steps` generations before the current one.
+    ///
+    /// Only the cells are kept in the snapshots, so the ages are estimated and the changes
+    /// after the restored generation are forgotten.
+    pub(crate) fn rewind(&mut self, cells: Vec<bool>, generation: u64, steps: u64) {
+        let steps = steps.min(u32::MAX as u64) as u32;
+        for ((age, &alive), &was_alive) in self.ages.iter_mut().zip(&self.cells).zip(&cells) {
+            *age = if alive && was_alive {
+                age.saturating_sub(steps)
+            } else {
+                0
+            };
+        }
+        for last_changed in &mut self.last_changed {
+            if last_changed.is_some_and(|last| last > generation) {
+                *last_changed = None;
+            }
+        }
+        self.decay.fill(0);
+        self.cells = cells;
+    }
+
+    /// makes the computed generation the current one
+    pub(crate) fn swap(&mut self) {
+        std::mem::swap(&mut self.cells, &mut self.next);
+    }
+
+    /// increments the age of cells that stayed alive and resets the dead ones
+    pub(crate) fn age_cells(&mut self) {
+        for (age, &alive) in self.ages.iter_mut().zip(&self.cells) {
+            *age = if alive { age.saturating_add(1) } else { 0 };
+        }
+    }
+
+    /// Advances the decaying cells by a generation and starts decaying the cells that died in the
+    /// last swap, the cells are dead once they passed through the `states - 2` decaying states.
+    pub(crate) fn decay_cells(&mut self, states: u8) {
+        let decay_len = states.saturating_sub(2);
+        // the back buffer holds the previous generation after the swap
+        for ((decay, &alive), &was_alive) in self.decay.iter_mut().zip(&self.cells).zip(&self.next)
+        {
+            let decaying = !alive && (*decay > 0 || was_alive);
+            *decay = if decaying && *decay < decay_len {
+                *decay + 1
+            } else {
+                0
+            };
+        }
+    }
+
+    /// records the cells that changed in the last swap
+    pub(crate) fn track_changes(&mut self, generation: u64) {
+        let changes = self
+            .cells
+            .iter()
+            .zip(&self.next)
+            .map(|(now, before)| now != before);
+        for (last_changed, changed) in self.last_changed.iter_mut().zip(changes) {
+            if changed {
+                *last_changed = Some(generation);
+            }
+        }
+    }
+
+    pub(crate) fn population(&self) -> u32 {
+        self.cells.iter().filter(|alive| **alive).count() as u32
+    }
+}
+
+/// The layout of a board: the amount of cells, where they are drawn and whether the edges wrap
+/// around.
+#[derive(Resource, Clone, Copy)]
+pub struct Board {
+    /// the center of the board
+    pub center: Vec2,
+    /// the amount of cells along the x axis
+    pub width: u32,
+    /// the amount of cells along the y axis
+    pub height: u32,
+    /// the size of each individual cell
+    pub cell_size: Vec2,
+    /// scale of each individual cell (should be 0.0 - 1.0)
+    pub cell_scale: Vec2,
+    /// whether the left and right edges of the board wrap around, or are bounded
+    pub wrap_x: bool,
+    /// whether the top and bottom edges of the board wrap around, or are bounded
+    pub wrap_y: bool,
+}
+
+impl Board {
+    /// computes full size of the board in pixels
+    #[inline]
+    pub fn pixel_size(&self) -> Vec2 {
+        self.size().as_vec2() * self.cell_size
+    }
+
+    /// the amount of cells on each axis
+    #[inline]
+    pub fn size(&self) -> UVec2 {
+        uvec2(self.width, self.height)
+    }
+
+    #[inline]
+    pub fn cell_count(&self) -> usize {
+        self.width as usize * self.height as usize
+    }
+
+    /// the world position of the cell's center
+    #[inline]
+    pub fn cell_coord_to_translation(&self, cell_coord: UVec2) -> Vec3 {
+        (self.center - (self.pixel_size() * 0.5)
+            + cell_coord.as_vec2() * self.cell_size
+            + self.cell_size * 0.5)
+            .extend(CELL_DEAD_Z)
+    }
+
+    /// inverse of `cell_coord_to_translation`, returns `None` if the position lies outside of
+    /// the board
+    #[inline]
+    pub fn translation_to_cell_coord(&self, world_pos: Vec2) -> Option<UVec2> {
+        let cell_coord = ((world_pos - (self.center - self.pixel_size() * 0.5)) / self.cell_size)
+            .floor()
+            .as_ivec2();
+        self.contains(cell_coord).then(|| cell_coord.as_uvec2())
+    }
+
+    /// whether the cell coordinate lies on the board
+    #[inline]
+    pub(crate) fn contains(&self, cell_coord: IVec2) -> bool {
+        cell_coord.cmpge(IVec2::ZERO).all() && cell_coord.cmplt(self.size().as_ivec2()).all()
+    }
+
+    /// the cell closest to the center of the board
+    #[inline]
+    pub fn center_cell_coord(&self) -> UVec2 {
+        self.size() / 2
+    }
+
+    /// the index of the cell in row-major order with the bottom row first, cell coordinates
+    /// outside of the board wrap around
+    #[inline]
+    pub fn cell_coord_to_idx(&self, cell_coord: UVec2) -> usize {
+        ((cell_coord.y % self.height) * self.width + (cell_coord.x % self.width)) as usize
+    }
+
+    /// inverse of `cell_coord_to_idx`
+    #[inline]
+    pub fn idx_to_cell_coord(&self, idx: usize) -> UVec2 {
+        uvec2(idx as u32 % self.width, idx as u32 / self.width)
+    }
+
+    /// returns the indices of all the cells within `radius` of a cell (a square), cells outside
+    /// of a board that doesn't wrap are skipped
+    pub(crate) fn brush_indices(
+        &self,
+        cell_coord: UVec2,
+        radius: u32,
+    ) -> impl Iterator<Item = usize> + '_ {
+        let radius = radius.min(self.width.min(self.height) / 2) as i32;
+        let center = cell_coord.as_ivec2();
+        (-radius..=radius)
+            .flat_map(move |y| (-radius..=radius).map(move |x| center + ivec2(x, y)))
+            .filter_map(|pos| self.wrapped_cell_coord(pos))
+            .map(|cell_coord| self.cell_coord_to_idx(cell_coord))
+    }
+
+    /// Returns the indices of the 8 surrounding cells, in the order of
+    /// `Neighbourhood::MOORE_OFFSETS`. A cell is `None` if it isn't part of the neighbourhood or
+    /// if it lies outside of a board that doesn't wrap.
+    #[inline]
+    pub fn neighbour_indices(
+        &self,
+        cell_coord: UVec2,
+        neighbourhood: Neighbourhood,
+    ) -> [Option<usize>; 8] {
+        let mut result = [None; 8];
+        for (neighbour, offset) in result.iter_mut().zip(Neighbourhood::MOORE_OFFSETS) {
+            if neighbourhood.includes(offset) {
+                *neighbour = self
+                    .wrapped_cell_coord(cell_coord.as_ivec2() + offset)
+                    .map(|neigh_pos| self.cell_coord_to_idx(neigh_pos));
+            }
+        }
+        result
+    }
+
+    /// Maps a cell coordinate that might lie outside of the board back onto it, wrapping around
+    /// the edges that wrap. Returns `None` if it lies beyond an edge that doesn't wrap.
+    #[inline]
+    pub fn wrapped_cell_coord(&self, cell_coord: IVec2) -> Option<UVec2> {
+        let size = self.size().as_ivec2();
+        let outside = cell_coord.cmplt(IVec2::ZERO) | cell_coord.cmpge(size);
+        if (outside.x && !self.wrap_x) || (outside.y && !self.wrap_y) {
+            return None;
+        }
+        Some(cell_coord.rem_euclid(size).as_uvec2())
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self {
+            center: BOARD_POS,
+            width: BOARD_SIZE,
+            height: BOARD_SIZE,
+            cell_size: CELL_SIZE_PX,
+            cell_scale: CELL_SCALE,
+            wrap_x: true,
+            wrap_y: true,
+        }
+    }
+}
+
+/// Seeded random boards for comparing different ways of stepping them, one per rulestring.
+///
+/// The rules cover both neighbourhoods and the boards wrap along a single axis, so mistakes
+/// in either direction of the wrapping show up.
+#[cfg(test)]
+pub(crate) fn seeded_test_boards() -> Vec<(&'static str, Board, Ruleset, Vec<bool>)> {
+    let mut rng = fastrand::Rng::with_seed(7);
+    [("B3/S23", true), ("B36/S23", false), ("B2/S013V", true)]
+        .into_iter()
+        .map(|(rulestring, wrap)| {
+            let board = Board {
+                width: 37,
+                height: 21,
+                wrap_x: wrap,
+                wrap_y: !wrap,
+                ..default()
+            };
+            let rules = Ruleset::from_rulestring(rulestring).unwrap();
+            let cells = (0..board.cell_count()).map(|_| rng.bool()).collect();
+            (rulestring, board, rules, cells)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::math::{vec2, vec3};
+
+    use super::*;
+
+    #[test]
+    fn board_works() {
+        let board = Board {
+            center: Vec2::ZERO,
+            cell_size: Vec2::splat(8.0),
+            cell_scale: Vec2::splat(0.9),
+            width: 8,
+            height: 4,
+            wrap_x: true,
+            wrap_y: true,
+        };
+
+        let px_size = board.pixel_size();
+        assert_eq!(vec2(64., 32.), px_size);
+        assert_eq!(32, board.cell_count());
+
+        let pos1_1 = uvec2(1, 1);
+        assert_eq!(9, board.cell_coord_to_idx(pos1_1));
+        assert_eq!(0, board.cell_coord_to_idx(uvec2(8, 4)));
+        assert_eq!(7, board.cell_coord_to_idx(uvec2(7, 4)));
+        assert_eq!(24, board.cell_coord_to_idx(uvec2(8, 3)));
+        assert_eq!(uvec2(7, 3), board.idx_to_cell_coord(31));
+        assert_eq!(uvec2(2, 1), board.idx_to_cell_coord(10));
+        assert_eq!(
+            vec3(-4.0, -4.0, 10.),
+            board.cell_coord_to_translation(uvec2(3, 1))
+        );
+
+        let neigh1_1 = board.neighbour_indices(pos1_1, Neighbourhood::Moore);
+        let expected_1_1 = [
+            Some(board.cell_coord_to_idx(uvec2(0, 0))),
+            Some(board.cell_coord_to_idx(uvec2(1, 0))),
+            Some(board.cell_coord_to_idx(uvec2(2, 0))),
+            Some(board.cell_coord_to_idx(uvec2(0, 1))),
+            Some(board.cell_coord_to_idx(uvec2(2, 1))),
+            Some(board.cell_coord_to_idx(uvec2(0, 2))),
+            Some(board.cell_coord_to_idx(uvec2(1, 2))),
+            Some(board.cell_coord_to_idx(uvec2(2, 2))),
+        ];
+        assert_eq!(expected_1_1, neigh1_1);
+
+        let neigh0_1 = board.neighbour_indices(uvec2(0, 1), Neighbourhood::Moore);
+        let expected_0_1 = [
+            Some(board.cell_coord_to_idx(uvec2(7, 0))),
+            Some(board.cell_coord_to_idx(uvec2(0, 0))),
+            Some(board.cell_coord_to_idx(uvec2(1, 0))),
+            Some(board.cell_coord_to_idx(uvec2(7, 1))),
+            Some(board.cell_coord_to_idx(uvec2(1, 1))),
+            Some(board.cell_coord_to_idx(uvec2(7, 2))),
+            Some(board.cell_coord_to_idx(uvec2(0, 2))),
+            Some(board.cell_coord_to_idx(uvec2(1, 2))),
+        ];
+        assert_eq!(expected_0_1, neigh0_1);
+
+        // the top row wraps around to the bottom one, after the board's height
+        let neigh1_3 = board.neighbour_indices(uvec2(1, 3), Neighbourhood::Moore);
+        let expected_1_3 = [
+            Some(board.cell_coord_to_idx(uvec2(0, 2))),
+            Some(board.cell_coord_to_idx(uvec2(1, 2))),
+            Some(board.cell_coord_to_idx(uvec2(2, 2))),
+            Some(board.cell_coord_to_idx(uvec2(0, 3))),
+            Some(board.cell_coord_to_idx(uvec2(2, 3))),
+            Some(board.cell_coord_to_idx(uvec2(0, 0))),
+            Some(board.cell_coord_to_idx(uvec2(1, 0))),
+            Some(board.cell_coord_to_idx(uvec2(2, 0))),
+        ];
+        assert_eq!(expected_1_3, neigh1_3);
+    }
+
+    #[test]
+    fn translation_to_cell_coord_round_trips() {
+        let board = Board {
+            center: vec2(100.0, -50.0),
+            cell_size: Vec2::splat(8.0),
+            cell_scale: Vec2::splat(0.9),
+            width: 8,
+            height: 4,
+            wrap_x: true,
+            wrap_y: true,
+        };
+
+        for cell_coord in [
+            uvec2(0, 0),
+            uvec2(7, 0),
+            uvec2(0, 3),
+            uvec2(7, 3),
+            uvec2(3, 0),
+            uvec2(0, 2),
+            uvec2(5, 2),
+        ] {
+            let translation = board.cell_coord_to_translation(cell_coord).truncate();
+            assert_eq!(
+                Some(cell_coord),
+                board.translation_to_cell_coord(translation)
+            );
+            // anywhere inside of the cell maps to the same coordinate
+            let corner = translation - board.cell_size * 0.5;
+            assert_eq!(Some(cell_coord), board.translation_to_cell_coord(corner));
+            let inner = translation + board.cell_size * 0.49;
+            assert_eq!(Some(cell_coord), board.translation_to_cell_coord(inner));
+        }
+
+        let bottom_left = board.center - board.pixel_size() * 0.5;
+        let top_right = board.center + board.pixel_size() * 0.5;
+        assert_eq!(None, board.translation_to_cell_coord(bottom_left - 0.1));
+        assert_eq!(None, board.translation_to_cell_coord(top_right));
+        assert_eq!(
+            None,
+            board.translation_to_cell_coord(vec2(board.center.x, top_right.y + 1.0))
+        );
+        assert_eq!(
+            None,
+            board.translation_to_cell_coord(vec2(bottom_left.x - 1.0, board.center.y))
+        );
+    }
+
+    #[test]
+    fn bounded_board_neighbours() {
+        let board = Board {
+            center: Vec2::ZERO,
+            cell_size: Vec2::splat(8.0),
+            cell_scale: Vec2::ONE,
+            width: 4,
+            height: 4,
+            wrap_x: false,
+            wrap_y: false,
+        };
+        let idx = |x, y| Some(board.cell_coord_to_idx(uvec2(x, y)));
+
+        // corners
+        let expected_0_0 = [
+            None,
+            None,
+            None,
+            None,
+            idx(1, 0),
+            None,
+            idx(0, 1),
+            idx(1, 1),
+        ];
+        assert_eq!(
+            expected_0_0,
+            board.neighbour_indices(uvec2(0, 0), Neighbourhood::Moore)
+        );
+        let expected_3_3 = [
+            idx(2, 2),
+            idx(3, 2),
+            None,
+            idx(2, 3),
+            None,
+            None,
+            None,
+            None,
+        ];
+        assert_eq!(
+            expected_3_3,
+            board.neighbour_indices(uvec2(3, 3), Neighbourhood::Moore)
+        );
+        // edges
+        let expected_0_2 = [
+            None,
+            idx(0, 1),
+            idx(1, 1),
+            None,
+            idx(1, 2),
+            None,
+            idx(0, 3),
+            idx(1, 3),
+        ];
+        assert_eq!(
+            expected_0_2,
+            board.neighbour_indices(uvec2(0, 2), Neighbourhood::Moore)
+        );
+        let expected_2_3 = [
+            idx(1, 2),
+            idx(2, 2),
+            idx(3, 2),
+            idx(1, 3),
+            idx(3, 3),
+            None,
+            None,
+            None,
+        ];
+        assert_eq!(
+            expected_2_3,
+            board.neighbour_indices(uvec2(2, 3), Neighbourhood::Moore)
+        );
+        // inner cells aren't affected
+        assert!(board
+            .neighbour_indices(uvec2(1, 1), Neighbourhood::Moore)
+            .iter()
+            .all(Option::is_some));
+
+        let torus = Board {
+            wrap_x: true,
+            wrap_y: true,
+            ..board
+        };
+        let expected_0_0 = [
+            idx(3, 3),
+            idx(0, 3),
+            idx(1, 3),
+            idx(3, 0),
+            idx(1, 0),
+            idx(3, 1),
+            idx(0, 1),
+            idx(1, 1),
+        ];
+        assert_eq!(
+            expected_0_0,
+            torus.neighbour_indices(uvec2(0, 0), Neighbourhood::Moore)
+        );
+        let expected_3_3 = [
+            idx(2, 2),
+            idx(3, 2),
+            idx(0, 2),
+            idx(2, 3),
+            idx(0, 3),
+            idx(2, 0),
+            idx(3, 0),
+            idx(0, 0),
+        ];
+        assert_eq!(
+            expected_3_3,
+            torus.neighbour_indices(uvec2(3, 3), Neighbourhood::Moore)
+        );
+        let expected_2_3 = [
+            idx(1, 2),
+            idx(2, 2),
+            idx(3, 2),
+            idx(1, 3),
+            idx(3, 3),
+            idx(1, 0),
+            idx(2, 0),
+            idx(3, 0),
+        ];
+        assert_eq!(
+            expected_2_3,
+            torus.neighbour_indices(uvec2(2, 3), Neighbourhood::Moore)
+        );
+        let expected_3_0 = [
+            idx(2, 3),
+            idx(3, 3),
+            idx(0, 3),
+            idx(2, 0),
+            idx(0, 0),
+            idx(2, 1),
+            idx(3, 1),
+            idx(0, 1),
+        ];
+        assert_eq!(
+            expected_3_0,
+            torus.neighbour_indices(uvec2(3, 0), Neighbourhood::Moore)
+        );
+        let expected_0_3 = [
+            idx(3, 2),
+            idx(0, 2),
+            idx(1, 2),
+            idx(3, 3),
+            idx(1, 3),
+            idx(3, 0),
+            idx(0, 0),
+            idx(1, 0),
+        ];
+        assert_eq!(
+            expected_0_3,
+            torus.neighbour_indices(uvec2(0, 3), Neighbourhood::Moore)
+        );
+    }
+
+    #[test]
+    fn cylinder_board_neighbours() {
+        let horizontal = Board {
+            width: 4,
+            height: 4,
+            wrap_x: true,
+            wrap_y: false,
+            ..default()
+        };
+        let idx = |x, y| Some(horizontal.cell_coord_to_idx(uvec2(x, y)));
+        let neighbours =
+            |board: &Board, x, y| board.neighbour_indices(uvec2(x, y), Neighbourhood::Moore);
+
+        // only the left and right edges are connected
+        let expected_0_0 = [
+            None,
+            None,
+            None,
+            idx(3, 0),
+            idx(1, 0),
+            idx(3, 1),
+            idx(0, 1),
+            idx(1, 1),
+        ];
+        assert_eq!(expected_0_0, neighbours(&horizontal, 0, 0));
+        let expected_3_3 = [
+            idx(2, 2),
+            idx(3, 2),
+            idx(0, 2),
+            idx(2, 3),
+            idx(0, 3),
+            None,
+            None,
+            None,
+        ];
+        assert_eq!(expected_3_3, neighbours(&horizontal, 3, 3));
+
+        // only the top and bottom edges are connected
+        let vertical = Board {
+            wrap_x: false,
+            wrap_y: true,
+            ..horizontal
+        };
+        let expected_0_0 = [
+            None,
+            idx(0, 3),
+            idx(1, 3),
+            None,
+            idx(1, 0),
+            None,
+            idx(0, 1),
+            idx(1, 1),
+        ];
+        assert_eq!(expected_0_0, neighbours(&vertical, 0, 0));
+        let expected_3_3 = [
+            idx(2, 2),
+            idx(3, 2),
+            None,
+            idx(2, 3),
+            None,
+            idx(2, 0),
+            idx(3, 0),
+            None,
+        ];
+        assert_eq!(expected_3_3, neighbours(&vertical, 3, 3));
+    }
+
+    #[test]
+    fn brush_covers_radius() {
+        let mut board = Board {
+            center: Vec2::ZERO,
+            cell_size: Vec2::splat(8.0),
+            cell_scale: Vec2::ONE,
+            width: 8,
+            height: 8,
+            wrap_x: false,
+            wrap_y: false,
+        };
+        let idx = |board: &Board, x, y| board.cell_coord_to_idx(uvec2(x, y));
+
+        assert_eq!(
+            vec![idx(&board, 3, 3)],
+            board.brush_indices(uvec2(3, 3), 0).collect::<Vec<_>>()
+        );
+        assert_eq!(9, board.brush_indices(uvec2(3, 3), 1).count());
+
+        // a bounded board clips the brush at the edge
+        let corner: Vec<_> = board.brush_indices(uvec2(0, 0), 1).collect();
+        assert_eq!(
+            vec![
+                idx(&board, 0, 0),
+                idx(&board, 1, 0),
+                idx(&board, 0, 1),
+                idx(&board, 1, 1)
+            ],
+            corner
+        );
+
+        // a wrapping board continues the brush on the other side
+        board.wrap_x = true;
+        board.wrap_y = true;
+        let corner: Vec<_> = board.brush_indices(uvec2(0, 0), 1).collect();
+        assert_eq!(9, corner.len());
+        assert!(corner.contains(&idx(&board, 7, 7)));
+        assert!(corner.contains(&idx(&board, 1, 7)));
+    }
+
+    #[test]
+    fn boards_step_without_the_plugin() {
+        let mut board = Board {
+            width: 6,
+            height: 5,
+            wrap_x: false,
+            wrap_y: false,
+            ..default()
+        };
+        let rules = Ruleset::default();
+        let alive = move |cells: &[UVec2]| {
+            let mut buffer = vec![false; board.cell_count()];
+            for &cell_coord in cells {
+                buffer[(cell_coord.y * board.width + cell_coord.x) as usize] = true;
+            }
+            buffer
+        };
+
+        // a blinker flips between a row and a column
+        let row = alive(&[uvec2(1, 2), uvec2(2, 2), uvec2(3, 2)]);
+        let column = alive(&[uvec2(2, 1), uvec2(2, 2), uvec2(2, 3)]);
+        let mut next = vec![false; board.cell_count()];
+        step_board(&row, &mut next, &board, &rules);
+        assert_eq!(column, next);
+        let current = next.clone();
+        step_board(&current, &mut next, &board, &rules);
+        assert_eq!(row, next);
+
+        // the row is only connected across the edge of a wrapping board
+        let edge_row = alive(&[uvec2(5, 2), uvec2(0, 2), uvec2(1, 2)]);
+        step_board(&edge_row, &mut next, &board, &rules);
+        assert_eq!(alive(&[]), next);
+        board.wrap_x = true;
+        board.wrap_y = true;
+        step_board(&edge_row, &mut next, &board, &rules);
+        assert_eq!(alive(&[uvec2(0, 1), uvec2(0, 2), uvec2(0, 3)]), next);
+    }
+
+    #[test]
+    #[should_panic(expected = "next buffer size")]
+    fn stepping_needs_matching_buffers() {
+        let board = Board::default();
+        let current = vec![false; board.cell_count()];
+        step_board(&current, &mut [false; 4], &board, &Ruleset::default());
+    }
+
+    #[test]
+    fn neighbourhoods_count_their_cells() {
+        let board = Board {
+            center: Vec2::ZERO,
+            cell_size: Vec2::splat(8.0),
+            cell_scale: Vec2::ONE,
+            width: 5,
+            height: 5,
+            wrap_x: false,
+            wrap_y: false,
+        };
+        let center = uvec2(2, 2);
+        let neighbours = |neighbourhood| {
+            board
+                .neighbour_indices(center, neighbourhood)
+                .into_iter()
+                .flatten()
+                .map(|idx| board.idx_to_cell_coord(idx))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(8, neighbours(Neighbourhood::Moore).len());
+        assert_eq!(
+            vec![uvec2(2, 1), uvec2(1, 2), uvec2(3, 2), uvec2(2, 3)],
+            neighbours(Neighbourhood::VonNeumann)
+        );
+
+        // a single cell gives birth to all of its neighbours with B1/S
+        let births = |rulestring| {
+            let mut grid = LifeGrid::new(board.cell_count());
+            grid.cells[board.cell_coord_to_idx(center)] = true;
+            grid.compute_next(&board, &Ruleset::from_rulestring(rulestring).unwrap());
+            grid.swap();
+            grid.cells
+                .iter()
+                .enumerate()
+                .filter(|(_, alive)| **alive)
+                .map(|(idx, _)| board.idx_to_cell_coord(idx))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(neighbours(Neighbourhood::Moore), births("B1/S"));
+        assert_eq!(neighbours(Neighbourhood::VonNeumann), births("B1/SV"));
+    }
+
+    #[test]
+    fn dying_cells_decay() {
+        let board = Board {
+            center: Vec2::ZERO,
+            cell_size: Vec2::splat(8.0),
+            cell_scale: Vec2::ONE,
+            width: 5,
+            height: 5,
+            wrap_x: false,
+            wrap_y: false,
+        };
+        let idx = |x, y| board.cell_coord_to_idx(uvec2(x, y));
+        let rules = Ruleset::from_rulestring("B2/S/4").unwrap();
+        let step = |grid: &mut LifeGrid| {
+            grid.compute_next(&board, &rules);
+            grid.swap();
+            grid.decay_cells(rules.states);
+        };
+        let mut grid = LifeGrid::new(board.cell_count());
+        grid.cells[idx(2, 1)] = true;
+        grid.cells[idx(2, 3)] = true;
+        grid.decay[idx(2, 2)] = 1;
+
+        // the decaying cell between the live ones isn't born, unlike the dead ones next to it
+        assert!(!grid.next_alive(&board, &rules, idx(2, 2)));
+        step(&mut grid);
+        assert!(!grid.cells[idx(2, 2)]);
+        assert!(grid.cells[idx(1, 2)] && grid.cells[idx(3, 2)]);
+        assert_eq!(2, grid.decay[idx(2, 2)]);
+        assert_eq!(1, grid.decay[idx(2, 1)]);
+
+        // the cells are dead after passing through the 2 decaying states
+        step(&mut grid);
+        assert_eq!(0, grid.decay[idx(2, 2)]);
+        assert_eq!(2, grid.decay[idx(2, 1)]);
+        assert_eq!(1, grid.decay[idx(1, 2)]);
+
+        // cells die right away with life-like rules
+        let mut grid = LifeGrid::new(board.cell_count());
+        grid.cells[idx(2, 2)] = true;
+        grid.compute_next(&board, &Ruleset::default());
+        grid.swap();
+        grid.decay_cells(Ruleset::default().states);
+        assert!(grid.decay.iter().all(|&decay| decay == 0));
+    }
+
+    #[test]
+    fn life_grid_steps() {
+        let board = Board {
+            center: Vec2::ZERO,
+            cell_size: Vec2::splat(8.0),
+            cell_scale: Vec2::ONE,
+            width: 5,
+            height: 5,
+            wrap_x: true,
+            wrap_y: true,
+        };
+        let rules = Ruleset::default();
+        let cells_of = |live_cells: &[UVec2]| {
+            let mut cells = vec![false; 25];
+            for &cell in live_cells {
+                cells[board.cell_coord_to_idx(cell)] = true;
+            }
+            cells
+        };
+        let step = |grid: &mut LifeGrid, board: &Board| {
+            grid.compute_next(board, &rules);
+            grid.swap();
+        };
+
+        // a blinker oscillates
+        let horizontal = cells_of(&[uvec2(1, 2), uvec2(2, 2), uvec2(3, 2)]);
+        let vertical = cells_of(&[uvec2(2, 1), uvec2(2, 2), uvec2(2, 3)]);
+        let mut grid = LifeGrid::new(board.cell_count());
+        grid.cells = horizontal.clone();
+        let live_neighbours = |x, y| {
+            grid.live_neighbours(
+                &board,
+                Neighbourhood::Moore,
+                board.cell_coord_to_idx(uvec2(x, y)),
+            )
+        };
+        assert_eq!(2, live_neighbours(2, 2));
+        assert_eq!(1, live_neighbours(1, 2));
+        assert_eq!(3, live_neighbours(2, 1));
+        assert_eq!(0, live_neighbours(0, 0));
+        step(&mut grid, &board);
+        assert_eq!(vertical, grid.cells);
+        step(&mut grid, &board);
+        assert_eq!(horizontal, grid.cells);
+
+        // only the center of the blinker stays alive
+        grid.age_cells();
+        grid.age_cells();
+        let center = board.cell_coord_to_idx(uvec2(2, 2));
+        assert_eq!(2, grid.ages[center]);
+        grid.compute_next(&board, &rules);
+        grid.swap();
+        grid.age_cells();
+        assert_eq!(3, grid.ages[center]);
+        assert_eq!(0, grid.ages[board.cell_coord_to_idx(uvec2(1, 2))]);
+        assert_eq!(1, grid.ages[board.cell_coord_to_idx(uvec2(2, 1))]);
+
+        // editing the center starts a new life, the cells that stay alive keep their age
+        let unedited = grid.clone();
+        grid.set_alive(center, false);
+        grid.set_alive(center, true);
+        assert_eq!(0, grid.ages[center]);
+        grid = unedited.clone();
+        let mut edited = grid.cells.clone();
+        edited[center] = false;
+        let previous = grid.replace(edited);
+        grid.replace(previous);
+        assert_eq!(0, grid.ages[center]);
+        assert_eq!(1, grid.ages[board.cell_coord_to_idx(uvec2(2, 1))]);
+        grid = unedited;
+
+        grid.track_changes(7);
+        assert_eq!(None, grid.last_changed[center]);
+        assert_eq!(
+            Some(7),
+            grid.last_changed[board.cell_coord_to_idx(uvec2(1, 2))]
+        );
+
+        // a blinker on the edge only survives on a wrapping board
+        let edge = cells_of(&[uvec2(4, 1), uvec2(4, 2), uvec2(4, 3)]);
+        grid.cells = edge.clone();
+        step(&mut grid, &board);
+        assert_eq!(3, grid.population());
+
+        let bounded = Board {
+            wrap_x: false,
+            wrap_y: false,
+            ..board
+        };
+        grid.cells = edge;
+        step(&mut grid, &bounded);
+        assert_eq!(2, grid.population());
+    }
+}
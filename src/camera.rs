@@ -1,7 +1,20 @@
-use bevy::prelude::*;
-use bevy_pancam::{PanCam, PanCamPlugin};
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowResized},
+};
+use bevy_pancam::{DirectionKeys, PanCam, PanCamPlugin};
 
-use crate::{prelude::BG_COLOR, state::GameState};
+use crate::{
+    bindings::{action_just_pressed, Action, KeyBindings},
+    board::{Board, LifeGrid},
+    life::CursorCell,
+    prelude::*,
+    state::GameState,
+};
+
+/// The anti-aliasing settings that are cycled through, the square cells stay crisp without any
+/// but the outlines drawn over them are smoother with it.
+pub const MSAA_SETTINGS: [Msaa; 3] = [Msaa::Off, Msaa::Sample2, Msaa::Sample4];
 
 pub struct CamPlugin;
 
@@ -9,7 +22,33 @@ impl Plugin for CamPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(PanCamPlugin)
             .insert_resource(ClearColor(BG_COLOR))
-            .add_systems(OnEnter(GameState::Load), spawn_cam);
+            .init_resource::<KeyBindings>()
+            .init_resource::<PanButton>()
+            .init_resource::<FittedScale>()
+            .add_systems(
+                OnEnter(GameState::Load),
+                (spawn_cam, fit_cam_to_board, update_zoom_bounds).chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    fit_cam_to_board.run_if(
+                        action_just_pressed(Action::FitCamera)
+                            .or(on_event::<WindowResized>.and(cam_is_fitted)),
+                    ),
+                    frame_live_cells.run_if(
+                        action_just_pressed(Action::FrameLiveCells)
+                            .and(resource_exists::<LifeGrid>),
+                    ),
+                    update_zoom_bounds
+                        .run_if(resource_changed::<Board>.or(on_event::<WindowResized>)),
+                    update_pan_grab_buttons
+                        .run_if(resource_changed::<PanButton>.or(state_changed::<GameState>)),
+                    update_pan_move_keys
+                        .run_if(resource_added::<CursorCell>.or(resource_removed::<CursorCell>)),
+                    cycle_msaa.run_if(action_just_pressed(Action::CycleMsaa)),
+                ),
+            );
     }
 }
 
@@ -19,6 +58,9 @@ fn spawn_cam(mut commands: Commands) {
         Camera2d,
         PanCam {
             grab_buttons: vec![],
+            // wasd would clash with the key bindings
+            move_keys: DirectionKeys::arrows(),
+            zoom_to_cursor: true,
             ..default()
         },
         OrthographicProjection {
@@ -31,3 +73,223 @@ fn spawn_cam(mut commands: Commands) {
         Msaa::Off,
     ));
 }
+
+/// centers the camera on the board and zooms it so that the whole board fits into the window
+fn fit_cam_to_board(
+    mut cam_query: Query<(&mut Transform, &mut OrthographicProjection), With<Camera2d>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    board: Res<Board>,
+    mut fitted_scale: ResMut<FittedScale>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let scale = fit_scale(board.pixel_size(), window.size());
+    for (mut transform, mut projection) in cam_query.iter_mut() {
+        transform.translation = board.center.extend(transform.translation.z);
+        projection.scale = scale;
+    }
+    **fitted_scale = Some(scale);
+}
+
+/// centers the camera on the live cells and zooms it so that they fill the window, or fits the
+/// whole board when there are none
+fn frame_live_cells(
+    mut cam_query: Query<(&mut Transform, &mut OrthographicProjection, &PanCam), With<Camera2d>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    board: Res<Board>,
+    grid: Res<LifeGrid>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let (min, max) = grid
+        .live_bounds(&board)
+        .unwrap_or((UVec2::ZERO, board.size() - 1));
+    let rect = cells_rect(&board, min, max);
+    let scale = fit_scale(rect.size(), window.size());
+    for (mut transform, mut projection, pan_cam) in cam_query.iter_mut() {
+        transform.translation = rect.center().extend(transform.translation.z);
+        projection.scale = scale.clamp(pan_cam.min_scale, pan_cam.max_scale);
+    }
+}
+
+/// whether the camera hasn't been zoomed since it was last fitted to the board
+fn cam_is_fitted(
+    cam_query: Query<&OrthographicProjection, With<Camera2d>>,
+    fitted_scale: Res<FittedScale>,
+) -> bool {
+    cam_query
+        .iter()
+        .all(|projection| Some(projection.scale) == **fitted_scale)
+}
+
+/// limits the zoom based on the size of the board, see `zoom_bounds`
+fn update_zoom_bounds(
+    mut cam_query: Query<&mut PanCam>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    board: Res<Board>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let (min_scale, max_scale) = zoom_bounds(&board, window.size());
+    for mut pan_cam in cam_query.iter_mut() {
+        pan_cam.min_scale = min_scale;
+        pan_cam.max_scale = max_scale;
+    }
+}
+
+fn update_pan_grab_buttons(
+    mut cam_query: Query<&mut PanCam>,
+    pan_button: Res<PanButton>,
+    state: Res<State<GameState>>,
+) {
+    for mut pan_cam in cam_query.iter_mut() {
+        pan_cam.grab_buttons = pan_button.grab_buttons(*state.get());
+    }
+}
+
+/// the arrow keys move the keyboard cursor instead of the camera while it's shown
+fn update_pan_move_keys(mut cam_query: Query<&mut PanCam>, cursor: Option<Res<CursorCell>>) {
+    for mut pan_cam in cam_query.iter_mut() {
+        pan_cam.move_keys = if cursor.is_some() {
+            DirectionKeys::NONE
+        } else {
+            DirectionKeys::arrows()
+        };
+    }
+}
+
+fn cycle_msaa(mut cam_query: Query<&mut Msaa, With<Camera2d>>) {
+    for mut msaa in cam_query.iter_mut() {
+        *msaa = cycle_next(&MSAA_SETTINGS, *msaa);
+        info!("anti-aliasing: {:?}", *msaa);
+    }
+}
+
+/// the area covered by the cells between the bottom left and top right corners
+fn cells_rect(board: &Board, min: UVec2, max: UVec2) -> Rect {
+    let half_cell = board.cell_size * 0.5;
+    Rect::from_corners(
+        board.cell_coord_to_translation(min).truncate() - half_cell,
+        board.cell_coord_to_translation(max).truncate() + half_cell,
+    )
+}
+
+/// the projection scale at which the board, its border and some padding fill the window
+fn fit_scale(board_pixel_size: Vec2, window_size: Vec2) -> f32 {
+    let needed = board_pixel_size + 2.0 * (BORDER_WIDTH_PX + CAMERA_FIT_PADDING_PX);
+    (needed / window_size.max(Vec2::ONE)).max_element()
+}
+
+/// the scale the camera was last fitted to the board with, see `fit_cam_to_board`
+#[derive(Resource, Debug, Default, Clone, Copy, Deref, DerefMut)]
+struct FittedScale(Option<f32>);
+
+/// the mouse button that pans the camera when dragged
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Deref, DerefMut)]
+pub struct PanButton(pub MouseButton);
+
+impl PanButton {
+    /// The left and right buttons paint cells in setup, so they can't be used for panning there.
+    fn grab_buttons(self, state: GameState) -> Vec<MouseButton> {
+        let paints_cells = matches!(self.0, MouseButton::Left | MouseButton::Right);
+        if paints_cells && state == GameState::Setup {
+            vec![]
+        } else {
+            vec![self.0]
+        }
+    }
+}
+
+impl Default for PanButton {
+    fn default() -> Self {
+        Self(MouseButton::Middle)
+    }
+}
+
+/// Returns the minimum and maximum projection scale of the camera.
+///
+/// Zooming in stops once a single cell fills the window, zooming out stops once the board is
+/// `CAMERA_MAX_ZOOM_OUT` times smaller than when fitted to the window.
+pub fn zoom_bounds(board: &Board, window_size: Vec2) -> (f32, f32) {
+    let min_scale = board.cell_size.max_element() / window_size.max(Vec2::ONE).min_element();
+    let max_scale = fit_scale(board.pixel_size(), window_size) * CAMERA_MAX_ZOOM_OUT;
+    (min_scale, max_scale)
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::math::{uvec2, vec2};
+
+    use super::*;
+
+    #[test]
+    fn board_fits_into_window() {
+        let padding = 2.0 * (BORDER_WIDTH_PX + CAMERA_FIT_PADDING_PX);
+        // the board is limited by the window's height
+        let scale = fit_scale(Vec2::splat(1000.0), vec2(1600.0, 1000.0 + padding));
+        assert_eq!(1.0, scale);
+        // a small board gets zoomed in
+        let scale = fit_scale(Vec2::splat(100.0), Vec2::splat(2.0 * (100.0 + padding)));
+        assert_eq!(0.5, scale);
+    }
+
+    #[test]
+    fn live_cells_are_framed() {
+        let mut board = Board::default();
+        (board.width, board.height) = (10, 6);
+        board.cell_size = Vec2::splat(10.0);
+        board.center = vec2(20.0, -5.0);
+
+        // the whole board when there are no live cells
+        let rect = cells_rect(&board, UVec2::ZERO, board.size() - 1);
+        assert_eq!(
+            Rect::from_center_size(board.center, board.pixel_size()),
+            rect
+        );
+
+        // the board's bottom left corner is at (-30, -35)
+        let rect = cells_rect(&board, uvec2(1, 2), uvec2(3, 2));
+        assert_eq!(Rect::new(-20.0, -15.0, 10.0, -5.0), rect);
+    }
+
+    #[test]
+    fn zoom_is_bounded_by_the_board() {
+        let mut board = Board::default();
+        (board.width, board.height) = (10, 10);
+        board.cell_size = Vec2::splat(10.0);
+        let window_size = vec2(800.0, 600.0);
+        let (min_scale, max_scale) = zoom_bounds(&board, window_size);
+        // a single cell fills the window's height
+        assert_eq!(board.cell_size.y / window_size.y, min_scale);
+        assert_eq!(
+            fit_scale(board.pixel_size(), window_size) * CAMERA_MAX_ZOOM_OUT,
+            max_scale
+        );
+    }
+
+    #[test]
+    fn msaa_settings_cycle() {
+        assert_eq!(Msaa::Sample2, cycle_next(&MSAA_SETTINGS, Msaa::Off));
+        assert_eq!(Msaa::Off, cycle_next(&MSAA_SETTINGS, Msaa::Sample4));
+        assert_eq!(Msaa::Off, cycle_next(&MSAA_SETTINGS, Msaa::Sample8));
+    }
+
+    #[test]
+    fn pan_button_does_not_paint() {
+        let middle = PanButton::default();
+        assert_eq!(
+            vec![MouseButton::Middle],
+            middle.grab_buttons(GameState::Setup)
+        );
+
+        let left = PanButton(MouseButton::Left);
+        assert!(left.grab_buttons(GameState::Setup).is_empty());
+        assert_eq!(
+            vec![MouseButton::Left],
+            left.grab_buttons(GameState::Running)
+        );
+    }
+}
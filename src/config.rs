@@ -0,0 +1,498 @@
+use std::{
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use bevy::{
+    math::uvec2,
+    prelude::*,
+    window::{MonitorSelection, PresentMode, WindowMode, WindowResolution},
+};
+use serde::Deserialize;
+
+use crate::{
+    prelude::*,
+    rules::{RuleParseError, Ruleset},
+    theme::Theme,
+};
+
+/// the pattern `--demo` runs, on the default board the gliders wrap around the edges instead
+/// of piling up against them
+pub const DEMO_PATTERN: &str = "gosper_glider_gun";
+
+/// the config file read at startup and reloaded with `Action::ReloadConfig`, see `ConfigFile`
+pub const CONFIG_FILE_PATH: &str = "gol.toml";
+
+pub const USAGE: &str =
+    "usage: conway-gol-bevy [--size <cells|WxH>] [--interval-ms <ms>] [--rule <B../S..>] \
+     [--seed <u64>] [--pause-on-blur <true|false>] [--compare <B../S..>]... \
+     [--window <windowed|maximized|fullscreen>] [--load <pattern name|file>] [--autorun] [--demo] \
+     [--stop-on-extinction <true|false>] [--max-population <cells>] \
+     [--population-csv <file>] [--cursor-picking] [--max-steps-per-frame <steps>]";
+
+/// Startup configuration of the game, usually read from `CONFIG_FILE_PATH` and the command
+/// line.
+///
+/// Insert it as a resource before adding the `LifePlugin` to override the defaults.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Config {
+    /// the amount of cells along the x and y axes of the board
+    pub board_size: UVec2,
+    /// whether the edges of the board wrap around
+    pub wrap: bool,
+    /// the interval between generations while running
+    pub update_interval_ms: u64,
+    pub ruleset: Ruleset,
+    /// the seed used for randomizing the board, a random one if `None`
+    pub seed: Option<u64>,
+    /// whether the simulation pauses while the window isn't focused
+    pub pause_on_focus_loss: bool,
+    /// rulesets of the boards run next to the main one, starting from the same cells
+    pub compared_rulesets: Vec<Ruleset>,
+    /// how the window is shown when the game starts
+    pub window_mode: WindowStartMode,
+    /// a built-in pattern (see `pattern::library_pattern`) or a pattern file stamped in the
+    /// center of the board at startup
+    pub startup_pattern: Option<String>,
+    /// whether the simulation starts running right away instead of being set up
+    pub autorun: bool,
+    /// whether the simulation stops once all of the cells died
+    pub stop_on_extinction: bool,
+    /// the simulation stops once more cells than this are alive
+    pub max_population: Option<u32>,
+    /// a CSV file the population of each generation is appended to while running
+    pub population_csv: Option<PathBuf>,
+    /// whether the cells are picked from the cursor's position on the board instead of by
+    /// casting rays against every cell's mesh, which is much cheaper on large boards
+    pub cursor_picking: bool,
+    /// the most generations advanced in a single frame while running, see
+    /// `life::MaxStepsPerFrame`
+    pub max_steps_per_frame: u32,
+    pub theme: Theme,
+    /// percentage of the cells that are alive after randomizing
+    pub seed_density: u32,
+}
+
+impl Config {
+    /// Parses arguments like `--size 256x128 --interval-ms 50 --rule B36/S23 --seed 42`, without
+    /// the program name. Options that aren't given keep their default values.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Config, ConfigError> {
+        Config::default().with_args(args)
+    }
+
+    /// Overrides the options given in the arguments, see `from_args`.
+    pub fn with_args(self, args: impl IntoIterator<Item = String>) -> Result<Config, ConfigError> {
+        let mut config = self;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            // flags don't take a value
+            match arg.as_str() {
+                "--autorun" => {
+                    config.autorun = true;
+                    continue;
+                }
+                // runs the `DEMO_PATTERN`, like `--load gosper_glider_gun --autorun`
+                "--demo" => {
+                    config.startup_pattern = Some(DEMO_PATTERN.to_owned());
+                    config.autorun = true;
+                    continue;
+                }
+                "--cursor-picking" => {
+                    config.cursor_picking = true;
+                    continue;
+                }
+                _ => {}
+            }
+            let value = args
+                .next()
+                .ok_or_else(|| ConfigError::MissingValue(arg.clone()))?;
+            let invalid = || ConfigError::InvalidValue(arg.clone(), value.clone());
+            match arg.as_str() {
+                "--size" => {
+                    config.board_size = parse_board_size(&value).ok_or_else(invalid)?;
+                }
+                "--interval-ms" => {
+                    config.update_interval_ms = value.parse().map_err(|_| invalid())?;
+                    if !(MIN_UPDATE_INTERVAL_MS..=MAX_UPDATE_INTERVAL_MS)
+                        .contains(&config.update_interval_ms)
+                    {
+                        return Err(invalid());
+                    }
+                }
+                "--rule" => config.ruleset = Ruleset::from_rulestring(&value)?,
+                "--seed" => config.seed = Some(value.parse().map_err(|_| invalid())?),
+                "--compare" => config
+                    .compared_rulesets
+                    .push(Ruleset::from_rulestring(&value)?),
+                "--window" => {
+                    config.window_mode = match value.as_str() {
+                        "windowed" => WindowStartMode::Windowed,
+                        "maximized" => WindowStartMode::Maximized,
+                        "fullscreen" => WindowStartMode::Fullscreen,
+                        _ => return Err(invalid()),
+                    };
+                }
+                "--pause-on-blur" => {
+                    config.pause_on_focus_loss = value.parse().map_err(|_| invalid())?;
+                }
+                "--load" => config.startup_pattern = Some(value),
+                "--stop-on-extinction" => {
+                    config.stop_on_extinction = value.parse().map_err(|_| invalid())?;
+                }
+                "--max-population" => {
+                    config.max_population = Some(value.parse().map_err(|_| invalid())?);
+                }
+                "--population-csv" => config.population_csv = Some(PathBuf::from(value)),
+                "--max-steps-per-frame" => {
+                    config.max_steps_per_frame = value.parse().map_err(|_| invalid())?;
+                    if config.max_steps_per_frame == 0 {
+                        return Err(invalid());
+                    }
+                }
+                _ => return Err(ConfigError::UnknownArgument(arg)),
+            }
+        }
+        Ok(config)
+    }
+
+    /// the primary window, shown as the `window_mode` asks for
+    pub fn primary_window(&self) -> Window {
+        let mut window = Window {
+            resizable: true,
+            focused: true,
+            present_mode: PresentMode::AutoNoVsync,
+            mode: WindowMode::Windowed,
+            resolution: WindowResolution::new(WINDOW_SIZE_PX.x, WINDOW_SIZE_PX.y),
+            ..default()
+        };
+        match self.window_mode {
+            WindowStartMode::Windowed => {}
+            WindowStartMode::Maximized => window.set_maximized(true),
+            WindowStartMode::Fullscreen => {
+                window.mode = WindowMode::BorderlessFullscreen(MonitorSelection::Current);
+            }
+        }
+        window
+    }
+}
+
+/// How the window is shown at startup, the camera gets fitted to the board once the window
+/// has its actual size.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum WindowStartMode {
+    /// a window of `WINDOW_SIZE_PX`
+    #[default]
+    Windowed,
+    Maximized,
+    /// borderless fullscreen on the current monitor
+    Fullscreen,
+}
+
+/// The settings of `CONFIG_FILE_PATH`, all of them optional:
+///
+/// ```toml
+/// size = "160x90"
+/// interval_ms = 50
+/// rule = "B36/S23"
+/// theme = "ocean"
+/// density = 30
+/// ```
+///
+/// The command line overrides the file at startup. Reloading it applies the rule, interval,
+/// theme and density right away, a new size rebuilds the board and is only applied in setup.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigFile {
+    /// the amount of cells along both axes or the width and height like `160x90`
+    pub size: Option<String>,
+    pub interval_ms: Option<u64>,
+    pub rule: Option<String>,
+    /// the name of one of the `Theme::PRESETS`
+    pub theme: Option<String>,
+    /// the seeding density in percent
+    pub density: Option<u32>,
+}
+
+impl ConfigFile {
+    pub fn parse(contents: &str) -> Result<ConfigFile, ConfigError> {
+        toml::from_str(contents).map_err(|e| ConfigError::InvalidFile(e.message().to_owned()))
+    }
+
+    /// reads the config file at `path`, `None` if there is none
+    pub fn read(path: impl AsRef<Path>) -> Result<Option<ConfigFile>, ConfigError> {
+        match fs::read_to_string(path) {
+            Ok(contents) => ConfigFile::parse(&contents).map(Some),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(ConfigError::InvalidFile(e.to_string())),
+        }
+    }
+
+    /// Overrides the settings present in the file, the others keep their values.
+    pub fn apply(&self, mut config: Config) -> Result<Config, ConfigError> {
+        let invalid = |key: &str, value: &dyn fmt::Display| {
+            ConfigError::InvalidValue(key.to_owned(), value.to_string())
+        };
+        if let Some(size) = &self.size {
+            config.board_size = parse_board_size(size).ok_or_else(|| invalid("size", size))?;
+        }
+        if let Some(interval_ms) = self.interval_ms {
+            if !(MIN_UPDATE_INTERVAL_MS..=MAX_UPDATE_INTERVAL_MS).contains(&interval_ms) {
+                return Err(invalid("interval_ms", &interval_ms));
+            }
+            config.update_interval_ms = interval_ms;
+        }
+        if let Some(rule) = &self.rule {
+            config.ruleset = Ruleset::from_rulestring(rule)?;
+        }
+        if let Some(theme) = &self.theme {
+            config.theme = Theme::preset(theme).ok_or_else(|| invalid("theme", theme))?;
+        }
+        if let Some(density) = self.density {
+            if !(1..=100).contains(&density) {
+                return Err(invalid("density", &density));
+            }
+            config.seed_density = density;
+        }
+        Ok(config)
+    }
+}
+
+/// parses either a single size for a square board or the width and height like `160x90`
+fn parse_board_size(value: &str) -> Option<UVec2> {
+    let (width, height) = value.split_once('x').unwrap_or((value, value));
+    let size = uvec2(width.parse().ok()?, height.parse().ok()?);
+    let in_range = |len| (MIN_BOARD_SIZE..=MAX_BOARD_SIZE).contains(&len);
+    (in_range(size.x) && in_range(size.y)).then_some(size)
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            board_size: UVec2::splat(BOARD_SIZE),
+            wrap: true,
+            update_interval_ms: UPDATE_INTERVAL_MS,
+            ruleset: Ruleset::default(),
+            seed: None,
+            pause_on_focus_loss: false,
+            compared_rulesets: Vec::new(),
+            window_mode: WindowStartMode::Windowed,
+            startup_pattern: None,
+            autorun: false,
+            stop_on_extinction: true,
+            max_population: None,
+            population_csv: None,
+            cursor_picking: false,
+            max_steps_per_frame: MAX_STEPS_PER_FRAME,
+            theme: Theme::default(),
+            seed_density: SEED_DENSITY,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    UnknownArgument(String),
+    /// an option without a value
+    MissingValue(String),
+    /// an option and its unparsable / out of range value
+    InvalidValue(String, String),
+    InvalidRule(RuleParseError),
+    /// a config file that can't be read or isn't valid TOML
+    InvalidFile(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::UnknownArgument(arg) => write!(f, "unknown argument '{arg}'"),
+            ConfigError::MissingValue(arg) => write!(f, "missing a value for '{arg}'"),
+            ConfigError::InvalidValue(arg, value) => match arg.as_str() {
+                "--size" | "size" => write!(
+                    f,
+                    "invalid board size '{value}', expected {MIN_BOARD_SIZE} - {MAX_BOARD_SIZE} \
+                     cells or <width>x<height>"
+                ),
+                "--interval-ms" | "interval_ms" => write!(
+                    f,
+                    "invalid interval '{value}', expected {MIN_UPDATE_INTERVAL_MS} - \
+                     {MAX_UPDATE_INTERVAL_MS}ms"
+                ),
+                _ => write!(f, "invalid value '{value}' for '{arg}'"),
+            },
+            ConfigError::InvalidRule(e) => write!(f, "{e}"),
+            ConfigError::InvalidFile(e) => write!(f, "invalid {CONFIG_FILE_PATH}: {e}"),
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConfigError::InvalidRule(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<RuleParseError> for ConfigError {
+    fn from(e: RuleParseError) -> Self {
+        ConfigError::InvalidRule(e)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::pattern;
+
+    fn parse(args: &str) -> Result<Config, ConfigError> {
+        Config::from_args(args.split_whitespace().map(str::to_owned))
+    }
+
+    #[test]
+    fn args_parse() {
+        assert_eq!(Ok(Config::default()), parse(""));
+
+        let config =
+            parse("--size 256 --interval-ms 50 --rule B36/S23 --seed 42 --pause-on-blur true")
+                .unwrap();
+        assert_eq!(UVec2::splat(256), config.board_size);
+        assert_eq!(uvec2(160, 90), parse("--size 160x90").unwrap().board_size);
+        assert_eq!(50, config.update_interval_ms);
+        assert_eq!(Ruleset::from_rulestring("B36/S23").unwrap(), config.ruleset);
+        assert_eq!(Some(42), config.seed);
+        assert!(config.pause_on_focus_loss);
+
+        let config = parse("--window fullscreen").unwrap();
+        assert_eq!(WindowStartMode::Fullscreen, config.window_mode);
+        assert!(matches!(
+            config.primary_window().mode,
+            WindowMode::BorderlessFullscreen(_)
+        ));
+        assert_eq!(
+            WindowStartMode::Maximized,
+            parse("--window maximized").unwrap().window_mode
+        );
+
+        let config = parse("--load gosper_glider_gun --autorun --seed 3").unwrap();
+        assert_eq!(Some("gosper_glider_gun".to_owned()), config.startup_pattern);
+        assert!(config.autorun);
+        assert_eq!(Some(3), config.seed);
+        let config = parse("--demo").unwrap();
+        assert_eq!(Some(DEMO_PATTERN.to_owned()), config.startup_pattern);
+        assert!(config.autorun);
+        assert!(pattern::library_pattern(DEMO_PATTERN).is_some());
+        let config = parse("--cursor-picking --size 512").unwrap();
+        assert!(config.cursor_picking);
+        assert_eq!(UVec2::splat(512), config.board_size);
+        assert_eq!(
+            3,
+            parse("--max-steps-per-frame 3")
+                .unwrap()
+                .max_steps_per_frame
+        );
+
+        let config = parse("--stop-on-extinction false --max-population 5000").unwrap();
+        assert!(!config.stop_on_extinction);
+        assert_eq!(Some(5000), config.max_population);
+        assert_eq!(
+            Some(PathBuf::from("population.csv")),
+            parse("--population-csv population.csv")
+                .unwrap()
+                .population_csv
+        );
+
+        let config = parse("--compare B36/S23 --compare B2/S").unwrap();
+        assert_eq!(
+            vec![
+                Ruleset::from_rulestring("B36/S23").unwrap(),
+                Ruleset::from_rulestring("B2/S").unwrap()
+            ],
+            config.compared_rulesets
+        );
+    }
+
+    #[test]
+    fn invalid_args_fail() {
+        assert_eq!(
+            Err(ConfigError::UnknownArgument("--wrap".to_owned())),
+            parse("--wrap false")
+        );
+        assert_eq!(
+            Err(ConfigError::MissingValue("--size".to_owned())),
+            parse("--size")
+        );
+        for (arg, value) in [
+            ("--size", "0"),
+            ("--size", "-3"),
+            ("--size", "100000"),
+            ("--size", "64x"),
+            ("--size", "64x2"),
+            ("--size", "8x8x8"),
+            ("--interval-ms", "0"),
+            ("--interval-ms", "fast"),
+            ("--seed", "-1"),
+            ("--pause-on-blur", "yes"),
+            ("--max-population", "lots"),
+            ("--max-steps-per-frame", "0"),
+            ("--window", "tiny"),
+        ] {
+            assert_eq!(
+                Err(ConfigError::InvalidValue(arg.to_owned(), value.to_owned())),
+                parse(&format!("{arg} {value}"))
+            );
+        }
+        assert!(matches!(
+            parse("--rule B3"),
+            Err(ConfigError::InvalidRule(_))
+        ));
+    }
+
+    #[test]
+    fn config_file_parses() {
+        let file = ConfigFile::parse(
+            "size = \"160x90\"\ninterval_ms = 50\nrule = \"B36/S23\"\ntheme = \"ocean\"\n\
+             density = 30",
+        )
+        .unwrap();
+        let config = file.apply(Config::default()).unwrap();
+        assert_eq!(uvec2(160, 90), config.board_size);
+        assert_eq!(50, config.update_interval_ms);
+        assert_eq!(Ruleset::from_rulestring("B36/S23").unwrap(), config.ruleset);
+        assert_eq!(Theme::OCEAN, config.theme);
+        assert_eq!(30, config.seed_density);
+
+        // missing settings keep their values and the arguments override the file
+        let config = ConfigFile::parse("theme = \"ember\"")
+            .unwrap()
+            .apply(Config::default())
+            .unwrap();
+        assert_eq!(Config::default().board_size, config.board_size);
+        let config = config.with_args(["--size".to_owned(), "64".to_owned()]);
+        assert_eq!(UVec2::splat(64), config.unwrap().board_size);
+    }
+
+    #[test]
+    fn invalid_config_file_fails() {
+        for contents in ["size = 64", "colour = \"red\"", "rule = "] {
+            assert!(matches!(
+                ConfigFile::parse(contents),
+                Err(ConfigError::InvalidFile(_))
+            ));
+        }
+        for (key, value, contents) in [
+            ("size", "0", "size = \"0\""),
+            ("interval_ms", "0", "interval_ms = 0"),
+            ("theme", "neon", "theme = \"neon\""),
+            ("density", "101", "density = 101"),
+        ] {
+            assert_eq!(
+                Err(ConfigError::InvalidValue(key.to_owned(), value.to_owned())),
+                ConfigFile::parse(contents)
+                    .unwrap()
+                    .apply(Config::default())
+            );
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    diagnostic::{
+        Diagnostic, DiagnosticPath, Diagnostics, DiagnosticsStore, FrameTimeDiagnosticsPlugin,
+        RegisterDiagnostic,
+    },
+    prelude::*,
+    window::{PresentMode, PrimaryWindow},
+};
+
+use crate::{
+    bindings::{action_just_pressed, Action, KeyBindings},
+    life::Generation,
+    prelude::*,
+    state::GameState,
+};
+
+/// the generations advanced per second of real time, see `GENERATION_RATE_WINDOW_SECS`
+pub const GENERATIONS_PER_SECOND: DiagnosticPath =
+    DiagnosticPath::const_new("generations_per_second");
+
+/// The present modes that are cycled through, vsync saves power while the simulation still
+/// advances at its fixed rate.
+pub const PRESENT_MODES: [PresentMode; 3] = [
+    PresentMode::AutoVsync,
+    PresentMode::AutoNoVsync,
+    PresentMode::Immediate,
+];
+
+/// Shows the frame rate and frame time in the corner of the window, hidden by default.
+///
+/// Also measures the generations per second, so they can be compared against the rate the
+/// fixed timestep aims for.
+pub struct DiagnosticsOverlayPlugin;
+
+impl Plugin for DiagnosticsOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin);
+        }
+        app.register_diagnostic(Diagnostic::new(GENERATIONS_PER_SECOND).with_suffix(" gen/s"))
+            .init_resource::<KeyBindings>()
+            .init_resource::<ShowDiagnostics>()
+            .init_resource::<GenerationSamples>()
+            .add_systems(Startup, spawn_diagnostics_text)
+            .add_systems(
+                Update,
+                (
+                    measure_generation_rate.run_if(resource_exists::<Generation>),
+                    toggle_diagnostics.run_if(action_just_pressed(Action::ToggleDiagnostics)),
+                    cycle_present_mode.run_if(action_just_pressed(Action::CyclePresentMode)),
+                    update_diagnostics_visibility.run_if(resource_changed::<ShowDiagnostics>),
+                    update_diagnostics_text.run_if(resource_equals(ShowDiagnostics(true))),
+                ),
+            );
+    }
+}
+
+fn spawn_diagnostics_text(mut commands: Commands) {
+    commands.spawn((
+        DiagnosticsText,
+        Text::default(),
+        TextFont {
+            font_size: HUD_FONT_SIZE,
+            ..default()
+        },
+        TextColor(HUD_TEXT_COLOR),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(8.0),
+            left: Val::Px(8.0),
+            ..default()
+        },
+        Visibility::Hidden,
+    ));
+}
+
+fn toggle_diagnostics(mut show_diagnostics: ResMut<ShowDiagnostics>) {
+    **show_diagnostics = !**show_diagnostics;
+}
+
+fn cycle_present_mode(mut window_query: Query<&mut Window, With<PrimaryWindow>>) {
+    for mut window in window_query.iter_mut() {
+        window.present_mode = cycle_next(&PRESENT_MODES, window.present_mode);
+        info!("present mode: {:?}", window.present_mode);
+    }
+}
+
+fn update_diagnostics_visibility(
+    mut text_query: Query<&mut Visibility, With<DiagnosticsText>>,
+    show_diagnostics: Res<ShowDiagnostics>,
+) {
+    let visibility = if **show_diagnostics {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    for mut text_visibility in text_query.iter_mut() {
+        text_visibility.set_if_neq(visibility);
+    }
+}
+
+/// samples the generation every frame, dropping the samples that left the sliding window
+fn measure_generation_rate(
+    time: Res<Time<Real>>,
+    generation: Res<Generation>,
+    mut samples: ResMut<GenerationSamples>,
+    mut diagnostics: Diagnostics,
+) {
+    samples.record(time.elapsed_secs_f64(), **generation);
+    if let Some(rate) = samples.rate() {
+        diagnostics.add_measurement(&GENERATIONS_PER_SECOND, || rate);
+    }
+}
+
+fn update_diagnostics_text(
+    mut text_query: Query<&mut Text, With<DiagnosticsText>>,
+    diagnostics: Res<DiagnosticsStore>,
+    fixed_time: Res<Time<Fixed>>,
+    state: Option<Res<State<GameState>>>,
+) {
+    let smoothed = |path: &DiagnosticPath| {
+        diagnostics
+            .get(path)
+            .and_then(|diagnostic| diagnostic.smoothed())
+            .unwrap_or_default()
+    };
+    let fps = smoothed(&FrameTimeDiagnosticsPlugin::FPS);
+    let frame_time = smoothed(&FrameTimeDiagnosticsPlugin::FRAME_TIME);
+    let generations = diagnostics
+        .get(&GENERATIONS_PER_SECOND)
+        .and_then(|diagnostic| diagnostic.value())
+        .unwrap_or_default();
+    // the fixed timestep only advances the board while it's running
+    let target = if state.is_some_and(|state| *state.get() == GameState::Running) {
+        format!("{:.1}", fixed_time.timestep().as_secs_f64().recip())
+    } else {
+        "-".to_owned()
+    };
+    for mut text in text_query.iter_mut() {
+        **text = format!(
+            "fps: {fps:.0}\nframe time: {frame_time:.2}ms\ngenerations/s: {generations:.1} \
+             (target: {target})"
+        );
+    }
+}
+
+/// marks the text showing the diagnostics
+#[derive(Component)]
+struct DiagnosticsText;
+
+/// the elapsed real time in seconds and the generation at that time, oldest first
+#[derive(Resource, Debug, Default, Clone, Deref)]
+struct GenerationSamples(VecDeque<(f64, u64)>);
+
+impl GenerationSamples {
+    fn record(&mut self, elapsed_secs: f64, generation: u64) {
+        // the generation gets reset when the board is set up again
+        if self.back().is_some_and(|&(_, last)| generation < last) {
+            self.0.clear();
+        }
+        self.0.push_back((elapsed_secs, generation));
+        // keep a single sample at or beyond the start of the window
+        let window_start = elapsed_secs - GENERATION_RATE_WINDOW_SECS;
+        while self.get(1).is_some_and(|&(secs, _)| secs <= window_start) {
+            self.0.pop_front();
+        }
+    }
+
+    /// the generations per second over the samples, `None` until they span any time
+    fn rate(&self) -> Option<f64> {
+        let (&(first_secs, first), &(last_secs, last)) = (self.front()?, self.back()?);
+        let elapsed = last_secs - first_secs;
+        (elapsed > 0.0).then(|| (last - first) as f64 / elapsed)
+    }
+}
+
+/// whether the diagnostics are shown
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+struct ShowDiagnostics(bool);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generation_rate_uses_a_sliding_window() {
+        let mut samples = GenerationSamples::default();
+        samples.record(0.0, 0);
+        assert_eq!(None, samples.rate());
+
+        // 10 generations per second for a while, then 50
+        for tick in 1..=40 {
+            samples.record(tick as f64 * 0.1, tick);
+        }
+        assert_eq!(Some(10.0), samples.rate().map(|rate| rate.round()));
+        for tick in 1..=40 {
+            samples.record(4.0 + tick as f64 * 0.1, 40 + 5 * tick);
+        }
+        assert_eq!(Some(50.0), samples.rate().map(|rate| rate.round()));
+
+        // setting the board up again starts over
+        samples.record(8.1, 0);
+        assert_eq!(1, samples.len());
+
+        // samples that are older than the window age out
+        let mut samples = GenerationSamples::default();
+        let half_window = GENERATION_RATE_WINDOW_SECS / 2.0;
+        for (i, generation) in [0, 10, 20].into_iter().enumerate() {
+            samples.record(i as f64 * half_window, generation);
+        }
+        assert_eq!(Some(20.0 / GENERATION_RATE_WINDOW_SECS), samples.rate());
+        samples.record(3.0 * half_window, 80);
+        assert_eq!(Some(&(half_window, 10)), samples.front());
+        assert_eq!(Some(70.0 / GENERATION_RATE_WINDOW_SECS), samples.rate());
+    }
+
+    #[test]
+    fn present_modes_cycle() {
+        let next = |mode| cycle_next(&PRESENT_MODES, mode);
+        assert_eq!(PresentMode::AutoNoVsync, next(PresentMode::AutoVsync));
+        assert_eq!(PresentMode::AutoVsync, next(PresentMode::Immediate));
+        assert_eq!(PresentMode::AutoVsync, next(PresentMode::Fifo));
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use bevy::{
+    color::{Color, ColorToPacked},
+    math::UVec2,
+};
+use image::{ImageResult, Rgba, RgbaImage};
+
+use crate::{save, theme::Theme};
+
+/// Renders the board into an image with one pixel per cell, using the on-screen colors.
+///
+/// `cells` are in row-major order (see `Board::cell_coord_to_idx`) with the first row at the
+/// bottom of the board, so the rows get flipped to match the image's y axis.
+pub fn board_image(size: UVec2, cells: &[bool], theme: &Theme) -> RgbaImage {
+    let (alive, dead) = (pixel(theme.cell_alive), pixel(theme.cell_dead));
+    RgbaImage::from_fn(size.x, size.y, |x, y| {
+        let idx = ((size.y - 1 - y) * size.x + x) as usize;
+        if cells[idx] {
+            alive
+        } else {
+            dead
+        }
+    })
+}
+
+/// Renders the board into an image whose longer side is `max_side` pixels, like `board_image`.
+///
+/// Each pixel blends the colors by the share of live cells it covers, so sparse regions of
+/// large boards stay visible. Boards smaller than the image are scaled up instead.
+pub fn thumbnail_image(size: UVec2, cells: &[bool], theme: &Theme, max_side: u32) -> RgbaImage {
+    let (alive, dead) = (pixel(theme.cell_alive), pixel(theme.cell_dead));
+    let scale = max_side as f32 / size.max_element() as f32;
+    let width = ((size.x as f32 * scale).round() as u32).max(1);
+    let height = ((size.y as f32 * scale).round() as u32).max(1);
+    // the cells covered by the `i`th of `len` pixels along an axis of `cell_len` cells
+    let covered = |i: u32, len: u32, cell_len: u32| {
+        let start = i * cell_len / len;
+        start..((i + 1) * cell_len / len).max(start + 1)
+    };
+    RgbaImage::from_fn(width, height, |x, y| {
+        let (xs, ys) = (
+            covered(x, width, size.x),
+            covered(height - 1 - y, height, size.y),
+        );
+        let total = xs.len() * ys.len();
+        let live = ys
+            .flat_map(|cell_y| xs.clone().map(move |cell_x| cell_y * size.x + cell_x))
+            .filter(|&idx| cells[idx as usize])
+            .count();
+        let share = live as f32 / total as f32;
+        Rgba(std::array::from_fn(|i| {
+            (dead[i] as f32 + (alive[i] as f32 - dead[i] as f32) * share).round() as u8
+        }))
+    })
+}
+
+fn pixel(color: Color) -> Rgba<u8> {
+    Rgba(color.to_srgba().to_u8_array())
+}
+
+pub fn write_png(
+    size: UVec2,
+    cells: &[bool],
+    theme: &Theme,
+    path: impl AsRef<Path>,
+) -> ImageResult<()> {
+    board_image(size, cells, theme).save_with_format(path, image::ImageFormat::Png)
+}
+
+/// returns a new image path in the working directory, e.g. `board_1712345678901.png`
+pub fn timestamped_png_path() -> PathBuf {
+    PathBuf::from(format!("board_{}.png", save::unix_millis()))
+}
+
+pub const POPULATION_CSV_HEADER: &str = "generation,population";
+
+/// Opens a CSV file to append the population of each generation to, new files start with
+/// `POPULATION_CSV_HEADER`.
+pub fn open_population_csv(path: impl AsRef<Path>) -> io::Result<BufWriter<File>> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let is_new = file.metadata()?.len() == 0;
+    let mut writer = BufWriter::new(file);
+    if is_new {
+        writeln!(writer, "{POPULATION_CSV_HEADER}")?;
+    }
+    Ok(writer)
+}
+
+pub fn write_population_row(
+    writer: &mut impl Write,
+    generation: u64,
+    population: u32,
+) -> io::Result<()> {
+    writeln!(writer, "{generation},{population}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn board_image_is_flipped() {
+        // only the bottom left cell is alive
+        let theme = Theme::default();
+        let image = board_image(
+            UVec2::new(3, 2),
+            &[true, false, false, false, false, false],
+            &theme,
+        );
+        let (alive, dead) = (pixel(theme.cell_alive), pixel(theme.cell_dead));
+        assert_eq!((3, 2), image.dimensions());
+        assert_eq!(&alive, image.get_pixel(0, 1));
+        assert_eq!(&dead, image.get_pixel(0, 0));
+        assert_eq!(&dead, image.get_pixel(2, 1));
+    }
+
+    #[test]
+    fn thumbnails_blend_the_covered_cells() {
+        // the bottom row is alive apart from the third cell, the top row only on the left
+        let theme = Theme {
+            cell_alive: Color::WHITE,
+            cell_dead: Color::BLACK,
+            ..Theme::default()
+        };
+        let cells = [true, true, false, true, true, true, false, false];
+        let image = thumbnail_image(UVec2::new(4, 2), &cells, &theme, 2);
+        assert_eq!((2, 1), image.dimensions());
+        assert_eq!(&Rgba([255, 255, 255, 255]), image.get_pixel(0, 0));
+        assert_eq!(&Rgba([64, 64, 64, 255]), image.get_pixel(1, 0));
+
+        // small boards are scaled up, with every cell covering a block of pixels
+        let image = thumbnail_image(UVec2::new(2, 1), &[true, false], &theme, 4);
+        assert_eq!((4, 2), image.dimensions());
+        assert_eq!(&Rgba([255, 255, 255, 255]), image.get_pixel(1, 1));
+        assert_eq!(&Rgba([0, 0, 0, 255]), image.get_pixel(2, 0));
+    }
+
+    #[test]
+    fn population_csv_appends_rows() {
+        let path = std::env::temp_dir().join(format!("population_{}.csv", save::unix_millis()));
+        for (generation, population) in [(1, 12), (2, 9)] {
+            let mut writer = open_population_csv(&path).unwrap();
+            write_population_row(&mut writer, generation, population).unwrap();
+        }
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(format!("{POPULATION_CSV_HEADER}\n1,12\n2,9\n"), contents);
+    }
+}
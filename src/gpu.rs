@@ -0,0 +1,220 @@
+use std::sync::mpsc;
+
+use bevy::{prelude::*, tasks::block_on};
+use wgpu::util::DeviceExt;
+
+use crate::{
+    board::Board,
+    rules::{Neighbourhood, Ruleset},
+};
+
+/// bytes per cell in the buffers, WGSL has no smaller integers
+const CELL_BYTES: u64 = 4;
+/// the cells stepped by each workgroup along each axis, see `step.wgsl`
+const WORKGROUP_SIZE: u32 = 8;
+
+/// Steps a board with a compute shader, for boards too large to step quickly on the CPU.
+///
+/// The cells are uploaded into a storage buffer before every step and the next generation is
+/// read back, so the `LifeGrid` stays the source of truth for editing and rendering. Only the
+/// life-like part of the rules runs on the GPU, the caller masks decaying cells like after
+/// `board::step_board`.
+#[derive(Resource)]
+pub struct GpuStepper {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+    bind_group: wgpu::BindGroup,
+    params: wgpu::Buffer,
+    current: wgpu::Buffer,
+    next: wgpu::Buffer,
+    /// the next generation is copied here to be mapped
+    readback: wgpu::Buffer,
+    size: UVec2,
+}
+
+impl GpuStepper {
+    /// Sets up stepping boards of `board`'s size on the default adapter, `None` if there is no
+    /// adapter to compute on.
+    pub fn new(board: &Board) -> Option<GpuStepper> {
+        let instance = wgpu::Instance::default();
+        let adapter = block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))?;
+        let (device, queue) =
+            block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None)).ok()?;
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("step"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("step.wgsl").into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("step"),
+            layout: None,
+            module: &module,
+            entry_point: Some("step"),
+            compilation_options: default(),
+            cache: None,
+        });
+
+        let cells_size = board.cell_count() as u64 * CELL_BYTES;
+        let buffer = |label, usage| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size: cells_size,
+                usage,
+                mapped_at_creation: false,
+            })
+        };
+        let current = buffer(
+            "current cells",
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        );
+        let next = buffer(
+            "next cells",
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        );
+        let readback = buffer(
+            "readback cells",
+            wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        );
+        let params = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("step params"),
+            contents: &params_bytes(board, &Ruleset::default()),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("step"),
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: current.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: next.as_entire_binding(),
+                },
+            ],
+        });
+
+        Some(GpuStepper {
+            device,
+            queue,
+            pipeline,
+            bind_group,
+            params,
+            current,
+            next,
+            readback,
+            size: board.size(),
+        })
+    }
+
+    /// whether the buffers were made for boards of `board`'s size
+    pub fn fits(&self, board: &Board) -> bool {
+        self.size == board.size()
+    }
+
+    /// Computes the generation after `current` into `next`, like `board::step_board`.
+    ///
+    /// # Panics
+    ///
+    /// if the stepper doesn't fit the board or either of the buffers doesn't match the amount of
+    /// cells on the board
+    pub fn step(
+        &self,
+        current: &[bool],
+        next: &mut [bool],
+        board: &Board,
+        rules: &Ruleset,
+    ) -> Result<(), wgpu::BufferAsyncError> {
+        assert!(self.fits(board), "stepper size");
+        assert_eq!(board.cell_count(), current.len(), "current buffer size");
+        assert_eq!(board.cell_count(), next.len(), "next buffer size");
+
+        let cells = current
+            .iter()
+            .flat_map(|&alive| u32::from(alive).to_ne_bytes())
+            .collect::<Vec<_>>();
+        self.queue.write_buffer(&self.current, 0, &cells);
+        self.queue
+            .write_buffer(&self.params, 0, &params_bytes(board, rules));
+
+        let mut encoder = self.device.create_command_encoder(&default());
+        {
+            let mut pass = encoder.begin_compute_pass(&default());
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &self.bind_group, &[]);
+            pass.dispatch_workgroups(
+                board.width.div_ceil(WORKGROUP_SIZE),
+                board.height.div_ceil(WORKGROUP_SIZE),
+                1,
+            );
+        }
+        encoder.copy_buffer_to_buffer(&self.next, 0, &self.readback, 0, self.readback.size());
+        self.queue.submit([encoder.finish()]);
+
+        let readback = self.readback.slice(..);
+        let (sender, receiver) = mpsc::channel();
+        readback.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        receiver.recv().unwrap_or(Err(wgpu::BufferAsyncError))?;
+        {
+            let cells = readback.get_mapped_range();
+            for (next_alive, cell) in next.iter_mut().zip(cells.chunks_exact(CELL_BYTES as usize)) {
+                *next_alive = cell.iter().any(|&byte| byte != 0);
+            }
+        }
+        self.readback.unmap();
+        Ok(())
+    }
+}
+
+/// the `Params` of `step.wgsl`
+fn params_bytes(board: &Board, rules: &Ruleset) -> Vec<u8> {
+    let counts_mask = |counts: [bool; 9]| {
+        (0..9)
+            .filter(|&n| counts[n])
+            .fold(0u32, |mask, n| mask | 1 << n)
+    };
+    [
+        board.width,
+        board.height,
+        counts_mask(rules.birth),
+        counts_mask(rules.survive),
+        board.wrap_x.into(),
+        board.wrap_y.into(),
+        (rules.neighbourhood == Neighbourhood::VonNeumann).into(),
+        0,
+    ]
+    .into_iter()
+    .flat_map(u32::to_ne_bytes)
+    .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::board::{seeded_test_boards, step_board};
+
+    #[test]
+    #[ignore = "needs a GPU adapter"]
+    fn gpu_steps_match_the_cpu() {
+        for (rulestring, board, rules, mut cells) in seeded_test_boards() {
+            let stepper = GpuStepper::new(&board).expect("no adapter to compute on");
+            let mut cpu_next = vec![false; board.cell_count()];
+            let mut gpu_next = vec![false; board.cell_count()];
+            for _ in 0..4 {
+                step_board(&cells, &mut cpu_next, &board, &rules);
+                stepper.step(&cells, &mut gpu_next, &board, &rules).unwrap();
+                assert_eq!(cpu_next, gpu_next, "{rulestring}");
+                cells.clone_from(&cpu_next);
+            }
+        }
+    }
+}
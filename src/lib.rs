@@ -1,22 +1,156 @@
+pub mod bindings;
+pub mod board;
 pub mod camera;
+pub mod config;
+pub mod diagnostics;
+pub mod export;
+#[cfg(feature = "gpu-step")]
+pub mod gpu;
 pub mod life;
+pub mod pattern;
+pub mod rules;
+pub mod save;
+pub mod sparse;
 pub mod state;
+pub mod theme;
 
 pub mod prelude {
     use bevy::{color::Color, math::Vec2};
 
     pub const UPDATE_INTERVAL_MS: u64 = 40;
+    pub const MIN_UPDATE_INTERVAL_MS: u64 = 1;
+    pub const MAX_UPDATE_INTERVAL_MS: u64 = 1000;
+    /// most generations the fixed timestep advances in a single frame, see `life::MaxStepsPerFrame`
+    pub const MAX_STEPS_PER_FRAME: u32 = 10;
+    /// factor the update interval is multiplied / divided by when changing the simulation speed
+    pub const UPDATE_INTERVAL_FACTOR: f64 = 1.5;
+    /// amount of recent taps the tempo is averaged over
+    pub const TAP_TEMPO_TAPS: usize = 5;
+    /// the tempo tapped along to is kept within these beats per minute, taps further apart than
+    /// the slowest tempo start over
+    pub const MIN_TAP_TEMPO_BPM: f64 = 60.0;
+    pub const MAX_TAP_TEMPO_BPM: f64 = 300.0;
+    /// amount of recent generations compared against when detecting a stable board
+    pub const STASIS_HISTORY_LEN: usize = 4;
+    /// maximum amount of edits that can be undone
+    pub const EDIT_HISTORY_LEN: usize = 64;
+    /// maximum amount of generations that can be stepped back through
+    pub const GENERATION_HISTORY_LEN: usize = 256;
+    /// largest radius of the brush used for painting cells
+    pub const MAX_BRUSH_SIZE: u32 = 16;
+    /// percentage of live cells when randomizing the board
+    pub const SEED_DENSITY: u32 = 50;
+    /// percentage the seeding density changes by
+    pub const SEED_DENSITY_STEP: u32 = 10;
+    /// largest chance of a cell flipping against the rules each generation
+    pub const MAX_NOISE_FLIP_CHANCE: f32 = 0.05;
+    /// amount the flipping chance of the noise changes by
+    pub const NOISE_FLIP_CHANCE_STEP: f32 = 0.001;
+    /// amount of recent generations shown in the population graph
+    pub const POPULATION_GRAPH_LEN: usize = 200;
+    /// size of the population graph in logical pixels
+    pub const POPULATION_GRAPH_SIZE_PX: Vec2 = Vec2::new(200.0, 80.0);
+    pub const POPULATION_GRAPH_COLOR: Color = Color::srgb(1.0, 0.9, 0.3);
+    /// length of the sliding window the generations per second are measured over
+    pub const GENERATION_RATE_WINDOW_SECS: f64 = 2.0;
+    /// length of the longer side of the board thumbnails in the gallery, in logical pixels
+    pub const THUMBNAIL_SIZE_PX: u32 = 96;
+    /// maximum amount of thumbnails kept in the gallery, the oldest ones are dropped
+    pub const GALLERY_LEN: usize = 8;
+    /// size of the window when it isn't maximized or fullscreen
+    pub const WINDOW_SIZE_PX: Vec2 = Vec2::new(1000.0, 1000.0);
+    /// text shown in the corners of the window
+    pub const HUD_FONT_SIZE: f32 = 16.0;
+    pub const HUD_TEXT_COLOR: Color = Color::srgb(1.0, 1.0, 1.0);
     pub const BG_COLOR: Color = Color::srgb(0.0, 0.1, 0.3);
 
     pub const BOARD_SIZE: u32 = 128;
+    pub const MIN_BOARD_SIZE: u32 = 4;
+    pub const MAX_BOARD_SIZE: u32 = 1024;
+    /// board sizes that can be cycled through at runtime
+    pub const BOARD_SIZES: [u32; 4] = [32, 64, 128, 256];
     pub const BOARD_POS: Vec2 = Vec2::ZERO;
+    /// boards with at least this many cells are stepped on the GPU, with the `gpu-step` feature
+    pub const GPU_STEP_MIN_CELLS: usize = 512 * 512;
+    /// maximum amount of cells spawned in a single frame while loading, keeps large boards from
+    /// freezing the window
+    pub const CELLS_SPAWNED_PER_FRAME: usize = 16384;
     pub const BORDER_WIDTH_PX: f32 = 8.0;
+    // z layers of the board, from back to front. Gizmos (the grid, the selection and other
+    // outlines) are drawn over all of them. The cells all stay in the dead cells' layer unless
+    // they are layered, see `life::LayerCells`.
+    pub const BORDER_Z: f32 = 0.0;
+    pub const CELL_DEAD_Z: f32 = 10.0;
+    /// live cells are drawn over dead ones, so overlapping cells are ordered consistently
+    pub const CELL_ALIVE_Z: f32 = 11.0;
+    /// the cell under the keyboard cursor is drawn over all of the other cells
+    pub const CURSOR_CELL_Z: f32 = 12.0;
+    pub const MIN_BORDER_WIDTH_PX: f32 = 2.0;
+    pub const MAX_BORDER_WIDTH_PX: f32 = 32.0;
+    /// amount the border's width changes by
+    pub const BORDER_WIDTH_STEP_PX: f32 = 2.0;
+    /// space between the main board and the boards comparing other rulesets
+    pub const COMPARED_BOARD_GAP_PX: f32 = 64.0;
+    /// space left between the board's border and the edges of the window when fitting the camera
+    pub const CAMERA_FIT_PADDING_PX: f32 = 32.0;
+    /// how many times further than the fitting zoom level the camera can zoom out
+    pub const CAMERA_MAX_ZOOM_OUT: f32 = 4.0;
     pub const BORDER_COLOR: Color = Color::srgb(1.0, 1.0, 1.0);
+    /// border color of a wrapping board, when the wrap indicator is shown
+    pub const BORDER_WRAP_COLOR: Color = Color::srgb(0.5, 0.8, 1.0);
+    /// outline of the selected cells
+    pub const SELECTION_COLOR: Color = Color::srgb(1.0, 0.6, 0.0);
+    /// outline of the gliders found on the board
+    pub const GLIDER_HIGHLIGHT_COLOR: Color = Color::srgb(1.0, 0.2, 0.8);
+    /// outline of the cells a previewed stamp would bring to life
+    pub const STAMP_PREVIEW_COLOR: Color = Color::srgba(0.4, 1.0, 0.6, 0.6);
+    /// how long the gliders found on the board stay outlined
+    pub const GLIDER_HIGHLIGHT_SECS: f32 = 2.0;
+    pub const GRID_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.15);
 
     pub const CELL_SIZE_PX: Vec2 = Vec2::splat(8.0);
     pub const CELL_SCALE: Vec2 = Vec2::splat(1.0);
+    /// width of the outline of live cells in the outline render mode
+    pub const CELL_OUTLINE_WIDTH_PX: f32 = 1.0;
+    pub const MIN_CELL_SCALE: f32 = 0.1;
+    pub const MAX_CELL_SCALE: f32 = 1.0;
+    /// amount the cell scale changes by when adjusting the gap between cells
+    pub const CELL_SCALE_STEP: f32 = 0.1;
     pub const CELL_ALIVE_COLOR: Color = Color::srgb(0.2, 1.0, 0.2);
+    /// color of cells that have been alive for a long time, see `AGE_BUCKETS`
+    pub const CELL_OLD_COLOR: Color = Color::srgb(0.7, 0.85, 1.0);
+    /// amount of colors live cells fade through as they age
+    pub const AGE_BUCKETS: usize = 8;
+    /// amount of generations a cell spends in each age bucket
+    pub const AGE_BUCKET_GENERATIONS: u32 = 4;
+    /// color of cells that just started decaying under Generations rules, see `Ruleset::states`
+    pub const CELL_DECAYING_COLOR: Color = Color::srgb(0.9, 0.4, 0.1);
+    /// amount of colors decaying cells fade through, longer decays stay on the last one
+    pub const DECAY_STEPS: usize = 8;
+    /// color of cells that just changed, in the heatmap mode
+    pub const HEATMAP_HOT_COLOR: Color = Color::srgb(1.0, 0.5, 0.1);
+    /// amount of generations it takes a changed cell to fade back in the heatmap mode
+    pub const HEATMAP_FADE_GENERATIONS: u32 = 16;
+    /// fraction of the update interval it takes a cell to fade after changing, in the fade mode
+    pub const CELL_FADE_FRACTION: f32 = 0.5;
+    /// amount of colors a cell fades through between being dead and alive
+    pub const CELL_FADE_STEPS: usize = 8;
     pub const CELL_CLICKED_COLOR: Color = Color::srgb(1.0, 1.0, 0.0);
     pub const CELL_HOVERED_ALIVE_COLOR: Color = Color::srgb(0.2, 0.4, 1.0);
     pub const CELL_HOVERED_DEAD_COLOR: Color = Color::srgb(0.7, 0.1, 0.1);
+    /// colors of the cells that change in the next generation, while it's previewed
+    pub const CELL_BORN_COLOR: Color = Color::srgb(0.1, 0.5, 0.9);
+    pub const CELL_DYING_COLOR: Color = Color::srgb(0.9, 0.3, 0.6);
+    /// colors of the cell under the keyboard cursor
+    pub const CURSOR_CELL_ALIVE_COLOR: Color = Color::srgb(0.3, 1.0, 0.4);
+    pub const CURSOR_CELL_DEAD_COLOR: Color = Color::srgb(0.1, 0.45, 0.2);
+
+    /// the setting after `current` in `all`, settings outside of them start over
+    pub fn cycle_next<T: PartialEq + Copy>(all: &[T], current: T) -> T {
+        let next = all
+            .iter()
+            .position(|&setting| setting == current)
+            .map_or(0, |i| (i + 1) % all.len());
+        all[next]
+    }
 }
@@ -1,45 +1,749 @@
 #![allow(clippy::type_complexity)]
 
-use std::time::Duration;
+use std::{
+    collections::{HashSet, VecDeque},
+    error::Error,
+    fmt,
+    fs::File,
+    hash::{DefaultHasher, Hash, Hasher},
+    io::{BufWriter, Write},
+    ops::Range,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use bevy::{
-    ecs::system::SystemState,
-    input::common_conditions::input_just_pressed,
+    ecs::{
+        schedule::ScheduleLabel,
+        system::{SystemParam, SystemState},
+    },
+    image::ImageSampler,
+    input::{
+        common_conditions::input_just_released,
+        mouse::{MouseScrollUnit, MouseWheel},
+    },
     math::{ivec2, uvec2, vec2},
+    picking::{
+        focus::HoverMap,
+        mesh_picking::{ray_cast::RayCastVisibility, MeshPickingSettings},
+        pointer::PointerId,
+        PickSet,
+    },
+    prelude::*,
+    render::{
+        mesh::Indices,
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, PrimitiveTopology, TextureDimension, TextureFormat},
+    },
+    window::{CursorLeft, CursorMoved, PrimaryWindow, WindowFocused},
+};
+use bevy_pancam::PanCam;
+
+pub(crate) use crate::board::LifeGrid;
+pub use crate::board::{cell_alive_next, live_neighbours, step_board, Board};
+#[cfg(feature = "gpu-step")]
+use crate::gpu::GpuStepper;
+use crate::{
+    bindings::{action_just_pressed, any_action_just_pressed, Action, KeyBindings},
+    config::{Config, ConfigFile, CONFIG_FILE_PATH},
+    export,
+    pattern::{self, Flip, Pattern},
     prelude::*,
-    utils::HashMap,
+    rules::{Neighbourhood, Ruleset},
+    save::{self, BoardSave, SessionSave},
+    sparse::SparseLife,
+    state::GameState,
+    theme::Theme,
 };
 
-use crate::{prelude::*, state::GameState};
+/// The board and everything to set it up and run it.
+///
+/// The settings come from the `Config` resource if it's inserted before adding the plugin, the
+/// builder methods override them:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use conway_gol_bevy::life::LifePlugin;
+/// App::new().add_plugins(
+///     LifePlugin::new()
+///         .board_size(256)
+///         .interval_ms(50)
+///         .rule("B36/S23")
+///         .wrap(false),
+/// );
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct LifePlugin {
+    board_size: Option<UVec2>,
+    interval_ms: Option<u64>,
+    ruleset: Option<Ruleset>,
+    wrap: Option<bool>,
+}
+
+impl LifePlugin {
+    /// the plugin with the settings of the `Config`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A square board with this many cells along each side.
+    ///
+    /// # Panics
+    ///
+    /// if the size isn't between `MIN_BOARD_SIZE` and `MAX_BOARD_SIZE`
+    pub fn board_size(self, cells: u32) -> Self {
+        self.board_dimensions(UVec2::splat(cells))
+    }
+
+    /// A board with `size.x` columns and `size.y` rows of cells.
+    ///
+    /// # Panics
+    ///
+    /// if either of them isn't between `MIN_BOARD_SIZE` and `MAX_BOARD_SIZE`
+    pub fn board_dimensions(self, size: UVec2) -> Self {
+        let in_range = |len| (MIN_BOARD_SIZE..=MAX_BOARD_SIZE).contains(&len);
+        assert!(
+            in_range(size.x) && in_range(size.y),
+            "board size {size} outside of {MIN_BOARD_SIZE} - {MAX_BOARD_SIZE}"
+        );
+        Self {
+            board_size: Some(size),
+            ..self
+        }
+    }
+
+    /// The interval between generations while running.
+    ///
+    /// # Panics
+    ///
+    /// if the interval isn't between `MIN_UPDATE_INTERVAL_MS` and `MAX_UPDATE_INTERVAL_MS`
+    pub fn interval_ms(self, interval_ms: u64) -> Self {
+        assert!(
+            (MIN_UPDATE_INTERVAL_MS..=MAX_UPDATE_INTERVAL_MS).contains(&interval_ms),
+            "update interval {interval_ms}ms outside of {MIN_UPDATE_INTERVAL_MS} - \
+             {MAX_UPDATE_INTERVAL_MS}ms"
+        );
+        Self {
+            interval_ms: Some(interval_ms),
+            ..self
+        }
+    }
+
+    /// The rules in B/S notation, see `Ruleset::from_rulestring`.
+    ///
+    /// # Panics
+    ///
+    /// if the rulestring can't be parsed, use `ruleset` to handle the error
+    pub fn rule(self, rulestring: &str) -> Self {
+        match Ruleset::from_rulestring(rulestring) {
+            Ok(ruleset) => self.ruleset(ruleset),
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    pub fn ruleset(self, ruleset: Ruleset) -> Self {
+        Self {
+            ruleset: Some(ruleset),
+            ..self
+        }
+    }
+
+    /// whether the edges of the board wrap around
+    pub fn wrap(self, wrap: bool) -> Self {
+        Self {
+            wrap: Some(wrap),
+            ..self
+        }
+    }
 
-pub struct LifePlugin;
+    /// the `Config` with the settings of the builder methods
+    fn configure(&self, mut config: Config) -> Config {
+        config.board_size = self.board_size.unwrap_or(config.board_size);
+        config.update_interval_ms = self.interval_ms.unwrap_or(config.update_interval_ms);
+        config.ruleset = self.ruleset.unwrap_or(config.ruleset);
+        config.wrap = self.wrap.unwrap_or(config.wrap);
+        config
+    }
+}
 
 impl Plugin for LifePlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(Board::default())
+        let config = self.configure(
+            app.world()
+                .get_resource::<Config>()
+                .cloned()
+                .unwrap_or_default(),
+        );
+        let board = Board {
+            width: config.board_size.x,
+            height: config.board_size.y,
+            wrap_x: config.wrap,
+            wrap_y: config.wrap,
+            ..default()
+        };
+        let seed = RngSeed(config.seed.unwrap_or_else(|| fastrand::u64(..)));
+        let focus_pause = PauseOnFocusLoss {
+            enabled: config.pause_on_focus_loss,
+            ..default()
+        };
+        let population_limits = PopulationLimits {
+            stop_on_extinction: config.stop_on_extinction,
+            max_population: config.max_population,
+        };
+        if config.cursor_picking {
+            app.init_resource::<CursorPicking>();
+        }
+        if let Some(path) = config.population_csv {
+            app.insert_resource(PopulationLog { path, writer: None });
+        }
+        if config.startup_pattern.is_some() || config.autorun {
+            app.insert_resource(StartupPattern {
+                source: config.startup_pattern,
+                autorun: config.autorun,
+            });
+        }
+        app.insert_resource(board)
+            .insert_resource(config.ruleset)
+            .insert_resource(seed)
+            .insert_resource(focus_pause)
+            .insert_resource(population_limits)
+            .insert_resource(MaxStepsPerFrame(config.max_steps_per_frame))
+            .init_resource::<StepsThisFrame>()
+            .insert_resource(ComparedRulesets(config.compared_rulesets))
+            .init_resource::<KeyBindings>()
+            .init_resource::<Generation>()
+            .init_resource::<Population>()
+            .init_resource::<HoveredCell>()
+            .init_resource::<StasisDetector>()
+            .init_resource::<EditHistory>()
+            .init_resource::<GenerationSnapshots>()
+            .init_resource::<ShowGrid>()
+            .init_resource::<BrushSize>()
+            .insert_resource(SeedDensity(config.seed_density))
+            .init_resource::<Noise>()
+            .init_resource::<TapTempo>()
+            .init_resource::<Symmetry>()
+            .init_resource::<StampOrientation>()
+            .init_resource::<CellSelection>()
+            .init_resource::<Clipboard>()
+            .init_resource::<ShowNeighbourCount>()
+            .init_resource::<CellProbe>()
+            .init_resource::<ShowWrapIndicator>()
+            .init_resource::<ShowBorder>()
+            .init_resource::<BorderWidth>()
+            .init_resource::<ShowHeatmap>()
+            .init_resource::<ShowFuture>()
+            .init_resource::<HideDeadCells>()
+            .init_resource::<LayerCells>()
+            .init_resource::<ShowFades>()
+            .insert_resource(config.theme)
+            .init_resource::<RenderMode>()
+            .init_resource::<PopulationHistory>()
+            .init_resource::<ShowPopulationGraph>()
+            .init_resource::<ShowStatus>()
+            .init_resource::<DebugVerify>()
+            .add_event::<CellToggled>()
             .insert_resource(Time::<Fixed>::from_duration(Duration::from_millis(
-                UPDATE_INTERVAL_MS,
+                config.update_interval_ms,
             )))
             .add_systems(
                 OnEnter(GameState::Load),
-                (load_meshes_and_materials, load_cell_board).chain(),
+                (
+                    (load_meshes_and_materials, load_cell_board).chain(),
+                    spawn_cursor_coord_text,
+                    spawn_status_text,
+                    spawn_thumbnail_gallery,
+                ),
+            )
+            .add_systems(
+                Update,
+                load_board_in_chunks.run_if(resource_exists::<BoardLoading>),
+            )
+            .add_systems(
+                GenerationStep,
+                (
+                    // only the visible window of an unbounded board would be restored
+                    record_generation_snapshot.run_if(not(resource_exists::<UnboundedWorld>)),
+                    update_cell_future_life,
+                    verify_future_life.map(drop).run_if(
+                        resource_equals(DebugVerify(true))
+                            .and(not(resource_exists::<UnboundedWorld>)),
+                    ),
+                    update_cell_current_life,
+                    step_compared_boards,
+                    record_population,
+                    advance_generation,
+                    // only the visible window of an unbounded board is stored in the grid
+                    detect_stasis.run_if(not(resource_exists::<UnboundedWorld>)),
+                    stop_at_population_limits.run_if(
+                        in_state(GameState::Running).and(not(resource_exists::<UnboundedWorld>)),
+                    ),
+                    log_population
+                        .run_if(resource_exists::<PopulationLog>.and(in_state(GameState::Running))),
+                )
+                    .chain(),
+            )
+            .add_systems(
+                OnEnter(GameState::Setup),
+                (
+                    reset_generation,
+                    reset_decay,
+                    reset_stasis_detection,
+                    reset_population_history,
+                    reset_generation_snapshots,
+                    stop_replay,
+                    stamp_startup_pattern.run_if(resource_exists::<StartupPattern>),
+                ),
+            )
+            .add_systems(
+                OnExit(GameState::Setup),
+                (
+                    reset_cell_interactions,
+                    record_session,
+                    cancel_stamp_preview.run_if(resource_exists::<StampPreview>),
+                    // the board might have been stepped and edited since entering setup
+                    reset_stasis_detection,
+                ),
             )
+            .add_systems(First, reset_steps_this_frame)
             .add_systems(
                 FixedUpdate,
-                ((update_cell_future_life, update_cell_current_life).chain())
-                    .run_if(in_state(GameState::Running)),
+                run_fixed_generation_step
+                    .run_if(in_state(GameState::Running).and(not(replay_finished))),
+            )
+            .add_systems(
+                Update,
+                (
+                    run_generation_step.run_if(
+                        action_just_pressed(Action::Step)
+                            .and(in_state(GameState::Setup).or(in_state(GameState::Paused))),
+                    ),
+                    step_back.run_if(
+                        action_just_pressed(Action::StepBack)
+                            .and(in_state(GameState::Setup).or(in_state(GameState::Paused)))
+                            .and(not(resource_exists::<UnboundedWorld>)),
+                    ),
+                    recount_population.run_if(resource_exists_and_changed::<LifeGrid>),
+                    handle_speed_kbd,
+                    export_board_png.run_if(action_just_pressed(Action::ExportPng)),
+                    toggle_grid.run_if(action_just_pressed(Action::ToggleGrid)),
+                    toggle_wrap_indicator.run_if(action_just_pressed(Action::ToggleWrapIndicator)),
+                    toggle_heatmap.run_if(action_just_pressed(Action::ToggleHeatmap)),
+                    toggle_render_mode.run_if(action_just_pressed(Action::ToggleRenderMode)),
+                    toggle_population_graph
+                        .run_if(action_just_pressed(Action::TogglePopulationGraph)),
+                    draw_population_graph.run_if(resource_equals(ShowPopulationGraph(true))),
+                    handle_cell_scale_kbd,
+                    update_cell_scales.run_if(resource_changed::<Board>),
+                    update_border_color.run_if(
+                        resource_changed::<Board>.or(resource_changed::<ShowWrapIndicator>),
+                    ),
+                    draw_grid.run_if(resource_equals(ShowGrid(true))),
+                    toggle_stasis_detection
+                        .run_if(action_just_pressed(Action::ToggleStasisDetection)),
+                    start_or_stop_running.run_if(
+                        action_just_pressed(Action::StartStop).and(not(in_state(GameState::Load))),
+                    ),
+                    pause_on_focus_loss.run_if(on_event::<WindowFocused>),
+                    edit_board.run_if(
+                        action_just_pressed(Action::EditBoard)
+                            .and(in_state(GameState::Running).or(in_state(GameState::Paused))),
+                    ),
+                ),
+            )
+            // the status panel
+            .add_systems(
+                Update,
+                (
+                    toggle_status.run_if(action_just_pressed(Action::ToggleStatus)),
+                    update_status_visibility.run_if(resource_changed::<ShowStatus>),
+                    update_status_text.run_if(
+                        resource_equals(ShowStatus(true)).and(not(in_state(GameState::Load))),
+                    ),
+                ),
+            )
+            // the gallery of board thumbnails
+            .add_systems(
+                Update,
+                capture_thumbnail.run_if(
+                    action_just_pressed(Action::CaptureThumbnail)
+                        .and(not(in_state(GameState::Load))),
+                ),
+            )
+            .add_systems(PreUpdate, scroll_thumbnail_gallery.after(PickSet::Focus))
+            // editing the board in setup
+            .add_systems(
+                Update,
+                (
+                    handle_setup_kbd,
+                    handle_brush_size_kbd,
+                    handle_seed_density_kbd.run_if(any_action_just_pressed([
+                        Action::DecreaseDensity,
+                        Action::IncreaseDensity,
+                    ])),
+                    cycle_symmetry.run_if(action_just_pressed(Action::CycleSymmetry)),
+                    toggle_board_wrap.run_if(action_just_pressed(Action::ToggleWrap)),
+                    toggle_neighbourhood.run_if(action_just_pressed(Action::ToggleNeighbourhood)),
+                    cycle_rule.run_if(action_just_pressed(Action::CycleRule)),
+                    save_board.run_if(action_just_pressed(Action::Save).and(not(shift_pressed))),
+                    load_board.run_if(action_just_pressed(Action::Load).and(not(shift_pressed))),
+                    import_dropped_pattern.run_if(on_event::<FileDragAndDrop>),
+                    preview_library_pattern.run_if(library_pattern_key_pressed),
+                    cycle_stamp_orientation.run_if(any_action_just_pressed([
+                        Action::RotateStamp,
+                        Action::FlipStamp,
+                    ])),
+                    copy_selection.run_if(action_just_pressed(Action::CopySelection)),
+                    preview_clipboard.run_if(action_just_pressed(Action::PasteClipboard)),
+                    draw_selection,
+                    undo_redo_edit.run_if(any_action_just_pressed([Action::Undo, Action::Redo])),
+                    transform_board.run_if(any_action_just_pressed([
+                        Action::FlipHorizontal,
+                        Action::FlipVertical,
+                        Action::Rotate,
+                    ])),
+                    end_edit_stroke.run_if(
+                        input_just_released(MouseButton::Left)
+                            .or(input_just_released(MouseButton::Right)),
+                    ),
+                    resize_board.run_if(
+                        any_action_just_pressed([Action::GrowBoard, Action::ShrinkBoard])
+                            .and(not(resource_exists::<UnboundedWorld>)),
+                    ),
+                    toggle_unbounded_world.run_if(action_just_pressed(Action::ToggleUnbounded)),
+                )
+                    .run_if(in_state(GameState::Setup)),
+            )
+            // noisy rules
+            .add_systems(
+                Update,
+                handle_noise_kbd.run_if(any_action_just_pressed([
+                    Action::DecreaseNoise,
+                    Action::IncreaseNoise,
+                ])),
+            )
+            // previewing the next generation
+            .add_systems(
+                Update,
+                toggle_future_preview.run_if(action_just_pressed(Action::ToggleFuturePreview)),
+            )
+            // stepping on a tapped beat
+            .add_systems(
+                Update,
+                tap_tempo.run_if(action_just_pressed(Action::TapTempo)),
+            )
+            // hiding dead cells
+            .add_systems(
+                Update,
+                (
+                    toggle_dead_cells.run_if(action_just_pressed(Action::ToggleDeadCells)),
+                    update_hidden_cell_picking.run_if(resource_changed::<HideDeadCells>),
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                toggle_cell_layers.run_if(action_just_pressed(Action::ToggleCellLayers)),
+            )
+            // previewing stamps, they are stamped by `commit_stamp_preview`
+            .add_systems(
+                Update,
+                (
+                    draw_stamp_preview.run_if(resource_exists::<StampPreview>),
+                    cancel_stamp_preview.run_if(
+                        resource_exists::<StampPreview>.and(action_just_pressed(Action::EditBoard)),
+                    ),
+                )
+                    .run_if(in_state(GameState::Setup)),
+            )
+            // quitting, without losing the edits to the board
+            .add_systems(Update, quit.run_if(action_just_pressed(Action::Quit)))
+            .add_systems(Last, save_recovery_on_exit.run_if(on_event::<AppExit>))
+            // the population log is written while running
+            .add_systems(
+                OnExit(GameState::Running),
+                flush_population_log.run_if(resource_exists::<PopulationLog>),
+            )
+            .add_systems(
+                Last,
+                flush_population_log
+                    .run_if(resource_exists::<PopulationLog>.and(on_event::<AppExit>)),
+            )
+            // plaintext pattern files
+            .add_systems(
+                Update,
+                (
+                    save_plaintext_pattern
+                        .run_if(action_just_pressed(Action::Save).and(shift_pressed)),
+                    load_plaintext_pattern
+                        .run_if(action_just_pressed(Action::Load).and(shift_pressed)),
+                )
+                    .run_if(in_state(GameState::Setup)),
+            )
+            // recording and replaying sessions
+            .add_systems(
+                Update,
+                (
+                    save_session.run_if(
+                        action_just_pressed(Action::SaveSession)
+                            .and(in_state(GameState::Running).or(in_state(GameState::Paused))),
+                    ),
+                    load_latest_session.pipe(start_replay).run_if(
+                        action_just_pressed(Action::ReplaySession).and(in_state(GameState::Setup)),
+                    ),
+                    finish_replay.run_if(replay_finished.and(in_state(GameState::Running))),
+                ),
+            )
+            .add_systems(
+                Update,
+                update_cursor_coord_text.run_if(on_event::<CursorMoved>.or(on_event::<CursorLeft>)),
+            )
+            // picking the cells from the cursor's position instead of casting rays at their meshes
+            .add_systems(
+                Update,
+                (
+                    pick_hovered_cell,
+                    probe_picked_cell,
+                    paint_picked_cells.run_if(in_state(GameState::Setup)),
+                )
+                    .chain()
+                    .run_if(resource_exists::<CursorPicking>.and(not(in_state(GameState::Load)))),
+            )
+            // the boards comparing other rulesets start from the main board
+            .add_systems(
+                Update,
+                mirror_main_board.run_if(
+                    in_state(GameState::Setup).and(
+                        resource_changed::<LifeGrid>
+                            .or(resource_changed::<Board>)
+                            .or(state_changed::<GameState>),
+                    ),
+                ),
+            )
+            // searching the board for gliders
+            .add_systems(
+                Update,
+                (
+                    find_gliders.run_if(
+                        action_just_pressed(Action::FindGliders)
+                            .and(not(in_state(GameState::Load))),
+                    ),
+                    draw_glider_highlights.run_if(resource_exists::<GliderHighlights>),
+                ),
+            )
+            // scrolling over an unbounded board
+            .add_systems(
+                Update,
+                (
+                    scroll_unbounded_world.run_if(resource_exists::<UnboundedWorld>),
+                    update_border_visibility.run_if(
+                        resource_added::<UnboundedWorld>
+                            .or(resource_removed::<UnboundedWorld>)
+                            .or(resource_changed::<ShowBorder>),
+                    ),
+                ),
             )
+            // adjusting the border around the boards
             .add_systems(
                 Update,
                 (
-                    handle_setup_kbd.run_if(in_state(GameState::Setup)),
-                    handle_cell_color_main.run_if(in_state(GameState::Running)),
-                    toggle_setup_and_running.run_if(
-                        input_just_pressed(KeyCode::Enter)
-                            .and(in_state(GameState::Running).or(in_state(GameState::Setup))),
+                    toggle_border.run_if(action_just_pressed(Action::ToggleBorder)),
+                    handle_border_width_kbd.run_if(any_action_just_pressed([
+                        Action::ThinnerBorder,
+                        Action::ThickerBorder,
+                    ])),
+                    respawn_borders.run_if(
+                        resource_changed::<BorderWidth>.and(not(resource_added::<BorderWidth>)),
+                    ),
+                )
+                    .chain()
+                    .run_if(not(in_state(GameState::Load))),
+            )
+            // inspecting the rules on the hovered cell
+            .add_systems(
+                Update,
+                (
+                    toggle_neighbour_count
+                        .run_if(action_just_pressed(Action::ToggleNeighbourCount)),
+                    toggle_probe.run_if(action_just_pressed(Action::ToggleProbe)),
+                    toggle_fades.run_if(action_just_pressed(Action::ToggleFades)),
+                    cycle_theme.run_if(action_just_pressed(Action::CycleTheme)),
+                    reload_config_file.run_if(
+                        action_just_pressed(Action::ReloadConfig)
+                            .and(not(in_state(GameState::Load))),
+                    ),
+                    apply_theme
+                        .run_if(resource_changed::<Theme>.and(resource_exists::<MeshAndMats>)),
+                    log_hovered_neighbour_count.run_if(
+                        resource_equals(ShowNeighbourCount(true))
+                            .and(in_state(GameState::Setup).or(in_state(GameState::Paused)))
+                            .and(
+                                resource_changed::<HoveredCell>
+                                    .or(resource_exists_and_changed::<LifeGrid>),
+                            ),
                     ),
                 ),
+            )
+            // editing the board with the keyboard
+            .add_systems(
+                Update,
+                (
+                    toggle_keyboard_cursor.run_if(
+                        action_just_pressed(Action::ToggleKeyboardCursor)
+                            .and(not(in_state(GameState::Load))),
+                    ),
+                    move_cursor_cell.run_if(resource_exists::<CursorCell>.and(
+                        any_action_just_pressed([
+                            Action::MoveCursorUp,
+                            Action::MoveCursorDown,
+                            Action::MoveCursorLeft,
+                            Action::MoveCursorRight,
+                        ]),
+                    )),
+                    paint_cursor_cell.run_if(
+                        resource_exists::<CursorCell>
+                            .and(action_just_pressed(Action::PaintCursorCell))
+                            .and(in_state(GameState::Setup)),
+                    ),
+                    highlight_cursor_cell.run_if(
+                        // the cell entities don't exist yet while loading
+                        not(in_state(GameState::Load)).and(
+                            resource_exists_and_changed::<CursorCell>
+                                .or(resource_removed::<CursorCell>)
+                                .or(resource_exists_and_changed::<CellEntities>),
+                        ),
+                    ),
+                )
+                    .chain(),
+            )
+            // the cell entities only mirror the grid for rendering
+            .add_systems(
+                PostUpdate,
+                (
+                    sync_cell_entities.run_if(
+                        resource_exists_and_changed::<LifeGrid>.or(resource_changed::<Generation>),
+                    ),
+                    // turning the preview off clears it once
+                    preview_future_life.run_if(
+                        resource_changed::<ShowFuture>.or(resource_equals(ShowFuture(true)).and(
+                            state_changed::<GameState>
+                                .or(resource_changed::<LifeGrid>)
+                                .or(resource_changed::<Ruleset>)
+                                .or(resource_changed::<Board>),
+                        )),
+                    ),
+                    (
+                        handle_cell_color_main,
+                        handle_cell_mesh_main,
+                        update_cell_depth,
+                    ),
+                    fade_cells.run_if(resource_equals(ShowFades(true))),
+                    // fading cells stay visible until they finish
+                    update_cell_visibility,
+                )
+                    .chain()
+                    .run_if(not(in_state(GameState::Load))),
             );
+        #[cfg(feature = "gpu-step")]
+        app.add_systems(Update, start_gpu_stepping.run_if(resource_changed::<Board>));
+    }
+}
+
+// ——> SCHEDULES
+
+/// Advances the board by exactly one generation.
+///
+/// Runs every fixed timestep while the game is running, or on demand to single-step the board.
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GenerationStep;
+
+// ——> BOARD ACCESS
+
+/// The cell entities of the main board, for reading the board from outside of `LifePlugin`.
+///
+/// The boards comparing other rulesets are left out, see `collect_board`.
+#[derive(SystemParam)]
+pub struct BoardCells<'w, 's> {
+    /// missing until the board is loaded
+    cell_entities: Option<Res<'w, CellEntities>>,
+    alive_query: Query<'w, 's, &'static CurrentAlive, With<Cell>>,
+}
+
+/// Collects the life status of the main board's cell entities, in row-major order with the
+/// bottom row first like `step_board`: the cell at `(x, y)` is at index `y * width + x`, see
+/// `Board::cell_coord_to_idx`.
+///
+/// The entities mirror the simulation as it was at the end of the last frame, cells that
+/// haven't been spawned yet are dead.
+pub fn collect_board(cells: &BoardCells, board: &Board) -> Vec<bool> {
+    let mut collected = vec![false; board.cell_count()];
+    let Some(cell_entities) = &cells.cell_entities else {
+        return collected;
+    };
+    for (alive, &entity) in collected.iter_mut().zip(cell_entities.iter()) {
+        if let Ok(current) = cells.alive_query.get(entity) {
+            *alive = **current;
+        }
+    }
+    collected
+}
+
+/// The simulated state of the main board, for setting the board from outside of `LifePlugin`.
+///
+/// The cell entities follow it at the end of the frame, see `apply_board`.
+#[derive(SystemParam)]
+pub struct BoardWriter<'w> {
+    grid: ResMut<'w, LifeGrid>,
+    history: ResMut<'w, EditHistory>,
+}
+
+/// Sets the life status of every cell of the main board from `cells`, in the row-major order of
+/// `collect_board`. The change can be undone like any other edit.
+///
+/// The cell entities pick up the change in `PostUpdate` and are repainted with the materials of
+/// their new life status.
+pub fn apply_board(
+    cells: &[bool],
+    writer: &mut BoardWriter,
+    board: &Board,
+) -> Result<(), CellCountMismatch> {
+    if cells.len() != board.cell_count() {
+        return Err(CellCountMismatch {
+            expected: board.cell_count(),
+            found: cells.len(),
+        });
+    }
+    let BoardWriter { grid, history } = writer;
+    replace_cells(grid, history, cells.to_vec());
+    Ok(())
+}
+
+/// the cells passed to `apply_board` don't fit the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellCountMismatch {
+    /// the amount of cells on the board
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl fmt::Display for CellCountMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected the {} cells of the board, found {}",
+            self.expected, self.found
+        )
+    }
+}
+
+impl Error for CellCountMismatch {}
+
+/// Replaces all of the cells of the grid as a single edit, `cells` have to fit the board.
+///
+/// Replacing them with the same cells isn't recorded, so change detection stays accurate.
+fn replace_cells(grid: &mut LifeGrid, history: &mut EditHistory, cells: Vec<bool>) {
+    if grid.cells != cells {
+        history.record(grid.replace(cells));
     }
 }
 
@@ -52,87 +756,204 @@ fn load_meshes_and_materials(
         ResMut<Assets<Mesh>>,
         ResMut<Assets<ColorMaterial>>,
         Res<Board>,
+        Res<BorderWidth>,
     )>,
 ) {
     // create material & mesh handles, and store them in the world
-    let (mut meshes, mut materials, board) = params.get_mut(world);
+    let (mut meshes, mut materials, board, border_width) = params.get_mut(world);
     let cell_mesh = meshes.add(Rectangle::from_size(board.cell_size));
-    let border_vert_mesh = meshes.add(Rectangle::new(
-        BORDER_WIDTH_PX,
-        board.pixel_size().y + 2.0 * BORDER_WIDTH_PX,
-    ));
-    let border_horiz_mesh = meshes.add(Rectangle::new(
-        board.pixel_size().x + 2.0 * BORDER_WIDTH_PX,
-        BORDER_WIDTH_PX,
-    ));
-    let border_mat = materials.add(ColorMaterial::from_color(BORDER_COLOR));
-    let cell_alive_mat = materials.add(ColorMaterial::from_color(CELL_ALIVE_COLOR));
-    let cell_dead_mat = materials.add(ColorMaterial::from_color(BG_COLOR));
-    let cell_clicked_mat = materials.add(ColorMaterial::from_color(CELL_CLICKED_COLOR));
-    let cell_hovered_alive_mat = materials.add(ColorMaterial::from_color(CELL_HOVERED_ALIVE_COLOR));
-    let cell_hovered_dead_mat = materials.add(ColorMaterial::from_color(CELL_HOVERED_DEAD_COLOR));
-
-    let meshes = HashMap::from([
-        ("cell", cell_mesh),
-        ("border_vert", border_vert_mesh),
-        ("border_horiz", border_horiz_mesh),
-    ]);
-    let materials = HashMap::from([
-        ("border", border_mat),
-        ("cell_alive", cell_alive_mat),
-        ("cell_dead", cell_dead_mat),
-        ("cell_clicked", cell_clicked_mat),
-        ("cell_hovered_alive", cell_hovered_alive_mat),
-        ("cell_hovered_dead", cell_hovered_dead_mat),
-    ]);
+    let cell_outline_mesh = meshes.add(hollow_rectangle(board.cell_size, CELL_OUTLINE_WIDTH_PX));
+    let (border_vert_mesh, border_horiz_mesh) =
+        add_border_meshes(&mut meshes, &board, **border_width);
+    let meshes = MeshKey::ALL.map(|key| match key {
+        MeshKey::Cell => cell_mesh.clone(),
+        MeshKey::CellOutline => cell_outline_mesh.clone(),
+        MeshKey::BorderVert => border_vert_mesh.clone(),
+        MeshKey::BorderHoriz => border_horiz_mesh.clone(),
+    });
+    // the colors are set from the theme below
+    let mut cell_material = || materials.add(ColorMaterial::default());
+    let materials = MaterialKey::ALL.map(|_| cell_material());
+    let age_materials = (0..AGE_BUCKETS).map(|_| cell_material()).collect();
+    let fade_materials = (0..CELL_FADE_STEPS).map(|_| cell_material()).collect();
+    let decay_materials = (0..DECAY_STEPS).map(|_| cell_material()).collect();
+    let heat_materials = (0..=HEATMAP_FADE_GENERATIONS)
+        .map(|_| cell_material())
+        .collect();
+
     // create an easily accessible resource for efficient reuse of materials and meshes
-    world.insert_resource(MeshAndMats { meshes, materials });
+    let meshes_and_mats = MeshAndMats {
+        meshes,
+        materials,
+        age_materials,
+        heat_materials,
+        fade_materials,
+        decay_materials,
+    };
+    let theme = *world.resource::<Theme>();
+    meshes_and_mats.recolor(&theme, &mut world.resource_mut::<Assets<ColorMaterial>>());
+    world.insert_resource(meshes_and_mats);
 }
 
 /// spawn game of life board
-fn load_cell_board(
-    world: &mut World,
-    params: &mut SystemState<(Res<MeshAndMats>, Res<Board>, ResMut<NextState<GameState>>)>,
-) {
-    let (meshes_and_mats, board, _) = params.get_mut(world);
-    // copy the board so that we can use it later
-    let board = *board;
+fn load_cell_board(world: &mut World) {
+    // add observers to support cell picking in the setup stage, unless the cells are picked
+    // from the cursor's position, see `CursorPicking`
+    if !world.contains_resource::<CursorPicking>() {
+        // hovering observer
+        world.add_observer(cells_interact_on::<Pointer<Over>>(CellPointerEvent::Over));
+        // end of hover observer
+        world.add_observer(cells_interact_on::<Pointer<Out>>(CellPointerEvent::Out));
+        // clicked observer
+        world.add_observer(cells_set_life_on::<Pointer<Down>>);
+        // drag-over observer
+        world.add_observer(cells_set_life_on::<Pointer<DragOver>>);
+        // stamping observer
+        world.add_observer(commit_stamp_preview);
+        // selection observers
+        world.add_observer(select_cells_on::<Pointer<Down>>);
+        world.add_observer(select_cells_on::<Pointer<DragOver>>);
+        // debug probe observer
+        world.add_observer(probe_cell);
+        // end of click observer
+        world.add_observer(cells_interact_on::<Pointer<Up>>(CellPointerEvent::Release));
+        // hovered cell tracking observers
+        world.add_observer(track_hovered_cell);
+        world.add_observer(untrack_hovered_cell);
+    }
 
-    let (alive_mat, dead_mat, clicked_mat, hovered_alive_mat, hovered_dead_mat) = (
-        meshes_and_mats
-            .materials
-            .get("cell_alive")
-            .unwrap()
-            .to_owned(),
-        meshes_and_mats
-            .materials
-            .get("cell_dead")
-            .unwrap()
-            .to_owned(),
-        meshes_and_mats
-            .materials
-            .get("cell_clicked")
-            .unwrap()
-            .to_owned(),
-        meshes_and_mats
-            .materials
-            .get("cell_hovered_alive")
-            .unwrap()
-            .to_owned(),
-        meshes_and_mats
-            .materials
-            .get("cell_hovered_dead")
-            .unwrap()
-            .to_owned(),
-    );
+    // large boards are spawned over several frames, see `spawn_board_chunk`
+    let loading = start_board_loading(world);
+    world.insert_resource(loading);
+    world.spawn((
+        LoadingText,
+        Text::new("loading board: 0%"),
+        TextFont {
+            font_size: HUD_FONT_SIZE,
+            ..default()
+        },
+        TextColor(HUD_TEXT_COLOR),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(8.0),
+            left: Val::Px(8.0),
+            ..default()
+        },
+    ));
+}
+
+/// Spawns the next chunk of the board being loaded and switches to setup once all of it is
+/// spawned.
+fn load_board_in_chunks(world: &mut World) {
+    let done = world.resource_scope(|world, mut loading: Mut<BoardLoading>| {
+        spawn_board_chunk(world, &mut loading, CELLS_SPAWNED_PER_FRAME);
+        let progress = loading.progress();
+        let mut text_query = world.query_filtered::<&mut Text, With<LoadingText>>();
+        for mut text in text_query.iter_mut(world) {
+            **text = format!("loading board: {:.0}%", progress * 100.0);
+        }
+        loading.is_done()
+    });
+    if !done {
+        return;
+    }
+    world.remove_resource::<BoardLoading>();
+    let mut text_query = world.query_filtered::<Entity, With<LoadingText>>();
+    for entity in text_query.iter(world).collect::<Vec<_>>() {
+        world.despawn(entity);
+    }
+    world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Setup);
+}
+
+/// Spawns the cells and borders of the board described by the `Board` resource, and inserts
+/// the matching `CellEntities` and an empty `LifeGrid`.
+fn spawn_board(world: &mut World) {
+    let mut loading = start_board_loading(world);
+    spawn_board_chunk(world, &mut loading, usize::MAX);
+}
+
+/// Inserts an empty `LifeGrid` for the board described by the `Board` resource and returns the
+/// spawning work of it and of the boards comparing other rulesets.
+fn start_board_loading(world: &mut World) -> BoardLoading {
+    let board = *world.resource::<Board>();
+    world.insert_resource(LifeGrid::new(board.cell_count()));
+
+    let mut boards = VecDeque::from([(board, None)]);
+    // the boards comparing other rulesets are laid out to the right
+    let rulesets = world.resource::<ComparedRulesets>().clone();
+    for (i, rules) in rulesets.iter().enumerate() {
+        let offset = (i + 1) as f32 * (board.pixel_size().x + COMPARED_BOARD_GAP_PX);
+        let compared_board = Board {
+            center: board.center + vec2(offset, 0.0),
+            ..board
+        };
+        boards.push_back((compared_board, Some(*rules)));
+    }
+    let total = boards.len() * board.cell_count();
+    BoardLoading {
+        boards,
+        cell_entities: Vec::new(),
+        spawned: 0,
+        total,
+    }
+}
+
+/// Spawns up to `max_cells` cells of the boards being loaded. Once all the cells of a board are
+/// spawned its borders are spawned and its cell entities are stored.
+fn spawn_board_chunk(world: &mut World, loading: &mut BoardLoading, max_cells: usize) {
+    let meshes_and_mats = world.resource::<MeshAndMats>().clone();
+    let mut budget = max_cells;
+    while let Some(&(board, rules)) = loading.boards.front() {
+        let start = loading.cell_entities.len();
+        let end = start.saturating_add(budget).min(board.cell_count());
+        let entities = spawn_cells(world, &board, &meshes_and_mats, start..end);
+        loading.cell_entities.extend(entities);
+        loading.spawned += end - start;
+        budget -= end - start;
+        if end < board.cell_count() {
+            break;
+        }
+
+        loading.boards.pop_front();
+        let cell_entities = std::mem::take(&mut loading.cell_entities);
+        spawn_borders(world, &board, &meshes_and_mats);
+        match rules {
+            None => world.insert_resource(CellEntities(cell_entities)),
+            Some(rules) => {
+                // only the main board can be edited
+                for &entity in &cell_entities {
+                    world.entity_mut(entity).insert(PickingBehavior::IGNORE);
+                }
+                world.spawn(ComparedBoard {
+                    board,
+                    rules,
+                    grid: LifeGrid::new(board.cell_count()),
+                    cell_entities,
+                });
+            }
+        }
+    }
+}
 
-    let coords_iter = (0..board.size).flat_map(|y| (0..board.size).map(move |x| uvec2(x, y)));
+/// spawns the cells of a board in the range of indices, returning their entities indexed by
+/// `Board::cell_coord_to_idx`
+fn spawn_cells(
+    world: &mut World,
+    board: &Board,
+    meshes_and_mats: &MeshAndMats,
+    idx_range: Range<usize>,
+) -> Vec<Entity> {
+    let dead_mat = meshes_and_mats.material(MaterialKey::CellDead).to_owned();
+
+    let coords_iter = idx_range.map(|idx| board.idx_to_cell_coord(idx));
     let cells_to_spawn = coords_iter
-        .clone()
         .map(|cell_coord| {
             (
                 Cell,
-                Mesh2d(meshes_and_mats.meshes.get("cell").unwrap().to_owned()),
+                CellIdx(board.cell_coord_to_idx(cell_coord)),
+                Mesh2d(meshes_and_mats.mesh(MeshKey::Cell).to_owned()),
                 MeshMaterial2d(dead_mat.clone()),
                 // CurrentAlive(fastrand::bool()),
                 Transform::from_translation(board.cell_coord_to_translation(cell_coord))
@@ -140,59 +961,22 @@ fn load_cell_board(
             )
         })
         .collect::<Vec<_>>();
-    // spawn cells
-    let entities: Vec<_> = world.spawn_batch(cells_to_spawn).collect();
-
-    // add observers to support cell picking in the setup stage.
-    //
-    // hovering observer
-    world.add_observer(cells_set_mats_on::<Pointer<Over>>(
-        hovered_alive_mat.clone(),
-        hovered_dead_mat.clone(),
-    ));
-    // end of hover observer
-    world.add_observer(cells_set_mats_on::<Pointer<Out>>(alive_mat, dead_mat));
-    // clicked observer
-    world.add_observer(cells_set_life_on::<Pointer<Down>>(clicked_mat.clone()));
-    // drag-over observer
-    world.add_observer(cells_set_life_on::<Pointer<DragOver>>(clicked_mat));
-    // end of click observer
-    world.add_observer(cells_set_mats_on::<Pointer<Up>>(
-        hovered_alive_mat,
-        hovered_dead_mat,
-    ));
-
-    let neighbours = (0..entities.len())
-        .map(|i| {
-            let neighbour_entity_indices = board.neighbour_indices(board.idx_to_cell_coord(i));
-
-            // temporarily initialize with the default value
-            let mut neigh_entities = [entities[0]; 8];
-            for (i, neigh_idx) in neighbour_entity_indices.into_iter().enumerate() {
-                neigh_entities[i] = entities[neigh_idx];
-            }
-            neigh_entities
-        })
-        .map(Neighbours)
-        .collect::<Vec<_>>();
-
-    let pairs = entities.into_iter().zip(neighbours);
-    // add neighbours to the cells
-    world.insert_batch(pairs);
+    world.spawn_batch(cells_to_spawn).collect()
+}
 
-    // create borders
-    let (meshes_and_mats, _, _) = params.get_mut(world);
+/// Spawns the four sides of the border around a board, using the current border settings.
+///
+/// The border meshes have to fit the board, see `replace_border_meshes`.
+fn spawn_borders(world: &mut World, board: &Board, meshes_and_mats: &MeshAndMats) {
+    let width = **world.resource::<BorderWidth>();
+    let visibility = border_visibility(
+        **world.resource::<ShowBorder>(),
+        world.contains_resource::<UnboundedWorld>(),
+    );
+    let show_wrap_indicator = **world.resource::<ShowWrapIndicator>();
     // meshes
-    let border_vert = meshes_and_mats
-        .meshes
-        .get("border_vert")
-        .unwrap()
-        .to_owned();
-    let border_horiz = meshes_and_mats
-        .meshes
-        .get("border_horiz")
-        .unwrap()
-        .to_owned();
+    let border_vert = meshes_and_mats.mesh(MeshKey::BorderVert).to_owned();
+    let border_horiz = meshes_and_mats.mesh(MeshKey::BorderHoriz).to_owned();
     // create vertical and horizontal meshes and transforms
     let border_mesh_and_transforms = (0..4).map(|i| {
         // vertical
@@ -202,15 +986,13 @@ fn load_cell_board(
                 // left
                 if i / 2 == 0 {
                     let pos = board.center
-                        - (board.pixel_size().with_y(0.0) * 0.5
-                            + Vec2::new(BORDER_WIDTH_PX, 0.0) * 0.5);
-                    Transform::from_translation(pos.extend(0.0))
+                        - (board.pixel_size().with_y(0.0) * 0.5 + Vec2::new(width, 0.0) * 0.5);
+                    Transform::from_translation(pos.extend(BORDER_Z))
                 // or right
                 } else {
                     let pos = board.center
-                        + (board.pixel_size().with_y(0.0) * 0.5
-                            + Vec2::new(BORDER_WIDTH_PX, 0.0) * 0.5);
-                    Transform::from_translation(pos.extend(0.0))
+                        + (board.pixel_size().with_y(0.0) * 0.5 + Vec2::new(width, 0.0) * 0.5);
+                    Transform::from_translation(pos.extend(BORDER_Z))
                 },
             )
         // or horizontal
@@ -220,326 +1002,5127 @@ fn load_cell_board(
                 // up
                 if i / 2 == 0 {
                     let pos = board.center
-                        + (board.pixel_size().with_x(0.0) * 0.5
-                            + Vec2::new(0.0, BORDER_WIDTH_PX) * 0.5);
-                    Transform::from_translation(pos.extend(0.0))
+                        + (board.pixel_size().with_x(0.0) * 0.5 + Vec2::new(0.0, width) * 0.5);
+                    Transform::from_translation(pos.extend(BORDER_Z))
                 // or down
                 } else {
                     let pos = board.center
-                        - (board.pixel_size().with_x(0.0) * 0.5
-                            + Vec2::new(0.0, BORDER_WIDTH_PX) * 0.5);
-                    Transform::from_translation(pos.extend(0.0))
+                        - (board.pixel_size().with_x(0.0) * 0.5 + Vec2::new(0.0, width) * 0.5);
+                    Transform::from_translation(pos.extend(BORDER_Z))
                 },
             )
         }
     });
-    let border_mat = meshes_and_mats.materials.get("border").unwrap().to_owned();
     // connect all the components in a bundle
     let borders = border_mesh_and_transforms
-        .map(|(mesh, transform)| (Border, MeshMaterial2d(border_mat.clone()), mesh, transform));
+        .enumerate()
+        .map(|(i, (mesh, transform))| {
+            let border = Border {
+                vertical: i % 2 == 0,
+            };
+            let material_key = border_material_key(board, border, show_wrap_indicator);
+            (
+                border,
+                MeshMaterial2d(meshes_and_mats.material(material_key).to_owned()),
+                mesh,
+                transform,
+                visibility,
+            )
+        })
+        .collect::<Vec<_>>();
     world.spawn_batch(borders);
+}
 
-    let (_, _, mut game_state) = params.get_mut(world);
-    game_state.set(GameState::Setup);
+/// replaces the border meshes with ones that fit the board and the border's current width
+fn replace_border_meshes(world: &mut World) {
+    let board = *world.resource::<Board>();
+    let width = **world.resource::<BorderWidth>();
+    world.resource_scope(|world, mut meshes_and_mats: Mut<MeshAndMats>| {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let (border_vert, border_horiz) = add_border_meshes(&mut meshes, &board, width);
+        meshes_and_mats.meshes[MeshKey::BorderVert as usize] = border_vert;
+        meshes_and_mats.meshes[MeshKey::BorderHoriz as usize] = border_horiz;
+    });
 }
 
-/// Returns an observer that changes the life status of a cell when clicked on, while also
-/// highlighting that cell by changing its material.
-fn cells_set_life_on<E>(
-    highlight_mat: Handle<ColorMaterial>,
-) -> impl Fn(
-    Trigger<E>,
-    Query<(&mut MeshMaterial2d<ColorMaterial>, &mut CurrentAlive), With<Cell>>,
-    Res<State<GameState>>,
+/// respawns the borders of the main board and the compared ones to fit the border's width
+fn respawn_borders(
+    world: &mut World,
+    params: &mut SystemState<(Query<Entity, With<Border>>, Query<&ComparedBoard>)>,
 ) {
-    move |trigger, mut query, state| {
-        if matches!(state.get(), GameState::Setup) {
-            if let Ok((mut material, mut alive)) = query.get_mut(trigger.entity()) {
-                material.0 = highlight_mat.clone();
-                alive.0 = !alive.0;
-            }
-        }
+    let (border_query, compared_query) = params.get(world);
+    let to_despawn = border_query.iter().collect::<Vec<_>>();
+    let compared_boards = compared_query
+        .iter()
+        .map(|compared| compared.board)
+        .collect::<Vec<_>>();
+
+    for entity in to_despawn {
+        world.despawn(entity);
+    }
+    replace_border_meshes(world);
+    let meshes_and_mats = world.resource::<MeshAndMats>().clone();
+    let board = *world.resource::<Board>();
+    for board in std::iter::once(board).chain(compared_boards) {
+        spawn_borders(world, &board, &meshes_and_mats);
     }
 }
 
-/// Returns an observer that updates the cell's material to one of the specified materials,
-/// depending on the cell's life status.
-fn cells_set_mats_on<E>(
-    new_mat_alive: Handle<ColorMaterial>,
-    new_mat_dead: Handle<ColorMaterial>,
-) -> impl Fn(
-    Trigger<E>,
-    Query<(&mut MeshMaterial2d<ColorMaterial>, &CurrentAlive), With<Cell>>,
-    Res<State<GameState>>,
+fn toggle_border(mut show_border: ResMut<ShowBorder>) {
+    **show_border = !**show_border;
+    info!("border: {}", **show_border);
+}
+
+fn handle_border_width_kbd(
+    mut border_width: ResMut<BorderWidth>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
 ) {
-    move |trigger, mut query, state| {
-        if matches!(state.get(), GameState::Setup) {
-            if let Ok((mut material, alive)) = query.get_mut(trigger.entity()) {
-                if alive.0 {
-                    material.0 = new_mat_alive.clone();
-                } else {
-                    material.0 = new_mat_dead.clone();
-                }
-            }
+    let width = if bindings.just_pressed(&keyboard_input, Action::ThinnerBorder) {
+        **border_width - BORDER_WIDTH_STEP_PX
+    } else if bindings.just_pressed(&keyboard_input, Action::ThickerBorder) {
+        **border_width + BORDER_WIDTH_STEP_PX
+    } else {
+        return;
+    };
+    let width = width.clamp(MIN_BORDER_WIDTH_PX, MAX_BORDER_WIDTH_PX);
+    if border_width.set_if_neq(BorderWidth(width)) {
+        info!("border width: {width}px");
+    }
+}
+
+/// Paints a cell when clicked on, while also highlighting that cell.
+///
+/// The left mouse button always sets cells alive and the right one always kills them, so
+/// dragging over cells that are already alive / dead doesn't toggle them back.
+/// All the cells changed during a single click / drag stroke are recorded as a single edit, and
+/// each of them is changed at most once, so dragging back over them can't undo the stroke.
+/// Every cell within the `BrushSize` around the hit cell is painted, along with its mirror
+/// images when painting with `Symmetry`. Nothing is painted while selecting cells.
+fn cells_set_life_on<E: PaintEvent>(
+    trigger: Trigger<E>,
+    mut query: Query<(&mut CellInteraction, &CellIdx), With<Cell>>,
+    (state, keyboard_input): (Res<State<GameState>>, Res<ButtonInput<KeyCode>>),
+    (board, probe, preview): (Res<Board>, Res<CellProbe>, Option<Res<StampPreview>>),
+    (brush_size, symmetry): (Res<BrushSize>, Res<Symmetry>),
+    (mut history, mut grid, mut toggled): (
+        ResMut<EditHistory>,
+        ResMut<LifeGrid>,
+        EventWriter<CellToggled>,
+    ),
+) {
+    // clicking stamps the previewed pattern instead, see `commit_stamp_preview`
+    if !matches!(state.get(), GameState::Setup)
+        || is_selecting(&keyboard_input)
+        || is_probing(&keyboard_input, &probe)
+        || preview.is_some()
+    {
+        return;
+    }
+    let paint_alive = match trigger.event().button() {
+        PointerButton::Primary => true,
+        PointerButton::Secondary => false,
+        PointerButton::Middle => return,
+    };
+    if let Ok((mut interaction, idx)) = query.get_mut(trigger.entity()) {
+        let next = interaction.transition(CellPointerEvent::Press);
+        interaction.set_if_neq(next);
+        let cell_coord = board.idx_to_cell_coord(**idx);
+        paint_stroke(
+            &board,
+            (&mut history, &mut grid, &mut toggled),
+            cell_coord,
+            paint_alive,
+            (*brush_size, *symmetry),
+        );
+    }
+}
+
+/// Paints the cells at `cell_coord` like `paint_cells`, recording the whole stroke as one edit.
+///
+/// The cells changed earlier in the stroke are left alone, see `EditHistory::stroke_cells`.
+fn paint_stroke(
+    board: &Board,
+    (history, grid, toggled): (
+        &mut EditHistory,
+        &mut ResMut<LifeGrid>,
+        &mut EventWriter<CellToggled>,
+    ),
+    cell_coord: UVec2,
+    paint_alive: bool,
+    brush: (BrushSize, Symmetry),
+) {
+    if !history.stroke_in_progress {
+        history.stroke_in_progress = true;
+        history.stroke_cells.clear();
+        history.record(grid.cells.clone());
+    }
+    paint_cells(
+        board,
+        (grid, toggled),
+        cell_coord,
+        paint_alive,
+        brush,
+        &mut history.stroke_cells,
+    );
+}
+
+/// Paints the cells under the brush at `cell_coord` and their mirror images, sending a
+/// `CellToggled` for each cell that changed.
+///
+/// The cells in `stroke_cells` are skipped and the changed ones are added to it.
+fn paint_cells(
+    board: &Board,
+    (grid, toggled): (&mut ResMut<LifeGrid>, &mut EventWriter<CellToggled>),
+    cell_coord: UVec2,
+    paint_alive: bool,
+    (brush_size, symmetry): (BrushSize, Symmetry),
+    stroke_cells: &mut HashSet<usize>,
+) {
+    let mirrored_indices = board
+        .brush_indices(cell_coord, *brush_size)
+        .flat_map(|idx| symmetry.mirror_cell_coords(board, board.idx_to_cell_coord(idx)))
+        .map(|cell_coord| board.cell_coord_to_idx(cell_coord))
+        .collect::<Vec<_>>();
+    for idx in mirrored_indices {
+        // only touch the grid when a cell changes, so change detection stays accurate
+        if grid.cells[idx] != paint_alive && stroke_cells.insert(idx) {
+            grid.set_alive(idx, paint_alive);
+            toggled.send(CellToggled {
+                cell_coord: board.idx_to_cell_coord(idx),
+                alive: paint_alive,
+            });
         }
     }
 }
 
-fn handle_setup_kbd(
-    mut cell_query: Query<(&mut CurrentAlive, &mut MeshMaterial2d<ColorMaterial>), With<Cell>>,
-    meshes_and_mats: Res<MeshAndMats>,
+/// shows the keyboard cursor on the center of the board, or hides it
+fn toggle_keyboard_cursor(
+    mut commands: Commands,
+    cursor: Option<Res<CursorCell>>,
+    board: Res<Board>,
+) {
+    if cursor.is_some() {
+        commands.remove_resource::<CursorCell>();
+    } else {
+        commands.insert_resource(CursorCell(board.center_cell_coord()));
+    }
+    info!("keyboard cursor: {}", cursor.is_none());
+}
+
+/// moves the keyboard cursor by a cell, wrapping around the edges of a wrapping board
+fn move_cursor_cell(
+    mut cursor: ResMut<CursorCell>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    board: Res<Board>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::KeyR) {
-        for (mut alive, mut material) in cell_query.iter_mut() {
-            alive.0 = fastrand::bool();
-            if alive.0 {
-                material.0 = meshes_and_mats
-                    .materials
-                    .get("cell_alive")
-                    .unwrap()
-                    .to_owned();
-            } else {
-                material.0 = meshes_and_mats
-                    .materials
-                    .get("cell_dead")
-                    .unwrap()
-                    .to_owned();
-            }
+    let offset = [
+        (Action::MoveCursorUp, IVec2::Y),
+        (Action::MoveCursorDown, IVec2::NEG_Y),
+        (Action::MoveCursorLeft, IVec2::NEG_X),
+        (Action::MoveCursorRight, IVec2::X),
+    ]
+    .into_iter()
+    .filter(|&(action, _)| bindings.just_pressed(&keyboard_input, action))
+    .map(|(_, offset)| offset)
+    .sum::<IVec2>();
+    let cell_coord = cursor.as_ivec2() + offset;
+    let moved = board.wrapped_cell_coord(cell_coord).unwrap_or_else(|| {
+        cell_coord
+            .clamp(IVec2::ZERO, board.size().as_ivec2() - 1)
+            .as_uvec2()
+    });
+    cursor.set_if_neq(CursorCell(moved));
+}
+
+/// paints the cells under the keyboard cursor like a click, with the brush and symmetry
+fn paint_cursor_cell(
+    cursor: Res<CursorCell>,
+    (board, brush_size, symmetry): (Res<Board>, Res<BrushSize>, Res<Symmetry>),
+    mut history: ResMut<EditHistory>,
+    mut grid: ResMut<LifeGrid>,
+    mut toggled: EventWriter<CellToggled>,
+) {
+    if !board.contains(cursor.as_ivec2()) {
+        return;
+    }
+    let paint_alive = !grid.cells[board.cell_coord_to_idx(**cursor)];
+    history.record(grid.cells.clone());
+    // every key press is a stroke of its own
+    paint_cells(
+        &board,
+        (&mut grid, &mut toggled),
+        **cursor,
+        paint_alive,
+        (*brush_size, *symmetry),
+        &mut HashSet::new(),
+    );
+}
+
+/// marks the cell under the keyboard cursor, see `UnderCursor`
+fn highlight_cursor_cell(
+    mut cursor_query: Query<&mut UnderCursor, With<Cell>>,
+    cursor: Option<Res<CursorCell>>,
+    cell_entities: Res<CellEntities>,
+    board: Res<Board>,
+) {
+    // the cursor might have been left outside of a board that shrunk
+    let cursor_idx = cursor
+        .filter(|cursor| board.contains(cursor.as_ivec2()))
+        .map(|cursor| board.cell_coord_to_idx(**cursor));
+    for (idx, &entity) in cell_entities.iter().enumerate() {
+        if let Ok(mut under_cursor) = cursor_query.get_mut(entity) {
+            under_cursor.set_if_neq(UnderCursor(Some(idx) == cursor_idx));
         }
     }
 }
 
-fn toggle_setup_and_running(
+/// Selects the rectangle between the cell the left mouse button was pressed on and the cell it
+/// is dragged over, while shift is held.
+fn select_cells_on<E: PaintEvent>(
+    trigger: Trigger<E>,
+    query: Query<&CellIdx, With<Cell>>,
     state: Res<State<GameState>>,
-    mut next_state: ResMut<NextState<GameState>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    board: Res<Board>,
+    mut selection: ResMut<CellSelection>,
 ) {
-    match state.get() {
-        GameState::Setup => next_state.set(GameState::Running),
-        GameState::Running => next_state.set(GameState::Setup),
-        _ => unreachable!(),
+    let event = trigger.event();
+    if !matches!(state.get(), GameState::Setup)
+        || !is_selecting(&keyboard_input)
+        || event.button() != PointerButton::Primary
+    {
+        return;
     }
+    let Ok(idx) = query.get(trigger.entity()) else {
+        return;
+    };
+    select_cells(
+        &mut selection,
+        board.idx_to_cell_coord(**idx),
+        event.starts_stroke(),
+    );
 }
 
-fn handle_cell_color_main(
-    mut cell_query: Query<
-        (&mut MeshMaterial2d<ColorMaterial>, &CurrentAlive),
-        (
-            With<Cell>,
-            Or<(
-                Changed<CurrentAlive>,
-                Changed<MeshMaterial2d<ColorMaterial>>,
-            )>,
-        ),
-    >,
-    mesh_n_mats: Res<MeshAndMats>,
+/// extends the selection to `cell_coord`, or starts a new one there
+fn select_cells(selection: &mut CellSelection, cell_coord: UVec2, starts_stroke: bool) {
+    let start = match **selection {
+        Some((start, _)) if !starts_stroke => start,
+        _ => cell_coord,
+    };
+    **selection = Some((start, cell_coord));
+}
+
+/// run condition that is true while either of the shift keys is held, see `is_selecting`
+fn shift_pressed(keyboard_input: Res<ButtonInput<KeyCode>>) -> bool {
+    is_selecting(&keyboard_input)
+}
+
+fn is_selecting(keyboard_input: &ButtonInput<KeyCode>) -> bool {
+    keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
+}
+
+/// Logs everything about a cell clicked on while alt is held, when the `CellProbe` is enabled.
+///
+/// Compares the cell entity against the grid it mirrors and lists the entities of the
+/// neighbours it is computed from, in any state of the game.
+fn probe_cell(
+    trigger: Trigger<Pointer<Down>>,
+    query: Query<(&CellIdx, &CurrentAlive), With<Cell>>,
+    (keyboard_input, probe): (Res<ButtonInput<KeyCode>>, Res<CellProbe>),
+    (board, rules): (Res<Board>, Res<Ruleset>),
+    grid: Res<LifeGrid>,
+    cell_entities: Res<CellEntities>,
 ) {
-    for (mut material, cell_alive) in cell_query.iter_mut() {
-        if **cell_alive {
-            **material = mesh_n_mats.materials.get("cell_alive").unwrap().to_owned();
-        } else {
-            **material = mesh_n_mats.materials.get("cell_dead").unwrap().to_owned();
+    if is_probing(&keyboard_input, &probe) {
+        log_cell_probe(
+            trigger.entity(),
+            &query,
+            (&board, &rules),
+            &grid,
+            &cell_entities,
+        );
+    }
+}
+
+/// Probes the hovered cell when it's clicked, like `probe_cell` does when the cell meshes are
+/// picked, see `CursorPicking`.
+fn probe_picked_cell(
+    query: Query<(&CellIdx, &CurrentAlive), With<Cell>>,
+    (mouse_input, keyboard_input, probe): (
+        Res<ButtonInput<MouseButton>>,
+        Res<ButtonInput<KeyCode>>,
+        Res<CellProbe>,
+    ),
+    (board, rules): (Res<Board>, Res<Ruleset>),
+    grid: Res<LifeGrid>,
+    (cell_entities, hovered_cell): (Res<CellEntities>, Res<HoveredCell>),
+) {
+    if mouse_input.get_just_pressed().next().is_none() || !is_probing(&keyboard_input, &probe) {
+        return;
+    }
+    let Some(cell_coord) = **hovered_cell else {
+        return;
+    };
+    if let Some(&entity) = cell_entities.get(board.cell_coord_to_idx(cell_coord)) {
+        log_cell_probe(entity, &query, (&board, &rules), &grid, &cell_entities);
+    }
+}
+
+/// see `probe_cell`
+fn log_cell_probe(
+    entity: Entity,
+    query: &Query<(&CellIdx, &CurrentAlive), With<Cell>>,
+    (board, rules): (&Board, &Ruleset),
+    grid: &LifeGrid,
+    cell_entities: &CellEntities,
+) {
+    let Ok((idx, current)) = query.get(entity) else {
+        return;
+    };
+    let cell_coord = board.idx_to_cell_coord(**idx);
+    let neighbours = board
+        .neighbour_indices(cell_coord, rules.neighbourhood)
+        .into_iter()
+        .map(|neigh_idx| match neigh_idx {
+            Some(neigh_idx) => {
+                let neigh_coord = board.idx_to_cell_coord(neigh_idx);
+                format!(
+                    "({}, {}) {}",
+                    neigh_coord.x, neigh_coord.y, cell_entities[neigh_idx]
+                )
+            }
+            None => "none".to_owned(),
+        })
+        .collect::<Vec<_>>();
+    info!(
+        "cell ({}, {}) {}: idx {}, alive in grid: {}, CurrentAlive: {}, alive next: {}, \
+         {} live neighbours: [{}]",
+        cell_coord.x,
+        cell_coord.y,
+        entity,
+        **idx,
+        grid.cells[**idx],
+        **current,
+        grid.next_alive(board, rules, **idx),
+        grid.live_neighbours(board, rules.neighbourhood, **idx),
+        neighbours.join(", ")
+    );
+}
+
+fn is_probing(keyboard_input: &ButtonInput<KeyCode>, probe: &CellProbe) -> bool {
+    **probe && keyboard_input.any_pressed([KeyCode::AltLeft, KeyCode::AltRight])
+}
+
+/// Returns an observer that updates the cell's interaction state, the cell's material is then
+/// derived from it in `handle_cell_color_main`.
+fn cells_interact_on<E>(
+    event: CellPointerEvent,
+) -> impl Fn(Trigger<E>, Query<&mut CellInteraction, With<Cell>>, Res<State<GameState>>) {
+    move |trigger, mut query, state| {
+        if matches!(state.get(), GameState::Setup) {
+            if let Ok(mut interaction) = query.get_mut(trigger.entity()) {
+                let next = interaction.transition(event);
+                interaction.set_if_neq(next);
+            }
         }
     }
 }
 
-fn update_cell_future_life(
-    mut cell_query: Query<(&mut FutureAlive, &Neighbours), With<Cell>>,
-    immutable_query: Query<&CurrentAlive, With<Cell>>,
+/// keeps track of the cell under the pointer
+fn track_hovered_cell(
+    trigger: Trigger<Pointer<Over>>,
+    cell_query: Query<&Transform, With<Cell>>,
+    board: Res<Board>,
+    mut hovered_cell: ResMut<HoveredCell>,
 ) {
-    for (mut future, neighbours) in cell_query.iter_mut() {
-        let nval = immutable_query
-            .many(**neighbours)
-            .map(|curr| if **curr { 1u8 } else { 0 })
-            .iter()
-            .sum::<u8>();
+    if let Ok(transform) = cell_query.get(trigger.entity()) {
+        **hovered_cell = board.translation_to_cell_coord(transform.translation.truncate());
+    }
+}
 
-        match nval {
-            3 => **future = Some(true),
-            2 => (),
-            _ => **future = Some(false),
+fn untrack_hovered_cell(
+    trigger: Trigger<Pointer<Out>>,
+    cell_query: Query<&Transform, With<Cell>>,
+    board: Res<Board>,
+    mut hovered_cell: ResMut<HoveredCell>,
+) {
+    if let Ok(transform) = cell_query.get(trigger.entity()) {
+        // the pointer might have already moved over another cell
+        if **hovered_cell == board.translation_to_cell_coord(transform.translation.truncate()) {
+            **hovered_cell = None;
         }
     }
 }
 
-fn update_cell_current_life(
-    mut cell_query: Query<
-        (&mut FutureAlive, &mut CurrentAlive),
-        (With<Cell>, Changed<FutureAlive>),
-    >,
+/// Tracks the cell under the cursor and updates the `CellInteraction` of the cells it moves
+/// over, like the hovering observers do when the cell meshes are picked, see `CursorPicking`.
+///
+/// The cursor isn't over any cell while it's over the UI, so nothing is painted through it.
+fn pick_hovered_cell(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    cam_query: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    mut interaction_query: Query<&mut CellInteraction, With<Cell>>,
+    (board, cell_entities, state): (Res<Board>, Res<CellEntities>, Res<State<GameState>>),
+    (hover_map, ui_query): (Res<HoverMap>, Query<(), With<Node>>),
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut hovered_cell: ResMut<HoveredCell>,
 ) {
-    for (mut fut, mut curr) in cell_query.iter_mut() {
-        if let Some(alive) = **fut {
-            **curr = alive;
-            **fut = None;
+    let Ok((camera, cam_transform)) = cam_query.get_single() else {
+        return;
+    };
+    let over_ui = hover_map
+        .get(&PointerId::Mouse)
+        .is_some_and(|hits| hits.keys().any(|&entity| ui_query.contains(entity)));
+    let cell_coord = window_query
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .and_then(|pos| camera.viewport_to_world_2d(cam_transform, pos).ok())
+        .and_then(|world_pos| board.translation_to_cell_coord(world_pos))
+        .filter(|_| !over_ui);
+
+    let in_setup = matches!(state.get(), GameState::Setup);
+    let mut interact = |cell_coord: Option<UVec2>, event| {
+        if let Some(cell_coord) = cell_coord.filter(|_| in_setup) {
+            interact_with_cell(
+                &mut interaction_query,
+                (&board, &cell_entities),
+                cell_coord,
+                event,
+            );
         }
+    };
+    // only touch the hovered cell when it changes, so change detection stays accurate
+    if **hovered_cell != cell_coord {
+        interact(**hovered_cell, CellPointerEvent::Out);
+        interact(cell_coord, CellPointerEvent::Over);
+        **hovered_cell = cell_coord;
+    }
+    if mouse_input.any_just_released([MouseButton::Left, MouseButton::Right]) {
+        interact(cell_coord, CellPointerEvent::Release);
     }
 }
 
-// ——> COMPONENTS
+/// Paints, selects and stamps the hovered cell like the click and drag observers do when the
+/// cell meshes are picked, see `CursorPicking`.
+fn paint_picked_cells(
+    mut commands: Commands,
+    mut picking: ResMut<CursorPicking>,
+    mut interaction_query: Query<&mut CellInteraction, With<Cell>>,
+    (mouse_input, keyboard_input, probe): (
+        Res<ButtonInput<MouseButton>>,
+        Res<ButtonInput<KeyCode>>,
+        Res<CellProbe>,
+    ),
+    (hovered_cell, preview, orientation): (
+        Res<HoveredCell>,
+        Option<Res<StampPreview>>,
+        Res<StampOrientation>,
+    ),
+    (board, cell_entities, brush_size, symmetry): (
+        Res<Board>,
+        Res<CellEntities>,
+        Res<BrushSize>,
+        Res<Symmetry>,
+    ),
+    (mut history, mut grid, mut selection, mut toggled): (
+        ResMut<EditHistory>,
+        ResMut<LifeGrid>,
+        ResMut<CellSelection>,
+        EventWriter<CellToggled>,
+    ),
+) {
+    const PAINT_BUTTONS: [MouseButton; 2] = [MouseButton::Left, MouseButton::Right];
 
-#[derive(Component)]
-#[require(CurrentAlive, FutureAlive, Mesh2d)]
-struct Cell;
+    // the previewed pattern is stamped once the click is released over the pressed cell
+    if let (Some(preview), Some(pressed)) = (&preview, picking.pressed) {
+        if mouse_input.just_released(MouseButton::Left)
+            && **hovered_cell == Some(pressed)
+            && !is_selecting(&keyboard_input)
+            && !is_probing(&keyboard_input, &probe)
+        {
+            stamp_preview_at(
+                &mut commands,
+                preview,
+                pressed,
+                (&board, &orientation),
+                (&mut grid, &mut history),
+            );
+        }
+    }
+    if !mouse_input.any_pressed(PAINT_BUTTONS) {
+        picking.set_if_neq(CursorPicking::default());
+        return;
+    }
+    let starts_stroke = mouse_input.any_just_pressed(PAINT_BUTTONS);
+    if starts_stroke {
+        picking.pressed = **hovered_cell;
+    }
+    // dragging only paints the cells it enters
+    let Some(cell_coord) = **hovered_cell else {
+        return;
+    };
+    if !starts_stroke && picking.last_picked == Some(cell_coord) {
+        return;
+    }
+    picking.last_picked = Some(cell_coord);
 
-#[derive(Component, Debug, Default, DerefMut, Deref)]
-struct CurrentAlive(bool);
+    let paint_alive = mouse_input.pressed(MouseButton::Left);
+    if is_selecting(&keyboard_input) {
+        if paint_alive {
+            select_cells(&mut selection, cell_coord, starts_stroke);
+        }
+        return;
+    }
+    // clicking stamps the previewed pattern instead
+    if is_probing(&keyboard_input, &probe) || preview.is_some() {
+        return;
+    }
+    interact_with_cell(
+        &mut interaction_query,
+        (&board, &cell_entities),
+        cell_coord,
+        CellPointerEvent::Press,
+    );
+    paint_stroke(
+        &board,
+        (&mut history, &mut grid, &mut toggled),
+        cell_coord,
+        paint_alive,
+        (*brush_size, *symmetry),
+    );
+}
 
-#[derive(Component, Debug, Default, DerefMut, Deref)]
-struct FutureAlive(Option<bool>);
+/// transitions the `CellInteraction` of the cell at `cell_coord` of the main board
+fn interact_with_cell(
+    interaction_query: &mut Query<&mut CellInteraction, With<Cell>>,
+    (board, cell_entities): (&Board, &CellEntities),
+    cell_coord: UVec2,
+    event: CellPointerEvent,
+) {
+    let Some(&entity) = cell_entities.get(board.cell_coord_to_idx(cell_coord)) else {
+        return;
+    };
+    if let Ok(mut interaction) = interaction_query.get_mut(entity) {
+        let next = interaction.transition(event);
+        interaction.set_if_neq(next);
+    }
+}
 
-#[derive(Component, Debug, DerefMut, Deref)]
-struct Neighbours([Entity; 8]);
+fn toggle_probe(mut probe: ResMut<CellProbe>) {
+    **probe = !**probe;
+    info!("cell probe (alt + click): {}", **probe);
+}
 
-#[derive(Component)]
-#[require(Mesh2d)]
-struct Border;
+fn toggle_neighbour_count(mut show_count: ResMut<ShowNeighbourCount>) {
+    **show_count = !**show_count;
+    info!("neighbour count: {}", **show_count);
+}
 
-// ——> RESOURCES
+/// logs the live neighbours of the hovered cell and what happens to it next generation
+fn log_hovered_neighbour_count(
+    hovered_cell: Res<HoveredCell>,
+    board: Res<Board>,
+    grid: Res<LifeGrid>,
+    rules: Res<Ruleset>,
+) {
+    let Some(cell_coord) = **hovered_cell else {
+        return;
+    };
+    let idx = board.cell_coord_to_idx(cell_coord);
+    let count = grid.live_neighbours(&board, rules.neighbourhood, idx);
+    let alive = grid.cells[idx];
+    let alive_next = grid.next_alive(&board, &rules, idx);
+    let fate = match (alive, alive_next) {
+        (true, true) => "survives",
+        (true, false) => "dies",
+        (false, true) => "is born",
+        (false, false) => "stays dead",
+    };
+    info!(
+        "cell ({}, {}): {count} live neighbours, {fate}",
+        cell_coord.x, cell_coord.y
+    );
+}
 
-/// hold handles for meshes and materials
-#[derive(Resource, Clone)]
-struct MeshAndMats {
-    meshes: HashMap<&'static str, Handle<Mesh>>,
-    materials: HashMap<&'static str, Handle<ColorMaterial>>,
-}
-
-#[derive(Resource, Clone, Copy)]
-struct Board {
-    /// the center of the board
-    center: Vec2,
-    /// the amount of cells on each axis
-    size: u32,
-    /// the size of each individual cell
-    cell_size: Vec2,
-    /// scale of each individual cell (should be 0.0 - 1.0)
-    cell_scale: Vec2,
-}
-
-impl Board {
-    /// computes full size of the board in pixels
-    #[inline]
-    fn pixel_size(&self) -> Vec2 {
-        vec2(
-            self.size as f32 * self.cell_size.x,
-            self.size as f32 * self.cell_size.y,
-        )
+fn handle_setup_kbd(
+    (keyboard_input, bindings): (Res<ButtonInput<KeyCode>>, Res<KeyBindings>),
+    mut generation: ResMut<Generation>,
+    mut history: ResMut<EditHistory>,
+    mut grid: ResMut<LifeGrid>,
+    mut seed: ResMut<RngSeed>,
+    (board, selection, density): (Res<Board>, Res<CellSelection>, Res<SeedDensity>),
+    unbounded: Option<ResMut<UnboundedWorld>>,
+) {
+    if bindings.just_pressed(&keyboard_input, Action::NextSeed) {
+        **seed = seed.wrapping_add(1);
+        info!("seed: {}", **seed);
+    }
+    let clear = bindings.just_pressed(&keyboard_input, Action::Clear);
+    let randomize = bindings.just_pressed(&keyboard_input, Action::Randomize);
+    if clear || randomize {
+        history.record(grid.cells.clone());
+    }
+    if clear {
+        **generation = 0;
+        grid.replace(vec![false; board.cell_count()]);
+        if let Some(mut unbounded) = unbounded {
+            unbounded.life.clear();
+        }
+    }
+    if randomize {
+        // holding ctrl seeds the ellipse inscribed in the selection
+        let region = match selection.bounds() {
+            Some((min, max))
+                if keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) =>
+            {
+                SeedRegion::Ellipse(min, max)
+            }
+            Some((min, max)) => SeedRegion::Rect(min, max),
+            None => SeedRegion::Board,
+        };
+        **generation = 0;
+        grid.randomize(&board, *seed, *density, region);
+        info!(
+            "randomized {region:?} with seed {} and a density of {}%",
+            **seed, **density
+        );
     }
+}
 
-    #[inline]
-    fn cell_coord_to_translation(&self, cell_coord: UVec2) -> Vec3 {
-        (self.center - (self.pixel_size() * 0.5)
-            + cell_coord.as_vec2() * self.cell_size
-            + self.cell_size * 0.5)
-            .extend(10.0)
+fn handle_seed_density_kbd(
+    mut density: ResMut<SeedDensity>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    let percent = if bindings.just_pressed(&keyboard_input, Action::DecreaseDensity) {
+        // the config file may set densities between the steps
+        density.saturating_sub(SEED_DENSITY_STEP).max(1)
+    } else {
+        (**density + SEED_DENSITY_STEP).min(100)
+    };
+    if density.set_if_neq(SeedDensity(percent)) {
+        info!("seeding density: {percent}%");
     }
+}
 
-    #[inline]
-    fn cell_coord_to_idx(&self, cell_coord: UVec2) -> usize {
-        ((cell_coord.y % self.size) * self.size + (cell_coord.x % self.size)) as usize
+/// changes the chance of cells flipping against the rules
+fn handle_noise_kbd(
+    mut noise: ResMut<Noise>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    let steps = (noise.flip_chance / NOISE_FLIP_CHANCE_STEP).round();
+    let steps = if bindings.just_pressed(&keyboard_input, Action::DecreaseNoise) {
+        steps - 1.0
+    } else {
+        steps + 1.0
+    };
+    let flip_chance = (steps * NOISE_FLIP_CHANCE_STEP).clamp(0.0, MAX_NOISE_FLIP_CHANCE);
+    if noise.flip_chance != flip_chance {
+        noise.flip_chance = flip_chance;
+        info!("noise: {:.1}% chance to flip", flip_chance * 100.0);
     }
+}
+
+/// slows down / speeds up the simulation by changing the fixed timestep
+fn handle_speed_kbd(
+    mut fixed_time: ResMut<Time<Fixed>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    let factor = if bindings.just_pressed(&keyboard_input, Action::SlowDown) {
+        UPDATE_INTERVAL_FACTOR
+    } else if bindings.just_pressed(&keyboard_input, Action::SpeedUp) {
+        UPDATE_INTERVAL_FACTOR.recip()
+    } else {
+        return;
+    };
+
+    let interval = (fixed_time.timestep().as_secs_f64() * factor).clamp(
+        Duration::from_millis(MIN_UPDATE_INTERVAL_MS).as_secs_f64(),
+        Duration::from_millis(MAX_UPDATE_INTERVAL_MS).as_secs_f64(),
+    );
+    fixed_time.set_timestep_seconds(interval);
+    info!("update interval: {:.1}ms", interval * 1000.0);
+}
 
-    #[inline]
-    fn idx_to_cell_coord(&self, idx: usize) -> UVec2 {
-        uvec2(idx as u32 % self.size, idx as u32 / self.size)
+/// sets the fixed timestep to the tempo tapped along to, see `TapTempo`
+fn tap_tempo(
+    mut tempo: ResMut<TapTempo>,
+    mut fixed_time: ResMut<Time<Fixed>>,
+    time: Res<Time<Real>>,
+) {
+    if let Some(bpm) = tempo.tap(time.elapsed_secs_f64()) {
+        fixed_time.set_timestep_seconds(60.0 / bpm);
+        info!("tap tempo: {bpm:.0} bpm");
     }
+}
 
-    #[inline]
-    fn neighbour_indices(&self, cell_coord: UVec2) -> [usize; 8] {
-        let mut result = [0; 8];
-        for (i, neigh_pos) in (-1..=1)
-            .flat_map(|y| (-1..=1).map(move |x| ivec2(x, y)))
-            // filter out if pos_offs is (0, 0)
-            .filter(|pos_offs| !(pos_offs.x == 0 && pos_offs.y == 0))
-            .enumerate()
-            .map(|(i, pos_offs)| {
-                let pos = cell_coord.as_ivec2() + pos_offs;
-                let mut neigh_pos = pos.as_uvec2();
-                if pos.x < 0 {
-                    neigh_pos.x = self.size - 1;
-                } else if pos.x >= self.size as i32 {
-                    neigh_pos.x = 0;
-                }
-                if pos.y < 0 {
-                    neigh_pos.y = self.size - 1;
-                } else if pos.y >= self.size as i32 {
-                    neigh_pos.y = 0;
-                }
+fn toggle_grid(mut show_grid: ResMut<ShowGrid>) {
+    **show_grid = !**show_grid;
+    info!("grid overlay: {}", **show_grid);
+}
 
-                (i, neigh_pos)
-            })
-        {
-            result[i] = self.cell_coord_to_idx(neigh_pos);
-        }
+/// draws lines along the edges of the cells
+fn draw_grid(mut gizmos: Gizmos, board: Res<Board>) {
+    let bottom_left = board.center - board.pixel_size() * 0.5;
+    let top_right = board.center + board.pixel_size() * 0.5;
+    for i in 0..=board.width {
+        let x = bottom_left.x + i as f32 * board.cell_size.x;
+        gizmos.line_2d(vec2(x, bottom_left.y), vec2(x, top_right.y), GRID_COLOR);
+    }
+    for i in 0..=board.height {
+        let y = bottom_left.y + i as f32 * board.cell_size.y;
+        gizmos.line_2d(vec2(bottom_left.x, y), vec2(top_right.x, y), GRID_COLOR);
+    }
+}
+
+fn toggle_wrap_indicator(mut show_indicator: ResMut<ShowWrapIndicator>) {
+    **show_indicator = !**show_indicator;
+    info!("wrap indicator: {}", **show_indicator);
+}
+
+fn toggle_render_mode(mut render_mode: ResMut<RenderMode>) {
+    *render_mode = match *render_mode {
+        RenderMode::Filled => RenderMode::Outline,
+        RenderMode::Outline => RenderMode::Filled,
+    };
+    info!("render mode: {:?}", *render_mode);
+}
+
+fn toggle_heatmap(mut show_heatmap: ResMut<ShowHeatmap>) {
+    **show_heatmap = !**show_heatmap;
+    info!("heatmap: {}", **show_heatmap);
+}
+
+fn toggle_future_preview(mut show_future: ResMut<ShowFuture>) {
+    **show_future = !**show_future;
+    info!("next generation preview: {}", **show_future);
+}
 
-        result
+/// Computes the next generation of the main board for `ShowFuture`, without stepping it.
+///
+/// The noise isn't previewed and an unbounded board isn't either, as the cells beyond the
+/// visible window are unknown.
+fn preview_future_life(
+    (grid, board, rules): (Res<LifeGrid>, Res<Board>, Res<Ruleset>),
+    (show_future, state): (Res<ShowFuture>, Res<State<GameState>>),
+    unbounded: Option<Res<UnboundedWorld>>,
+    cell_entities: Res<CellEntities>,
+    mut cell_query: Query<&mut FutureAlive, With<Cell>>,
+    mut next: Local<Vec<bool>>,
+) {
+    let previewing = **show_future && *state.get() != GameState::Running && unbounded.is_none();
+    if previewing {
+        next.resize(grid.cells.len(), false);
+        step_board(&grid.cells, &mut next, &board, &rules);
+    }
+    for (idx, &entity) in cell_entities.iter().enumerate() {
+        if let Ok(mut future_alive) = cell_query.get_mut(entity) {
+            let alive_next = previewing.then(|| next[idx] && !grid.is_decaying(idx));
+            future_alive.set_if_neq(FutureAlive(alive_next));
+        }
     }
 }
 
-impl Default for Board {
-    fn default() -> Self {
-        Self {
-            center: BOARD_POS,
-            size: BOARD_SIZE,
-            cell_size: CELL_SIZE_PX,
-            cell_scale: CELL_SCALE,
+fn toggle_dead_cells(mut hide_dead_cells: ResMut<HideDeadCells>) {
+    **hide_dead_cells = !**hide_dead_cells;
+    info!("hide dead cells: {}", **hide_dead_cells);
+}
+
+fn toggle_cell_layers(mut layer_cells: ResMut<LayerCells>) {
+    **layer_cells = !**layer_cells;
+    info!("layer cells: {}", **layer_cells);
+}
+
+/// Hidden entities can't be picked by default, so the hidden dead cells are only pickable while
+/// the mesh picking casts rays against all of the meshes.
+fn update_hidden_cell_picking(
+    hide_dead_cells: Res<HideDeadCells>,
+    picking_settings: Option<ResMut<MeshPickingSettings>>,
+) {
+    let Some(mut picking_settings) = picking_settings else {
+        return;
+    };
+    picking_settings.ray_cast_visibility = if **hide_dead_cells {
+        RayCastVisibility::Any
+    } else {
+        RayCastVisibility::VisibleInView
+    };
+}
+
+fn cycle_theme(mut theme: ResMut<Theme>) {
+    *theme = theme.next();
+    info!("theme: {}", theme.name);
+}
+
+/// recolors the cell materials, the cells keep their materials so they don't need repainting
+fn apply_theme(
+    theme: Res<Theme>,
+    meshes_and_mats: Res<MeshAndMats>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut clear_color: ResMut<ClearColor>,
+) {
+    meshes_and_mats.recolor(&theme, &mut materials);
+    clear_color.0 = theme.cell_dead;
+}
+
+fn toggle_fades(mut show_fades: ResMut<ShowFades>) {
+    **show_fades = !**show_fades;
+    info!("fading cells: {}", **show_fades);
+}
+
+/// tints the border of a wrapping board
+fn update_border_color(
+    mut border_query: Query<(&mut MeshMaterial2d<ColorMaterial>, &Border)>,
+    board: Res<Board>,
+    show_indicator: Res<ShowWrapIndicator>,
+    meshes_and_mats: Res<MeshAndMats>,
+) {
+    for (mut material, &border) in border_query.iter_mut() {
+        let key = border_material_key(&board, border, **show_indicator);
+        let border_mat = meshes_and_mats.material(key);
+        if material.0 != *border_mat {
+            material.0 = border_mat.to_owned();
         }
     }
 }
 
-#[cfg(test)]
-mod test {
-    use bevy::math::vec3;
+/// shrinks / grows the cells to adjust the gap between them
+fn handle_cell_scale_kbd(
+    mut board: ResMut<Board>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    let step = if bindings.just_pressed(&keyboard_input, Action::ShrinkCells) {
+        -CELL_SCALE_STEP
+    } else if bindings.just_pressed(&keyboard_input, Action::GrowCells) {
+        CELL_SCALE_STEP
+    } else {
+        return;
+    };
+    board.cell_scale =
+        (board.cell_scale + step).clamp(Vec2::splat(MIN_CELL_SCALE), Vec2::splat(MAX_CELL_SCALE));
+    info!("cell scale: {:.1}", board.cell_scale.x);
+}
 
-    use super::*;
+fn cycle_symmetry(mut symmetry: ResMut<Symmetry>) {
+    *symmetry = symmetry.next();
+    info!("painting symmetry: {:?}", *symmetry);
+}
 
-    #[test]
-    fn board_works() {
-        let board = Board {
-            center: Vec2::ZERO,
-            cell_size: Vec2::splat(8.0),
-            cell_scale: Vec2::splat(0.9),
-            size: 8,
+/// grows / shrinks the radius of the brush used for painting cells
+fn handle_brush_size_kbd(
+    mut brush_size: ResMut<BrushSize>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    let size = if bindings.just_pressed(&keyboard_input, Action::ShrinkBrush) {
+        brush_size.saturating_sub(1)
+    } else if bindings.just_pressed(&keyboard_input, Action::GrowBrush) {
+        (**brush_size + 1).min(MAX_BRUSH_SIZE)
+    } else {
+        return;
+    };
+    if brush_size.set_if_neq(BrushSize(size)) {
+        info!("brush size: {size}");
+    }
+}
+
+fn update_cell_scales(mut transform_query: Query<&mut Transform, With<Cell>>, board: Res<Board>) {
+    let scale = board.cell_scale.xyx();
+    for mut transform in transform_query.iter_mut() {
+        transform.scale = scale;
+    }
+}
+
+/// Switches between the fixed board and an unbounded one.
+///
+/// The cells on the board become the center of the unbounded board, switching back only keeps
+/// the cells that are currently visible on the board.
+fn toggle_unbounded_world(
+    mut commands: Commands,
+    unbounded: Option<Res<UnboundedWorld>>,
+    board: Res<Board>,
+    grid: Res<LifeGrid>,
+) {
+    if unbounded.is_some() {
+        commands.remove_resource::<UnboundedWorld>();
+    } else {
+        let mut unbounded = UnboundedWorld {
+            life: SparseLife::default(),
+            origin: -(board.size() / 2).as_ivec2(),
         };
+        unbounded.store(&board, &grid);
+        commands.insert_resource(unbounded);
+    }
+    info!("unbounded board: {}", unbounded.is_none());
+}
 
-        let px_size = board.pixel_size();
-        assert_eq!(vec2(64., 64.), px_size);
+/// Keeps the board under the camera while it pans over an unbounded board.
+///
+/// Whenever the camera moves whole cells away from the center of the board it is moved back,
+/// and the window of the unbounded board shown on the cells is scrolled by the same amount.
+fn scroll_unbounded_world(
+    mut cam_query: Query<&mut Transform, With<Camera2d>>,
+    board: Res<Board>,
+    mut unbounded: ResMut<UnboundedWorld>,
+    mut grid: ResMut<LifeGrid>,
+    mut history: ResMut<EditHistory>,
+) {
+    let Ok(mut cam_transform) = cam_query.get_single_mut() else {
+        return;
+    };
+    let offset = ((cam_transform.translation.truncate() - board.center) / board.cell_size)
+        .trunc()
+        .as_ivec2();
+    if offset == IVec2::ZERO {
+        return;
+    }
+    cam_transform.translation -= (offset.as_vec2() * board.cell_size).extend(0.0);
 
-        let pos1_1 = uvec2(1, 1);
-        assert_eq!(9, board.cell_coord_to_idx(pos1_1));
-        assert_eq!(0, board.cell_coord_to_idx(uvec2(8, 8)));
-        assert_eq!(7, board.cell_coord_to_idx(uvec2(7, 8)));
-        assert_eq!(56, board.cell_coord_to_idx(uvec2(8, 7)));
-        assert_eq!(uvec2(7, 7), board.idx_to_cell_coord(63));
-        assert_eq!(
-            vec3(-4.0, -4.0, 10.),
-            board.cell_coord_to_translation(uvec2(3, 3))
-        );
-
-        let neigh1_1 = board.neighbour_indices(pos1_1);
-        let expected_1_1 = [
-            board.cell_coord_to_idx(uvec2(0, 0)),
-            board.cell_coord_to_idx(uvec2(1, 0)),
-            board.cell_coord_to_idx(uvec2(2, 0)),
-            board.cell_coord_to_idx(uvec2(0, 1)),
-            board.cell_coord_to_idx(uvec2(2, 1)),
-            board.cell_coord_to_idx(uvec2(0, 2)),
-            board.cell_coord_to_idx(uvec2(1, 2)),
-            board.cell_coord_to_idx(uvec2(2, 2)),
-        ];
-        assert_eq!(expected_1_1, neigh1_1);
-
-        let neigh0_1 = board.neighbour_indices(uvec2(0, 1));
-        let expected_0_1 = [
-            board.cell_coord_to_idx(uvec2(7, 0)),
-            board.cell_coord_to_idx(uvec2(0, 0)),
-            board.cell_coord_to_idx(uvec2(1, 0)),
-            board.cell_coord_to_idx(uvec2(7, 1)),
-            board.cell_coord_to_idx(uvec2(1, 1)),
-            board.cell_coord_to_idx(uvec2(7, 2)),
-            board.cell_coord_to_idx(uvec2(0, 2)),
-            board.cell_coord_to_idx(uvec2(1, 2)),
-        ];
-        assert_eq!(expected_0_1, neigh0_1);
+    unbounded.store(&board, &grid);
+    unbounded.origin += offset;
+    let cells = unbounded.window(&board);
+    grid.scroll(&board, offset, cells);
+    // the recorded edits belong to the previous window
+    history.clear();
+}
+
+fn spawn_cursor_coord_text(mut commands: Commands) {
+    commands.spawn((
+        CursorCoordText,
+        Text::new("—"),
+        TextFont {
+            font_size: HUD_FONT_SIZE,
+            ..default()
+        },
+        TextColor(HUD_TEXT_COLOR),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(8.0),
+            left: Val::Px(8.0),
+            ..default()
+        },
+    ));
+}
+
+/// Shows the coordinate of the cell under the cursor, or "—" outside of the board.
+///
+/// The coordinates of an unbounded board are relative to its center when it was created.
+fn update_cursor_coord_text(
+    mut cursor_moved: EventReader<CursorMoved>,
+    mut cursor_left: EventReader<CursorLeft>,
+    cam_query: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    board: Res<Board>,
+    unbounded: Option<Res<UnboundedWorld>>,
+    mut text_query: Query<&mut Text, With<CursorCoordText>>,
+) {
+    let left = cursor_left.read().count() > 0;
+    let cursor_pos = cursor_moved.read().last().map(|moved| moved.position);
+    let Ok((camera, cam_transform)) = cam_query.get_single() else {
+        return;
+    };
+    let cell_coord = cursor_pos
+        .filter(|_| !left)
+        .and_then(|pos| camera.viewport_to_world_2d(cam_transform, pos).ok())
+        .and_then(|world_pos| board.translation_to_cell_coord(world_pos));
+    let coord_text = match (cell_coord, unbounded) {
+        (Some(cell_coord), Some(unbounded)) => {
+            let world_coord = unbounded.origin + cell_coord.as_ivec2();
+            format!("({}, {})", world_coord.x, world_coord.y)
+        }
+        (Some(cell_coord), None) => format!("({}, {})", cell_coord.x, cell_coord.y),
+        (None, _) => "—".to_owned(),
+    };
+    for mut text in text_query.iter_mut() {
+        if **text != coord_text {
+            **text = coord_text.clone();
+        }
+    }
+}
+
+fn spawn_status_text(mut commands: Commands, show_status: Res<ShowStatus>) {
+    commands.spawn((
+        StatusText,
+        Text::default(),
+        TextFont {
+            font_size: HUD_FONT_SIZE,
+            ..default()
+        },
+        TextColor(HUD_TEXT_COLOR),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(8.0),
+            right: Val::Px(8.0),
+            ..default()
+        },
+        status_visibility(**show_status),
+    ));
+}
+
+fn toggle_status(mut show_status: ResMut<ShowStatus>) {
+    **show_status = !**show_status;
+}
+
+fn update_status_visibility(
+    mut text_query: Query<&mut Visibility, With<StatusText>>,
+    show_status: Res<ShowStatus>,
+) {
+    for mut text_visibility in text_query.iter_mut() {
+        text_visibility.set_if_neq(status_visibility(**show_status));
+    }
+}
+
+fn status_visibility(show_status: bool) -> Visibility {
+    if show_status {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+/// Shows the generation, the share of live cells, the rules and whether the board stabilized.
+///
+/// The population of an unbounded board only counts the cells in the window.
+fn update_status_text(
+    mut text_query: Query<&mut Text, With<StatusText>>,
+    (generation, population): (Res<Generation>, Res<Population>),
+    (board, rules, fixed_time): (Res<Board>, Res<Ruleset>, Res<Time<Fixed>>),
+    detector: Res<StasisDetector>,
+    unbounded: Option<Res<UnboundedWorld>>,
+) {
+    let ratio = **population as f64 / board.cell_count() as f64 * 100.0;
+    let wrap = match (unbounded.is_some(), board.wrap_x, board.wrap_y) {
+        (true, _, _) => "unbounded",
+        (false, true, true) => "torus",
+        (false, true, false) => "horizontal",
+        (false, false, true) => "vertical",
+        (false, false, false) => "none",
+    };
+    let rule = match rules.preset_name() {
+        Some(name) => format!("{} ({name})", *rules),
+        None => rules.to_string(),
+    };
+    let stabilized = match (detector.enabled, detector.stabilized) {
+        (false, _) => "detection off".to_owned(),
+        (true, None) => "no".to_owned(),
+        (true, Some((start, period))) => format!("period {period} since generation {start}"),
+    };
+    let status = format!(
+        "generation: {}\npopulation: {} / {} ({ratio:.1}%)\nrule: {rule}\ninterval: {:.1}ms\n\
+         wrap: {wrap}\nstabilized: {stabilized}",
+        **generation,
+        **population,
+        board.cell_count(),
+        fixed_time.timestep().as_secs_f64() * 1000.0,
+    );
+    for mut text in text_query.iter_mut() {
+        if **text != status {
+            **text = status.clone();
+        }
+    }
+}
+
+fn update_border_visibility(
+    mut border_query: Query<&mut Visibility, With<Border>>,
+    show_border: Res<ShowBorder>,
+    unbounded: Option<Res<UnboundedWorld>>,
+) {
+    let visibility = border_visibility(**show_border, unbounded.is_some());
+    for mut border_visibility in border_query.iter_mut() {
+        border_visibility.set_if_neq(visibility);
+    }
+}
+
+/// Cycles through the topologies of the board: a torus, a cylinder wrapping horizontally, one
+/// wrapping vertically and a bounded board.
+fn toggle_board_wrap(mut board: ResMut<Board>) {
+    (board.wrap_x, board.wrap_y) = match (board.wrap_x, board.wrap_y) {
+        (true, true) => (true, false),
+        (true, false) => (false, true),
+        (false, true) => (false, false),
+        (false, false) => (true, true),
+    };
+    info!(
+        "board wrapping: horizontally {}, vertically {}",
+        board.wrap_x, board.wrap_y
+    );
+}
+
+/// Switches to the next of the `Ruleset::PRESETS`, the next generation is stepped with them
+/// even while running.
+fn cycle_rule(mut rules: ResMut<Ruleset>) {
+    let (name, preset) = rules.next_preset();
+    *rules = preset;
+    info!("rule: {name} ({preset})");
+}
+
+/// switches the rules between the Moore and the von Neumann neighbourhood
+fn toggle_neighbourhood(mut rules: ResMut<Ruleset>) {
+    rules.neighbourhood = rules.neighbourhood.toggled();
+    info!("neighbourhood: {:?}", rules.neighbourhood);
+}
+
+/// saves the current state of the board into a new file in the working directory
+fn save_board(board: Res<Board>, grid: Res<LifeGrid>, mut history: ResMut<EditHistory>) {
+    let save = BoardSave {
+        width: board.width,
+        height: board.height,
+        cells: grid.cells.clone(),
+    };
+    let path = save::timestamped_save_path();
+    match save.write_to(&path) {
+        Ok(()) => {
+            history.unsaved = false;
+            info!("saved board to {}", path.display());
+        }
+        Err(e) => error!("failed to save board to {}: {e}", path.display()),
+    }
+}
+
+/// Saves the board to `save::RECOVERY_SAVE_PATH` when the game quits with unsaved edits. The
+/// board the simulation was started from is saved if it's running, since that's the one that
+/// was edited.
+fn save_recovery_on_exit(
+    history: Res<EditHistory>,
+    board: Res<Board>,
+    grid: Option<Res<LifeGrid>>,
+    session_start: Option<Res<SessionStart>>,
+    state: Option<Res<State<GameState>>>,
+) {
+    if !history.unsaved {
+        return;
+    }
+    let running = state.is_some_and(|state| *state.get() != GameState::Setup);
+    let save = match (session_start, grid) {
+        (Some(session_start), _) if running => session_start.session.board.clone(),
+        (_, Some(grid)) => BoardSave {
+            width: board.width,
+            height: board.height,
+            cells: grid.cells.clone(),
+        },
+        _ => return,
+    };
+    match save.write_to(save::RECOVERY_SAVE_PATH) {
+        Ok(()) => info!("saved the unsaved board to {}", save::RECOVERY_SAVE_PATH),
+        Err(e) => error!(
+            "failed to save the unsaved board to {}: {e}",
+            save::RECOVERY_SAVE_PATH
+        ),
+    }
+}
+
+fn quit(mut app_exit: EventWriter<AppExit>) {
+    app_exit.send(AppExit::Success);
+}
+
+/// exports the current state of the board as a PNG image in the working directory
+fn export_board_png(board: Res<Board>, grid: Res<LifeGrid>, theme: Res<Theme>) {
+    let path = export::timestamped_png_path();
+    match export::write_png(board.size(), &grid.cells, &theme, &path) {
+        Ok(()) => info!("exported board to {}", path.display()),
+        Err(e) => error!("failed to export board to {}: {e}", path.display()),
+    }
+}
+
+fn spawn_thumbnail_gallery(mut commands: Commands) {
+    commands.spawn((
+        ThumbnailGallery,
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(32.0),
+            left: Val::Px(8.0),
+            max_height: Val::Percent(60.0),
+            flex_direction: FlexDirection::Column,
+            overflow: Overflow::scroll_y(),
+            ..default()
+        },
+    ));
+}
+
+/// Adds a thumbnail of the board to the top of the gallery, dropping the oldest one once there
+/// are `GALLERY_LEN` of them.
+fn capture_thumbnail(
+    mut commands: Commands,
+    gallery_query: Query<(Entity, Option<&Children>), With<ThumbnailGallery>>,
+    mut images: ResMut<Assets<Image>>,
+    (board, grid, theme): (Res<Board>, Res<LifeGrid>, Res<Theme>),
+    generation: Res<Generation>,
+) {
+    let Ok((gallery, thumbnails)) = gallery_query.get_single() else {
+        return;
+    };
+    if let Some(&oldest) = thumbnails
+        .filter(|thumbnails| thumbnails.len() >= GALLERY_LEN)
+        .and_then(|thumbnails| thumbnails.last())
+    {
+        commands.entity(oldest).despawn_recursive();
+    }
+
+    let rgba = export::thumbnail_image(board.size(), &grid.cells, &theme, THUMBNAIL_SIZE_PX);
+    let size = uvec2(rgba.width(), rgba.height());
+    let mut image = Image::new(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        rgba.into_raw(),
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    // the cells of small boards stay crisp when they are scaled up
+    image.sampler = ImageSampler::nearest();
+    let thumbnail = commands
+        .spawn((
+            Thumbnail {
+                board: BoardSave {
+                    width: board.width,
+                    height: board.height,
+                    cells: grid.cells.clone(),
+                },
+                generation: **generation,
+            },
+            ImageNode::new(images.add(image)),
+            Node {
+                width: Val::Px(size.x as f32),
+                height: Val::Px(size.y as f32),
+                margin: UiRect::bottom(Val::Px(4.0)),
+                flex_shrink: 0.0,
+                ..default()
+            },
+        ))
+        .observe(restore_thumbnail)
+        .id();
+    commands.entity(gallery).insert_children(0, &[thumbnail]);
+    info!("captured a thumbnail of generation {}", **generation);
+}
+
+/// restores the board of the clicked thumbnail in setup, if it still fits the board
+fn restore_thumbnail(
+    trigger: Trigger<Pointer<Click>>,
+    thumbnail_query: Query<&Thumbnail>,
+    (state, board): (Res<State<GameState>>, Res<Board>),
+    (mut grid, mut generation, mut history): (
+        ResMut<LifeGrid>,
+        ResMut<Generation>,
+        ResMut<EditHistory>,
+    ),
+) {
+    if trigger.event().button != PointerButton::Primary {
+        return;
+    }
+    let Ok(thumbnail) = thumbnail_query.get(trigger.entity()) else {
+        return;
+    };
+    if *state.get() != GameState::Setup {
+        warn!("thumbnails can only be restored in setup");
+        return;
+    }
+    let save = &thumbnail.board;
+    if (save.width, save.height) != (board.width, board.height) {
+        warn!(
+            "can't restore the thumbnail: its board size ({}x{}) doesn't match the current one \
+             ({}x{})",
+            save.width, save.height, board.width, board.height
+        );
+        return;
+    }
+
+    // the cells are repainted by `sync_cell_entities`, like after any other edit
+    replace_cells(&mut grid, &mut history, save.cells.clone());
+    **generation = 0;
+    info!(
+        "restored the board from the thumbnail of generation {}",
+        thumbnail.generation
+    );
+}
+
+/// Scrolls the gallery while the cursor is over it.
+///
+/// The camera's zoom is turned off meanwhile, so it doesn't zoom at the same time.
+fn scroll_thumbnail_gallery(
+    mut scroll_events: EventReader<MouseWheel>,
+    hover_map: Res<HoverMap>,
+    gallery_part_query: Query<(), Or<(With<ThumbnailGallery>, With<Thumbnail>)>>,
+    mut gallery_query: Query<&mut ScrollPosition, With<ThumbnailGallery>>,
+    mut cam_query: Query<&mut PanCam>,
+) {
+    let hovered = hover_map.get(&PointerId::Mouse).is_some_and(|hits| {
+        hits.keys()
+            .any(|&entity| gallery_part_query.contains(entity))
+    });
+    for mut pan_cam in cam_query.iter_mut() {
+        if pan_cam.enabled == hovered {
+            pan_cam.enabled = !hovered;
+        }
+    }
+    let scrolled_px = scroll_events
+        .read()
+        .map(|scroll| match scroll.unit {
+            MouseScrollUnit::Line => scroll.y * THUMBNAIL_SIZE_PX as f32,
+            MouseScrollUnit::Pixel => scroll.y,
+        })
+        .sum::<f32>();
+    if !hovered {
+        return;
+    }
+    for mut scroll_position in gallery_query.iter_mut() {
+        scroll_position.offset_y -= scrolled_px;
+    }
+}
+
+/// loads the most recent save file from the working directory
+fn load_board(
+    board: Res<Board>,
+    mut grid: ResMut<LifeGrid>,
+    mut generation: ResMut<Generation>,
+    mut history: ResMut<EditHistory>,
+) {
+    let path = match save::latest_save_path(".") {
+        Ok(Some(path)) => path,
+        Ok(None) => {
+            warn!("there is no saved board to load");
+            return;
+        }
+        Err(e) => {
+            error!("failed to look for saved boards: {e}");
+            return;
+        }
+    };
+    let save = match BoardSave::read_from(&path) {
+        Ok(save) => save,
+        Err(e) => {
+            error!("failed to load board from {}: {e}", path.display());
+            return;
+        }
+    };
+    if (save.width, save.height) != (board.width, board.height) {
+        error!(
+            "can't load {}: the saved board size ({}x{}) doesn't match the current one ({}x{})",
+            path.display(),
+            save.width,
+            save.height,
+            board.width,
+            board.height
+        );
+        return;
+    }
+
+    replace_cells(&mut grid, &mut history, save.cells);
+    history.unsaved = false;
+    **generation = 0;
+    info!("loaded board from {}", path.display());
+}
+
+/// remembers the board the simulation starts from, unbounded boards can't be recorded
+fn record_session(
+    mut commands: Commands,
+    board: Res<Board>,
+    (grid, rules): (Res<LifeGrid>, Res<Ruleset>),
+    generation: Res<Generation>,
+    unbounded: Option<Res<UnboundedWorld>>,
+) {
+    if unbounded.is_some() {
+        commands.remove_resource::<SessionStart>();
+        return;
+    }
+    commands.insert_resource(SessionStart {
+        session: SessionSave {
+            board: BoardSave {
+                width: board.width,
+                height: board.height,
+                cells: grid.cells.clone(),
+            },
+            wrap_x: board.wrap_x,
+            wrap_y: board.wrap_y,
+            rules: *rules,
+            generations: 0,
+        },
+        generation: **generation,
+    });
+}
+
+/// saves the board the simulation started from and how far it has run into a new file
+fn save_session(session_start: Option<Res<SessionStart>>, generation: Res<Generation>) {
+    let Some(session_start) = session_start else {
+        warn!("there is no session to save, unbounded boards aren't recorded");
+        return;
+    };
+    let session = session_start.session_at(**generation);
+    let path = save::timestamped_session_path();
+    match session.write_to(&path) {
+        Ok(()) => info!(
+            "saved session of {} generations to {}",
+            session.generations,
+            path.display()
+        ),
+        Err(e) => error!("failed to save session to {}: {e}", path.display()),
+    }
+}
+
+/// reads the most recent session file from the working directory
+fn load_latest_session() -> Option<SessionSave> {
+    let path = match save::latest_session_path(".") {
+        Ok(Some(path)) => path,
+        Ok(None) => {
+            warn!("there is no saved session to replay");
+            return None;
+        }
+        Err(e) => {
+            error!("failed to look for saved sessions: {e}");
+            return None;
+        }
+    };
+    match SessionSave::read_from(&path) {
+        Ok(session) => Some(session),
+        Err(e) => {
+            error!("failed to load session from {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// restores the starting board of a session and runs it until the recorded generation
+fn start_replay(
+    In(session): In<Option<SessionSave>>,
+    mut commands: Commands,
+    (mut board, mut rules): (ResMut<Board>, ResMut<Ruleset>),
+    (mut grid, mut generation, mut history): (
+        ResMut<LifeGrid>,
+        ResMut<Generation>,
+        ResMut<EditHistory>,
+    ),
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(session) = session else {
+        return;
+    };
+    if (session.board.width, session.board.height) != (board.width, board.height) {
+        error!(
+            "can't replay the session: its board size ({}x{}) doesn't match the current one \
+             ({}x{})",
+            session.board.width, session.board.height, board.width, board.height
+        );
+        return;
+    }
+
+    board.wrap_x = session.wrap_x;
+    board.wrap_y = session.wrap_y;
+    *rules = session.rules;
+    replace_cells(&mut grid, &mut history, session.board.cells);
+    **generation = 0;
+    commands.insert_resource(ReplayTarget(session.generations));
+    next_state.set(GameState::Running);
+    info!("replaying a session of {} generations", session.generations);
+}
+
+/// the replay stops once it reaches the recorded generation
+fn replay_finished(target: Option<Res<ReplayTarget>>, generation: Res<Generation>) -> bool {
+    target.is_some_and(|target| **generation >= **target)
+}
+
+fn finish_replay(mut commands: Commands, mut next_state: ResMut<NextState<GameState>>) {
+    commands.remove_resource::<ReplayTarget>();
+    next_state.set(GameState::Paused);
+    info!("replay finished");
+}
+
+fn stop_replay(mut commands: Commands) {
+    commands.remove_resource::<ReplayTarget>();
+}
+
+/// Saves the selected cells as a plaintext pattern into a new file in the working directory,
+/// or all of the live cells without a selection.
+fn save_plaintext_pattern(board: Res<Board>, grid: Res<LifeGrid>, selection: Res<CellSelection>) {
+    let Some((min, max)) = selection.bounds().or_else(|| grid.live_bounds(&board)) else {
+        warn!("there are no live cells to save");
+        return;
+    };
+    let pattern = grid.copy_region(&board, min, max);
+    let path = save::timestamped_plaintext_path();
+    match std::fs::write(&path, pattern::to_plaintext(&pattern)) {
+        Ok(()) => info!("saved pattern to {}", path.display()),
+        Err(e) => error!("failed to save pattern to {}: {e}", path.display()),
+    }
+}
+
+/// stamps the most recently modified plaintext pattern in the working directory in the center
+/// of the board
+fn load_plaintext_pattern(
+    board: Res<Board>,
+    orientation: Res<StampOrientation>,
+    mut grid: ResMut<LifeGrid>,
+    mut history: ResMut<EditHistory>,
+) {
+    let path = match save::latest_plaintext_path(".") {
+        Ok(Some(path)) => path,
+        Ok(None) => {
+            warn!("there is no plaintext pattern to load");
+            return;
+        }
+        Err(e) => {
+            error!("failed to look for plaintext patterns: {e}");
+            return;
+        }
+    };
+    match read_pattern_file(&path) {
+        Ok(pattern) => {
+            history.record(grid.cells.clone());
+            stamp_in_center(&orientation.apply(&pattern), &board, &mut grid);
+            info!("loaded pattern from {}", path.display());
+        }
+        Err(e) => error!("failed to load pattern from {}: {e}", path.display()),
+    }
+}
+
+/// reads a pattern file, in the plaintext format for `.cells` files and RLE otherwise
+fn read_pattern_file(path: &Path) -> Result<Pattern, Box<dyn Error>> {
+    let contents = std::fs::read_to_string(path)?;
+    if path
+        .extension()
+        .is_some_and(|ext| ext == save::PLAINTEXT_FILE_EXTENSION)
+    {
+        Ok(pattern::parse_plaintext(&contents)?)
+    } else {
+        Ok(pattern::parse_rle(&contents)?)
+    }
+}
+
+/// stamps the pattern so it's centered on the board
+fn stamp_in_center(pattern: &Pattern, board: &Board, grid: &mut LifeGrid) {
+    let top_left = board.center_cell_coord().as_ivec2()
+        + ivec2(-(pattern.width as i32 / 2), pattern.height as i32 / 2);
+    stamp_pattern(pattern, top_left, board, grid);
+}
+
+/// Stamps the pattern from the `Config` once the board is first set up, and starts running it
+/// if asked to.
+fn stamp_startup_pattern(
+    mut commands: Commands,
+    startup: Res<StartupPattern>,
+    board: Res<Board>,
+    mut grid: ResMut<LifeGrid>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    commands.remove_resource::<StartupPattern>();
+    if let Some(source) = &startup.source {
+        let pattern = pattern::library_pattern(source)
+            .map(Ok)
+            .unwrap_or_else(|| read_pattern_file(Path::new(source)));
+        match pattern {
+            Ok(pattern) => {
+                if pattern.width > board.width || pattern.height > board.height {
+                    warn!(
+                        "the {}x{} pattern {source} doesn't fit on the {}x{} board",
+                        pattern.width, pattern.height, board.width, board.height
+                    );
+                }
+                stamp_in_center(&pattern, &board, &mut grid);
+                info!("loaded pattern {source}");
+            }
+            Err(e) => error!("failed to load pattern {source}: {e}"),
+        }
+    }
+    if startup.autorun {
+        next_state.set(GameState::Running);
+    }
+}
+
+/// stamps RLE and plaintext pattern files dropped onto the window in the center of the board
+fn import_dropped_pattern(
+    mut drag_and_drop: EventReader<FileDragAndDrop>,
+    board: Res<Board>,
+    orientation: Res<StampOrientation>,
+    mut grid: ResMut<LifeGrid>,
+    mut history: ResMut<EditHistory>,
+) {
+    for event in drag_and_drop.read() {
+        let FileDragAndDrop::DroppedFile { path_buf, .. } = event else {
+            continue;
+        };
+        match read_pattern_file(path_buf) {
+            Ok(pattern) => {
+                history.record(grid.cells.clone());
+                stamp_in_center(&orientation.apply(&pattern), &board, &mut grid);
+                info!("imported pattern from {}", path_buf.display());
+            }
+            Err(e) => error!("failed to import pattern from {}: {e}", path_buf.display()),
+        }
+    }
+}
+
+fn library_pattern_key_pressed(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) -> bool {
+    (0..pattern::LIBRARY.len())
+        .any(|i| bindings.just_pressed(&keyboard_input, Action::StampPattern(i)))
+}
+
+/// previews one of the built-in patterns with its top left corner at the hovered cell
+fn preview_library_pattern(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    let Some((name, cells)) = pattern::LIBRARY
+        .into_iter()
+        .enumerate()
+        .find_map(|(i, pattern)| {
+            bindings
+                .just_pressed(&keyboard_input, Action::StampPattern(i))
+                .then_some(pattern)
+        })
+    else {
+        return;
+    };
+
+    commands.insert_resource(StampPreview {
+        description: format!("a {name}"),
+        pattern: Pattern::from_cells(cells),
+    });
+    info!("click to stamp a {name}");
+}
+
+/// copies the selected cells into the clipboard and clears the selection
+fn copy_selection(
+    mut selection: ResMut<CellSelection>,
+    mut clipboard: ResMut<Clipboard>,
+    board: Res<Board>,
+    grid: Res<LifeGrid>,
+) {
+    let Some((min, max)) = selection.bounds() else {
+        info!("there are no selected cells to copy");
+        return;
+    };
+    let pattern = grid.copy_region(&board, min, max);
+    info!(
+        "copied {} live cells from a {}x{} region",
+        pattern.live_cells.len(),
+        pattern.width,
+        pattern.height
+    );
+    **clipboard = Some(pattern);
+    **selection = None;
+}
+
+/// previews the clipboard with its top left corner at the hovered cell
+fn preview_clipboard(mut commands: Commands, clipboard: Res<Clipboard>) {
+    let Some(pattern) = clipboard.0.clone() else {
+        return;
+    };
+    commands.insert_resource(StampPreview {
+        description: "the clipboard".to_owned(),
+        pattern,
+    });
+    info!("click to paste the clipboard");
+}
+
+/// outlines the cells the previewed stamp would bring to life at the hovered cell
+fn draw_stamp_preview(
+    mut gizmos: Gizmos,
+    preview: Res<StampPreview>,
+    hovered_cell: Res<HoveredCell>,
+    board: Res<Board>,
+    orientation: Res<StampOrientation>,
+) {
+    let Some(hovered_cell) = **hovered_cell else {
+        return;
+    };
+    let pattern = orientation.apply(&preview.pattern);
+    for cell_coord in stamp_cell_coords(&pattern, hovered_cell.as_ivec2(), &board) {
+        gizmos.rect_2d(
+            Isometry2d::from_translation(board.cell_coord_to_translation(cell_coord).truncate()),
+            board.cell_size,
+            STAMP_PREVIEW_COLOR,
+        );
+    }
+}
+
+/// stamps the previewed pattern with its top left corner at the clicked cell
+fn commit_stamp_preview(
+    trigger: Trigger<Pointer<Click>>,
+    query: Query<&CellIdx, With<Cell>>,
+    mut commands: Commands,
+    preview: Option<Res<StampPreview>>,
+    (state, keyboard_input, probe): (
+        Res<State<GameState>>,
+        Res<ButtonInput<KeyCode>>,
+        Res<CellProbe>,
+    ),
+    (board, orientation): (Res<Board>, Res<StampOrientation>),
+    (mut grid, mut history): (ResMut<LifeGrid>, ResMut<EditHistory>),
+) {
+    let Some(preview) = preview else {
+        return;
+    };
+    if !matches!(state.get(), GameState::Setup)
+        || trigger.event().button != PointerButton::Primary
+        || is_selecting(&keyboard_input)
+        || is_probing(&keyboard_input, &probe)
+    {
+        return;
+    }
+    let Ok(idx) = query.get(trigger.entity()) else {
+        return;
+    };
+    stamp_preview_at(
+        &mut commands,
+        &preview,
+        board.idx_to_cell_coord(**idx),
+        (&board, &orientation),
+        (&mut grid, &mut history),
+    );
+}
+
+fn stamp_preview_at(
+    commands: &mut Commands,
+    preview: &StampPreview,
+    cell_coord: UVec2,
+    (board, orientation): (&Board, &StampOrientation),
+    (grid, history): (&mut LifeGrid, &mut EditHistory),
+) {
+    history.record(grid.cells.clone());
+    stamp_pattern(
+        &orientation.apply(&preview.pattern),
+        cell_coord.as_ivec2(),
+        board,
+        grid,
+    );
+    info!("stamped {} at {cell_coord}", preview.description);
+    commands.remove_resource::<StampPreview>();
+}
+
+fn cancel_stamp_preview(mut commands: Commands) {
+    commands.remove_resource::<StampPreview>();
+    info!("cancelled the stamp");
+}
+
+/// outlines the selected cells
+fn draw_selection(mut gizmos: Gizmos, selection: Res<CellSelection>, board: Res<Board>) {
+    let Some((min, max)) = selection.bounds() else {
+        return;
+    };
+    let bottom_left = board.cell_coord_to_translation(min).truncate() - board.cell_size * 0.5;
+    let top_right = board.cell_coord_to_translation(max).truncate() + board.cell_size * 0.5;
+    gizmos.rect_2d(
+        Isometry2d::from_translation((bottom_left + top_right) * 0.5),
+        top_right - bottom_left,
+        SELECTION_COLOR,
+    );
+}
+
+/// Looks for isolated gliders in every phase and direction, it's O(cells × templates) so it
+/// only runs on demand.
+fn find_gliders(mut commands: Commands, board: Res<Board>, grid: Res<LifeGrid>) {
+    let matches = pattern::glider_templates()
+        .iter()
+        .flat_map(|template| grid.find_pattern(&board, template))
+        .collect::<Vec<_>>();
+    info!("found {} gliders", matches.len());
+    commands.insert_resource(GliderHighlights {
+        matches,
+        timer: Timer::from_seconds(GLIDER_HIGHLIGHT_SECS, TimerMode::Once),
+    });
+}
+
+fn draw_glider_highlights(
+    mut commands: Commands,
+    mut gizmos: Gizmos,
+    mut highlights: ResMut<GliderHighlights>,
+    board: Res<Board>,
+    time: Res<Time>,
+) {
+    if highlights.timer.tick(time.delta()).finished() {
+        commands.remove_resource::<GliderHighlights>();
+        return;
+    }
+    for &(min, max) in &highlights.matches {
+        let bottom_left = board.cell_coord_to_translation(min).truncate() - board.cell_size * 0.5;
+        let top_right = board.cell_coord_to_translation(max).truncate() + board.cell_size * 0.5;
+        gizmos.rect_2d(
+            Isometry2d::from_translation((bottom_left + top_right) * 0.5),
+            top_right - bottom_left,
+            GLIDER_HIGHLIGHT_COLOR,
+        );
+    }
+}
+
+/// rotates / mirrors the patterns that are stamped next
+fn cycle_stamp_orientation(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut orientation: ResMut<StampOrientation>,
+) {
+    if bindings.just_pressed(&keyboard_input, Action::RotateStamp) {
+        orientation.quarter_turns = (orientation.quarter_turns + 1) % 4;
+    }
+    if bindings.just_pressed(&keyboard_input, Action::FlipStamp) {
+        orientation.flipped = !orientation.flipped;
+    }
+    info!(
+        "stamp orientation: rotated by {}°{}",
+        orientation.quarter_turns as u32 * 90,
+        if orientation.flipped { ", flipped" } else { "" }
+    );
+}
+
+/// undoes / redoes edits, while ctrl is held
+fn undo_redo_edit(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut history: ResMut<EditHistory>,
+    mut grid: ResMut<LifeGrid>,
+) {
+    if !keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        return;
+    }
+    let current = grid.cells.clone();
+    let restored = if bindings.just_pressed(&keyboard_input, Action::Undo) {
+        history.undo(current)
+    } else {
+        history.redo(current)
+    };
+    if let Some(cells) = restored {
+        grid.replace(cells);
+    }
+}
+
+/// flips the board horizontally / vertically or rotates it by 90°
+fn transform_board(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    board: Res<Board>,
+    mut grid: ResMut<LifeGrid>,
+    mut history: ResMut<EditHistory>,
+) {
+    let transform = if bindings.just_pressed(&keyboard_input, Action::FlipHorizontal) {
+        BoardTransform::FlipHorizontal
+    } else if bindings.just_pressed(&keyboard_input, Action::FlipVertical) {
+        BoardTransform::FlipVertical
+    } else if board.width == board.height {
+        BoardTransform::Rotate
+    } else {
+        warn!("only square boards can be rotated");
+        return;
+    };
+    let transformed = transform.apply(&board, &grid.cells);
+    replace_cells(&mut grid, &mut history, transformed);
+}
+
+fn end_edit_stroke(mut history: ResMut<EditHistory>) {
+    history.stroke_in_progress = false;
+    history.stroke_cells.clear();
+}
+
+/// Cycles the board's width through `BOARD_SIZES`, respawning all of the board's entities.
+///
+/// The height is scaled along with the width, so non-square boards keep their aspect ratio.
+/// The board stays centered on the same cell, so the region the old and the new board share is
+/// preserved.
+fn resize_board(
+    world: &mut World,
+    params: &mut SystemState<(Res<ButtonInput<KeyCode>>, Res<KeyBindings>, Res<Board>)>,
+) {
+    let (keyboard_input, bindings, board) = params.get_mut(world);
+    let old_board = *board;
+    let new_width = if bindings.just_pressed(&keyboard_input, Action::GrowBoard) {
+        BOARD_SIZES.into_iter().find(|&size| size > old_board.width)
+    } else {
+        BOARD_SIZES
+            .into_iter()
+            .rev()
+            .find(|&size| size < old_board.width)
+    };
+    let Some(new_width) = new_width else {
+        return;
+    };
+    let new_height = (old_board.height as u64 * new_width as u64 / old_board.width as u64)
+        .clamp(MIN_BOARD_SIZE as u64, MAX_BOARD_SIZE as u64) as u32;
+    rebuild_board(world, uvec2(new_width, new_height));
+}
+
+/// respawns the board with the new amount of cells, keeping the live cells the boards share
+fn rebuild_board(world: &mut World, size: UVec2) {
+    let old_board = *world.resource::<Board>();
+    let old_cells = world.resource::<LifeGrid>().cells.clone();
+    let to_despawn = world
+        .query_filtered::<Entity, Or<(With<Cell>, With<Border>, With<ComparedBoard>)>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+
+    for entity in to_despawn {
+        world.despawn(entity);
+    }
+    let new_board = Board {
+        width: size.x,
+        height: size.y,
+        ..old_board
+    };
+    world.insert_resource(new_board);
+    replace_border_meshes(world);
+    spawn_board(world);
+
+    // copy over the cells the boards share
+    let offset =
+        new_board.center_cell_coord().as_ivec2() - old_board.center_cell_coord().as_ivec2();
+    let mut grid = world.resource_mut::<LifeGrid>();
+    for (idx, _) in old_cells.iter().enumerate().filter(|(_, alive)| **alive) {
+        let cell_coord = old_board.idx_to_cell_coord(idx).as_ivec2() + offset;
+        if new_board.contains(cell_coord) {
+            grid.set_alive(new_board.cell_coord_to_idx(cell_coord.as_uvec2()), true);
+        }
+    }
+
+    // the edit history, the snapshots and the hovered cell refer to the old board
+    world.resource_mut::<EditHistory>().clear();
+    world.resource_mut::<GenerationSnapshots>().clear();
+    **world.resource_mut::<HoveredCell>() = None;
+    **world.resource_mut::<Generation>() = 0;
+    info!("board size: {}x{}", size.x, size.y);
+}
+
+/// Re-reads `CONFIG_FILE_PATH`. The rule, interval, theme and density change right away, a new
+/// board size rebuilds the board so like `resize_board` it's only applied in setup.
+fn reload_config_file(world: &mut World) {
+    let file = match ConfigFile::read(CONFIG_FILE_PATH) {
+        Ok(Some(file)) => file,
+        Ok(None) => {
+            warn!("there is no {CONFIG_FILE_PATH} to reload");
+            return;
+        }
+        Err(e) => {
+            warn!("couldn't reload the config: {e}");
+            return;
+        }
+    };
+    let board = *world.resource::<Board>();
+    let timestep = world.resource::<Time<Fixed>>().timestep();
+    let current = Config {
+        board_size: uvec2(board.width, board.height),
+        update_interval_ms: timestep.as_millis() as u64,
+        ruleset: *world.resource::<Ruleset>(),
+        theme: *world.resource::<Theme>(),
+        seed_density: **world.resource::<SeedDensity>(),
+        ..default()
+    };
+    let config = match file.apply(current.clone()) {
+        Ok(config) => config,
+        Err(e) => {
+            warn!("couldn't reload the config: {e}");
+            return;
+        }
+    };
+
+    if config.ruleset != current.ruleset {
+        world.insert_resource(config.ruleset);
+        info!("rule: {}", config.ruleset);
+    }
+    // the timestep isn't always whole milliseconds, so it's left alone unless the file sets it
+    let interval = Duration::from_millis(config.update_interval_ms);
+    if file.interval_ms.is_some() && interval != timestep {
+        world.resource_mut::<Time<Fixed>>().set_timestep(interval);
+        info!("update interval: {}ms", config.update_interval_ms);
+    }
+    if config.theme != current.theme {
+        world.insert_resource(config.theme);
+        info!("theme: {}", config.theme.name);
+    }
+    if config.seed_density != current.seed_density {
+        world.insert_resource(SeedDensity(config.seed_density));
+        info!("seeding density: {}%", config.seed_density);
+    }
+    if config.board_size != current.board_size {
+        let in_setup = *world.resource::<State<GameState>>() == GameState::Setup;
+        if in_setup && !world.contains_resource::<UnboundedWorld>() {
+            rebuild_board(world, config.board_size);
+        } else {
+            warn!("the board size only changes in setup on a bounded board");
+        }
+    }
+}
+
+/// Starts running the board from setup, stopping freezes the simulation without enabling the
+/// editing tools available in setup.
+///
+/// Leaving setup records the board, see `record_session`.
+fn start_or_stop_running(
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if let Some(next) = state.get().started_or_stopped() {
+        next_state.set(next);
+    }
+}
+
+/// stops the simulation and goes back to setup for editing the board
+fn edit_board(state: Res<State<GameState>>, mut next_state: ResMut<NextState<GameState>>) {
+    if let Some(next) = state.get().edited() {
+        next_state.set(next);
+    }
+}
+
+/// pauses the simulation while the window isn't focused, see `PauseOnFocusLoss`
+fn pause_on_focus_loss(
+    mut focus_events: EventReader<WindowFocused>,
+    mut pause: ResMut<PauseOnFocusLoss>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let mut current = *state.get();
+    for event in focus_events.read() {
+        if let Some(next) = pause.focus_changed(event.focused, current) {
+            info!("window focused: {}, switching to {next:?}", event.focused);
+            next_state.set(next);
+            current = next;
+        }
+    }
+}
+
+/// pushes the state of the grid to the cell entities
+fn sync_cell_entities(
+    grid: Res<LifeGrid>,
+    generation: Res<Generation>,
+    cell_entities: Res<CellEntities>,
+    compared_boards: Query<&ComparedBoard>,
+    mut cell_query: Query<
+        (
+            &mut CurrentAlive,
+            &mut Age,
+            &mut LastChanged,
+            &mut Decay,
+            &mut Fade,
+        ),
+        With<Cell>,
+    >,
+    show_fades: Res<ShowFades>,
+    fixed_time: Res<Time<Fixed>>,
+) {
+    let fade_duration = fixed_time.timestep().mul_f32(CELL_FADE_FRACTION);
+    let grids_and_entities = std::iter::once((&*grid, &**cell_entities)).chain(
+        compared_boards
+            .iter()
+            .map(|compared| (&compared.grid, &compared.cell_entities)),
+    );
+    for (grid, cell_entities) in grids_and_entities {
+        sync_cells(
+            grid,
+            **generation,
+            cell_entities,
+            &mut cell_query,
+            **show_fades,
+            fade_duration,
+        );
+    }
+}
+
+fn sync_cells(
+    grid: &LifeGrid,
+    generation: u64,
+    cell_entities: &[Entity],
+    cell_query: &mut Query<
+        (
+            &mut CurrentAlive,
+            &mut Age,
+            &mut LastChanged,
+            &mut Decay,
+            &mut Fade,
+        ),
+        With<Cell>,
+    >,
+    show_fades: bool,
+    fade_duration: Duration,
+) {
+    for (idx, &entity) in cell_entities.iter().enumerate() {
+        if let Ok((mut current, mut age, mut last_changed, mut decay, mut fade)) =
+            cell_query.get_mut(entity)
+        {
+            let alive = grid.cells[idx];
+            if current.set_if_neq(CurrentAlive(alive)) && show_fades {
+                **fade = Some(Timer::new(fade_duration, TimerMode::Once));
+            }
+            // edited cells start over with an age of 0, see `LifeGrid::set_alive`
+            age.set_if_neq(Age(if alive { grid.ages[idx] } else { 0 }));
+            // changes from before the generation was last reset are forgotten
+            let since_change = grid.last_changed[idx].and_then(|last| generation.checked_sub(last));
+            last_changed.set_if_neq(LastChanged(since_change));
+            decay.set_if_neq(Decay(grid.decay[idx]));
+        }
+    }
+}
+
+/// the components `handle_cell_color_main` picks the material of a cell from
+type CellColorData = (
+    &'static mut MeshMaterial2d<ColorMaterial>,
+    &'static CurrentAlive,
+    &'static Age,
+    &'static LastChanged,
+    &'static Decay,
+    &'static CellInteraction,
+    &'static UnderCursor,
+    &'static FutureAlive,
+);
+
+/// Sets the materials of the cells based on their life status, decay and interaction state, or
+/// their recent activity in the heatmap mode.
+///
+/// Only the cells whose state changed are visited, unless the coloring mode or the materials
+/// changed. Theme changes recolor the materials themselves, see `MeshAndMats`.
+fn handle_cell_color_main(
+    mut cell_queries: ParamSet<(
+        Query<
+            CellColorData,
+            (
+                With<Cell>,
+                Or<(
+                    Changed<CurrentAlive>,
+                    Changed<Age>,
+                    Changed<LastChanged>,
+                    Changed<Decay>,
+                    Changed<CellInteraction>,
+                    Changed<UnderCursor>,
+                    Changed<FutureAlive>,
+                )>,
+            ),
+        >,
+        Query<CellColorData, With<Cell>>,
+    )>,
+    mesh_n_mats: Res<MeshAndMats>,
+    show_heatmap: Res<ShowHeatmap>,
+    show_fades: Res<ShowFades>,
+) {
+    let recolor_all =
+        mesh_n_mats.is_changed() || show_heatmap.is_changed() || show_fades.is_changed();
+    if recolor_all {
+        for cell in cell_queries.p1().iter_mut() {
+            set_cell_material(cell, &mesh_n_mats, **show_heatmap);
+        }
+    } else {
+        for cell in cell_queries.p0().iter_mut() {
+            set_cell_material(cell, &mesh_n_mats, **show_heatmap);
+        }
+    }
+}
+
+fn set_cell_material(
+    (mut material, cell_alive, age, last_changed, decay, interaction, under_cursor, future_alive): (
+        Mut<MeshMaterial2d<ColorMaterial>>,
+        &CurrentAlive,
+        &Age,
+        &LastChanged,
+        &Decay,
+        &CellInteraction,
+        &UnderCursor,
+        &FutureAlive,
+    ),
+    mesh_n_mats: &MeshAndMats,
+    show_heatmap: bool,
+) {
+    let target = match (*interaction, **cell_alive) {
+        (_, alive) if **under_cursor => {
+            let key = if alive {
+                MaterialKey::CursorAlive
+            } else {
+                MaterialKey::CursorDead
+            };
+            mesh_n_mats.material(key)
+        }
+        (CellInteraction::Idle, alive) if future_alive.is_some_and(|future| future != alive) => {
+            let key = if alive {
+                MaterialKey::CellDying
+            } else {
+                MaterialKey::CellBorn
+            };
+            mesh_n_mats.material(key)
+        }
+        (CellInteraction::Idle, _) if show_heatmap => mesh_n_mats.heat_material(**last_changed),
+        (CellInteraction::Idle, true) => mesh_n_mats.age_material(**age),
+        (CellInteraction::Idle, false) if **decay > 0 => mesh_n_mats.decay_material(**decay),
+        _ => mesh_n_mats.material(interaction.material_key(**cell_alive)),
+    };
+    // most changes (e.g. a live cell aging) keep the cell's material, only the cells that get
+    // another one are marked as changed and re-extracted for rendering
+    if **material != *target {
+        **material = target.to_owned();
+    }
+}
+
+/// Blends the colors of cells that just changed from their previous life status, ends on the
+/// colors of young live cells and dead cells.
+///
+/// Runs after `handle_cell_color_main` so that it overrides the colors of fading cells.
+fn fade_cells(
+    mut cell_query: Query<
+        (
+            &mut MeshMaterial2d<ColorMaterial>,
+            &mut Fade,
+            &CurrentAlive,
+            &CellInteraction,
+            &UnderCursor,
+            &FutureAlive,
+        ),
+        With<Cell>,
+    >,
+    mesh_n_mats: Res<MeshAndMats>,
+    show_heatmap: Res<ShowHeatmap>,
+    time: Res<Time>,
+) {
+    for (mut material, mut fade, cell_alive, interaction, under_cursor, future_alive) in
+        cell_query.iter_mut()
+    {
+        let Some(timer) = fade.0.as_mut() else {
+            continue;
+        };
+        timer.tick(time.delta());
+        let progress = timer.fraction();
+        if timer.finished() {
+            **fade = None;
+        }
+        let changes_next = future_alive.is_some_and(|future| future != **cell_alive);
+        if **show_heatmap || *interaction != CellInteraction::Idle || **under_cursor || changes_next
+        {
+            continue;
+        }
+        let alive_amount = if **cell_alive {
+            progress
+        } else {
+            1.0 - progress
+        };
+        let fade_material = mesh_n_mats.fade_material(alive_amount);
+        if **material != *fade_material {
+            **material = fade_material.to_owned();
+        }
+    }
+}
+
+/// Moves the cells that changed into their layer while `LayerCells` is on, see `CELL_ALIVE_Z`.
+/// Toggling it moves all of the cells.
+fn update_cell_depth(
+    mut cell_query: Query<(&mut Transform, Ref<CurrentAlive>, Ref<UnderCursor>), With<Cell>>,
+    layer_cells: Res<LayerCells>,
+) {
+    if !**layer_cells && !layer_cells.is_changed() {
+        return;
+    }
+    for (mut transform, cell_alive, under_cursor) in cell_query.iter_mut() {
+        if !layer_cells.is_changed() && !cell_alive.is_changed() && !under_cursor.is_changed() {
+            continue;
+        }
+        let z = if **layer_cells {
+            cell_depth(**cell_alive, **under_cursor)
+        } else {
+            CELL_DEAD_Z
+        };
+        if transform.translation.z != z {
+            transform.translation.z = z;
+        }
+    }
+}
+
+/// Hides the dead cells while `HideDeadCells` is on, except for the ones that are drawn in
+/// another color than the background (hovered, under the cursor, fading, decaying, about to be
+/// born or in the heatmap).
+fn update_cell_visibility(
+    mut cell_query: Query<
+        (
+            &mut Visibility,
+            Ref<CurrentAlive>,
+            Ref<Decay>,
+            Ref<CellInteraction>,
+            Ref<UnderCursor>,
+            Ref<Fade>,
+            Ref<FutureAlive>,
+        ),
+        With<Cell>,
+    >,
+    hide_dead_cells: Res<HideDeadCells>,
+    show_heatmap: Res<ShowHeatmap>,
+) {
+    for (mut visibility, cell_alive, decay, interaction, under_cursor, fade, future_alive) in
+        cell_query.iter_mut()
+    {
+        let changed = cell_alive.is_changed()
+            || decay.is_changed()
+            || interaction.is_changed()
+            || under_cursor.is_changed()
+            || fade.is_changed()
+            || future_alive.is_changed();
+        if !changed && !hide_dead_cells.is_changed() && !show_heatmap.is_changed() {
+            continue;
+        }
+        let hidden = **hide_dead_cells
+            && !**cell_alive
+            && **decay == 0
+            && *interaction == CellInteraction::Idle
+            && !**under_cursor
+            && fade.is_none()
+            && **future_alive != Some(true)
+            && !**show_heatmap;
+        visibility.set_if_neq(if hidden {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        });
+    }
+}
+
+/// swaps the mesh of live cells for an outline in the outline render mode
+fn handle_cell_mesh_main(
+    mut cell_query: Query<(&mut Mesh2d, Ref<CurrentAlive>), With<Cell>>,
+    mesh_n_mats: Res<MeshAndMats>,
+    render_mode: Res<RenderMode>,
+) {
+    for (mut mesh, cell_alive) in cell_query.iter_mut() {
+        if !cell_alive.is_changed() && !render_mode.is_changed() {
+            continue;
+        }
+        let key = match *render_mode {
+            RenderMode::Outline if **cell_alive => MeshKey::CellOutline,
+            _ => MeshKey::Cell,
+        };
+        let target = mesh_n_mats.mesh(key);
+        if **mesh != *target {
+            **mesh = target.to_owned();
+        }
+    }
+}
+
+/// pointer interactions are only tracked in setup
+fn reset_cell_interactions(mut interaction_query: Query<&mut CellInteraction, With<Cell>>) {
+    for mut interaction in interaction_query.iter_mut() {
+        interaction.set_if_neq(CellInteraction::Idle);
+    }
+}
+
+fn run_generation_step(world: &mut World) {
+    world.run_schedule(GenerationStep);
+}
+
+/// steps the board on the fixed timestep, dropping the steps past the `MaxStepsPerFrame`
+fn run_fixed_generation_step(world: &mut World) {
+    let max_steps = **world.resource::<MaxStepsPerFrame>();
+    let mut steps = world.resource_mut::<StepsThisFrame>();
+    if **steps >= max_steps {
+        warn_once!("dropping generations, stepping takes longer than the update interval");
+        return;
+    }
+    **steps += 1;
+    world.run_schedule(GenerationStep);
+}
+
+fn reset_steps_this_frame(mut steps: ResMut<StepsThisFrame>) {
+    **steps = 0;
+}
+
+/// computes the next generation into the grid's back buffer
+fn update_cell_future_life(
+    mut grid: ResMut<LifeGrid>,
+    board: Res<Board>,
+    rules: Res<Ruleset>,
+    unbounded: Option<ResMut<UnboundedWorld>>,
+    (noise, seed, generation): (Res<Noise>, Res<RngSeed>, Res<Generation>),
+    #[cfg(feature = "gpu-step")] (gpu, mut commands): (Option<Res<GpuStepper>>, Commands),
+) {
+    match unbounded {
+        Some(mut unbounded) => {
+            // the grid might have been edited since the last step
+            unbounded.store(&board, &grid);
+            unbounded.life.step(&rules);
+            grid.next = unbounded.window(&board);
+        }
+        None => {
+            #[cfg(feature = "gpu-step")]
+            let stepped_on_gpu = step_on_gpu(&mut grid, &board, &rules, gpu, &mut commands);
+            #[cfg(not(feature = "gpu-step"))]
+            let stepped_on_gpu = false;
+            if !stepped_on_gpu {
+                grid.compute_next(&board, &rules);
+            }
+        }
+    }
+    // the flipped cells of an unbounded board are stored before the next step
+    noise.apply(&mut grid.next, *seed, **generation);
+}
+
+/// Logs the cells of the next generation that don't match `recount_next`, see `DebugVerify`.
+///
+/// Returns the coordinates of the mismatched cells.
+fn verify_future_life(
+    grid: Res<LifeGrid>,
+    board: Res<Board>,
+    rules: Res<Ruleset>,
+    noise: Res<Noise>,
+    generation: Res<Generation>,
+) -> Vec<UVec2> {
+    // the cells flipped by the noise can't be told apart from wrongly stepped ones
+    if noise.flip_chance > 0.0 {
+        return Vec::new();
+    }
+    let expected = recount_next(&grid.cells, &board, &rules);
+    let mismatches = expected
+        .into_iter()
+        .zip(&grid.next)
+        .enumerate()
+        .filter(|&(idx, (expected, &next))| (expected && !grid.is_decaying(idx)) != next)
+        .map(|(idx, _)| board.idx_to_cell_coord(idx))
+        .collect::<Vec<_>>();
+    if let Some(first) = mismatches.first() {
+        warn!(
+            "generation {}: {} cells don't match the recount, the first at ({}, {})",
+            **generation + 1,
+            mismatches.len(),
+            first.x,
+            first.y
+        );
+    }
+    mismatches
+}
+
+/// The generation after `cells`, worked out without any of the neighbour lookups of the
+/// `Board` that `step_board` goes through, so the two can catch each other's mistakes.
+fn recount_next(cells: &[bool], board: &Board, rules: &Ruleset) -> Vec<bool> {
+    let (width, height) = (board.width as i64, board.height as i64);
+    // the position along an axis of `len` cells, if it's on the board
+    let wrap = |pos: i64, len: i64, wraps: bool| match pos {
+        -1 if wraps => Some(len - 1),
+        pos if pos == len && wraps => Some(0),
+        pos if (0..len).contains(&pos) => Some(pos),
+        _ => None,
+    };
+    let mut next = vec![false; cells.len()];
+    for y in 0..height {
+        for x in 0..width {
+            let mut count = 0;
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    let diagonal = dx != 0 && dy != 0;
+                    if (dx, dy) == (0, 0)
+                        || (diagonal && rules.neighbourhood == Neighbourhood::VonNeumann)
+                    {
+                        continue;
+                    }
+                    let neighbour =
+                        wrap(x + dx, width, board.wrap_x).zip(wrap(y + dy, height, board.wrap_y));
+                    if let Some((nx, ny)) = neighbour {
+                        count += usize::from(cells[(ny * width + nx) as usize]);
+                    }
+                }
+            }
+            let idx = (y * width + x) as usize;
+            next[idx] = if cells[idx] {
+                rules.survive[count]
+            } else {
+                rules.birth[count]
+            };
+        }
+    }
+    next
+}
+
+/// Steps the grid with the `GpuStepper` if there is one that fits the board, false if the board
+/// still has to be stepped on the CPU.
+#[cfg(feature = "gpu-step")]
+fn step_on_gpu(
+    grid: &mut LifeGrid,
+    board: &Board,
+    rules: &Ruleset,
+    gpu: Option<Res<GpuStepper>>,
+    commands: &mut Commands,
+) -> bool {
+    let Some(gpu) = gpu.filter(|gpu| gpu.fits(board)) else {
+        return false;
+    };
+    let mut result = Ok(());
+    grid.compute_next_with(|cells, next| result = gpu.step(cells, next, board, rules));
+    if let Err(e) = result {
+        warn!("failed to step the board on the GPU, stepping it on the CPU: {e}");
+        commands.remove_resource::<GpuStepper>();
+        return false;
+    }
+    true
+}
+
+/// Sets up stepping boards of at least `GPU_STEP_MIN_CELLS` on the GPU, smaller boards are
+/// stepped on the CPU.
+#[cfg(feature = "gpu-step")]
+fn start_gpu_stepping(mut commands: Commands, board: Res<Board>, stepper: Option<Res<GpuStepper>>) {
+    if board.cell_count() < GPU_STEP_MIN_CELLS {
+        if stepper.is_some() {
+            commands.remove_resource::<GpuStepper>();
+        }
+        return;
+    }
+    if stepper.is_some_and(|stepper| stepper.fits(&board)) {
+        return;
+    }
+    match GpuStepper::new(&board) {
+        Some(stepper) => {
+            info!("stepping the board on the GPU");
+            commands.insert_resource(stepper);
+        }
+        None => {
+            warn!("there is no GPU to step the board on, stepping it on the CPU");
+            commands.remove_resource::<GpuStepper>();
+        }
+    }
+}
+
+fn update_cell_current_life(
+    mut grid: ResMut<LifeGrid>,
+    generation: Res<Generation>,
+    rules: Res<Ruleset>,
+    unbounded: Option<Res<UnboundedWorld>>,
+) {
+    grid.swap();
+    grid.age_cells();
+    // the cells outside of the window of an unbounded board don't decay
+    grid.decay_cells(if unbounded.is_some() { 2 } else { rules.states });
+    // forget the changes from before the board was last set up
+    if **generation == 0 {
+        grid.last_changed.fill(None);
+    }
+    // the generation is advanced right after this
+    grid.track_changes(**generation + 1);
+}
+
+/// steps the boards comparing other rulesets along with the main board
+fn step_compared_boards(
+    mut compared_boards: Query<&mut ComparedBoard>,
+    generation: Res<Generation>,
+) {
+    for mut compared in compared_boards.iter_mut() {
+        let ComparedBoard {
+            board, rules, grid, ..
+        } = &mut *compared;
+        grid.compute_next(board, rules);
+        grid.swap();
+        grid.age_cells();
+        grid.decay_cells(rules.states);
+        if **generation == 0 {
+            grid.last_changed.fill(None);
+        }
+        grid.track_changes(**generation + 1);
+    }
+}
+
+/// the boards comparing other rulesets show the main board while it's set up
+fn mirror_main_board(
+    grid: Res<LifeGrid>,
+    board: Res<Board>,
+    mut compared_boards: Query<&mut ComparedBoard>,
+) {
+    for mut compared in compared_boards.iter_mut() {
+        compared.grid = grid.clone();
+        compared.board.wrap_x = board.wrap_x;
+        compared.board.wrap_y = board.wrap_y;
+    }
+}
+
+/// recount the whole population whenever the grid changes
+fn recount_population(grid: Res<LifeGrid>, mut population: ResMut<Population>) {
+    population.set_if_neq(Population(grid.population()));
+}
+
+/// pauses the game once the board starts repeating itself (it only contains still lifes and
+/// oscillators with a short period)
+fn detect_stasis(
+    mut detector: ResMut<StasisDetector>,
+    grid: Res<LifeGrid>,
+    generation: Res<Generation>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if !detector.enabled {
+        return;
+    }
+
+    let mut hasher = DefaultHasher::new();
+    grid.cells.hash(&mut hasher);
+    if let Some(period) = detector.record(hasher.finish()) {
+        // the first repetition is found, so the repeated generation is where the cycle started
+        let start = generation.saturating_sub(period as u64);
+        info!("the board has stabilized with period {period} at generation {start}");
+        detector.history.clear();
+        detector.stabilized = Some((start, period));
+        if *state.get() == GameState::Running {
+            next_state.set(GameState::Paused);
+        }
+    }
+}
+
+/// stops the simulation once the board died out or grew too large, see `PopulationLimits`
+fn stop_at_population_limits(
+    limits: Res<PopulationLimits>,
+    grid: Res<LifeGrid>,
+    generation: Res<Generation>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let population = grid.population();
+    if population == 0 && limits.stop_on_extinction {
+        info!("extinct at generation {}", **generation);
+    } else if let Some(max) = limits.max_population.filter(|&max| population > max) {
+        info!(
+            "the population of {population} exceeded {max} at generation {}",
+            **generation
+        );
+    } else {
+        return;
+    }
+    if let Some(stopped) = GameState::Running.started_or_stopped() {
+        next_state.set(stopped);
+    }
+}
+
+/// appends the population of the generation to the `PopulationLog`, giving up on errors
+fn log_population(
+    mut commands: Commands,
+    mut log: ResMut<PopulationLog>,
+    grid: Res<LifeGrid>,
+    generation: Res<Generation>,
+) {
+    let PopulationLog { path, writer } = &mut *log;
+    let result = match writer {
+        Some(writer) => export::write_population_row(writer, **generation, grid.population()),
+        None => export::open_population_csv(&*path).and_then(|opened| {
+            export::write_population_row(writer.insert(opened), **generation, grid.population())
+        }),
+    };
+    if let Err(e) = result {
+        warn!("stopped logging the population to {}: {e}", path.display());
+        commands.remove_resource::<PopulationLog>();
+    }
+}
+
+fn flush_population_log(mut log: ResMut<PopulationLog>) {
+    let PopulationLog { path, writer } = &mut *log;
+    if let Some(Err(e)) = writer.as_mut().map(|writer| writer.flush()) {
+        warn!("failed to write the population log {}: {e}", path.display());
+    }
+}
+
+fn record_population(grid: Res<LifeGrid>, mut history: ResMut<PopulationHistory>) {
+    if history.len() == POPULATION_GRAPH_LEN {
+        history.pop_front();
+    }
+    history.push_back(grid.population());
+}
+
+fn reset_population_history(mut history: ResMut<PopulationHistory>) {
+    history.clear();
+}
+
+fn toggle_population_graph(mut show_graph: ResMut<ShowPopulationGraph>) {
+    **show_graph = !**show_graph;
+    info!("population graph: {}", **show_graph);
+}
+
+/// draws the recent population in the bottom left corner of the window, scaled to the highest
+/// recent population
+fn draw_population_graph(
+    mut gizmos: Gizmos,
+    history: Res<PopulationHistory>,
+    cam_query: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+) {
+    let Ok((camera, cam_transform)) = cam_query.get_single() else {
+        return;
+    };
+    let Some(viewport_size) = camera.logical_viewport_size() else {
+        return;
+    };
+    // graph positions are in viewport coordinates, where y goes down
+    let margin = 16.0;
+    let bottom_left = vec2(margin, viewport_size.y - margin);
+    let to_world = |pos: Vec2| camera.viewport_to_world_2d(cam_transform, pos).ok();
+
+    let max_population = history.iter().copied().max().unwrap_or_default().max(1);
+    let step = POPULATION_GRAPH_SIZE_PX.x / (POPULATION_GRAPH_LEN - 1) as f32;
+    let points = history.iter().enumerate().map(|(i, &population)| {
+        let height = population as f32 / max_population as f32 * POPULATION_GRAPH_SIZE_PX.y;
+        bottom_left + vec2(i as f32 * step, -height)
+    });
+    if let Some(points) = points.map(to_world).collect::<Option<Vec<_>>>() {
+        gizmos.linestrip_2d(points, POPULATION_GRAPH_COLOR);
+    }
+    // axes
+    let axes = [
+        bottom_left - vec2(0.0, POPULATION_GRAPH_SIZE_PX.y),
+        bottom_left,
+        bottom_left + vec2(POPULATION_GRAPH_SIZE_PX.x, 0.0),
+    ];
+    if let Some(axes) = axes.map(to_world).into_iter().collect::<Option<Vec<_>>>() {
+        gizmos.linestrip_2d(axes, GRID_COLOR);
+    }
+}
+
+fn toggle_stasis_detection(mut detector: ResMut<StasisDetector>) {
+    detector.enabled = !detector.enabled;
+    detector.history.clear();
+    detector.stabilized = None;
+    info!("auto-pause on a stable board: {}", detector.enabled);
+}
+
+fn reset_stasis_detection(mut detector: ResMut<StasisDetector>) {
+    detector.history.clear();
+    detector.stabilized = None;
+}
+
+/// keeps the generation that is about to be replaced, see `step_back`
+fn record_generation_snapshot(
+    mut snapshots: ResMut<GenerationSnapshots>,
+    grid: Res<LifeGrid>,
+    generation: Res<Generation>,
+    compared_boards: Query<&ComparedBoard>,
+) {
+    snapshots.record(GenerationSnapshot {
+        generation: **generation,
+        cells: grid.cells.clone(),
+        compared_cells: compared_boards
+            .iter()
+            .map(|compared| compared.grid.cells.clone())
+            .collect(),
+    });
+}
+
+/// Restores the generation before the last step, along with the boards comparing other
+/// rulesets. Can be undone while the board is set up.
+fn step_back(
+    mut snapshots: ResMut<GenerationSnapshots>,
+    mut grid: ResMut<LifeGrid>,
+    mut generation: ResMut<Generation>,
+    mut compared_boards: Query<&mut ComparedBoard>,
+    (mut edit_history, mut population_history, mut detector): (
+        ResMut<EditHistory>,
+        ResMut<PopulationHistory>,
+        ResMut<StasisDetector>,
+    ),
+    state: Res<State<GameState>>,
+) {
+    // snapshots taken before the board was resized don't fit it anymore
+    let Some(snapshot) = snapshots
+        .pop_back()
+        .filter(|snapshot| snapshot.cells.len() == grid.cells.len())
+    else {
+        snapshots.clear();
+        info!("no earlier generation to step back to");
+        return;
+    };
+
+    if *state.get() == GameState::Setup {
+        edit_history.record(grid.cells.clone());
+    }
+    let steps = generation.saturating_sub(snapshot.generation);
+    grid.rewind(snapshot.cells, snapshot.generation, steps);
+    for (mut compared, cells) in compared_boards.iter_mut().zip(snapshot.compared_cells) {
+        if cells.len() == compared.grid.cells.len() {
+            compared.grid.rewind(cells, snapshot.generation, steps);
+        }
+    }
+    **generation = snapshot.generation;
+    population_history.pop_back();
+    detector.history.clear();
+}
+
+fn reset_generation_snapshots(mut snapshots: ResMut<GenerationSnapshots>) {
+    snapshots.clear();
+}
+
+fn advance_generation(mut generation: ResMut<Generation>) {
+    **generation += 1;
+}
+
+fn reset_generation(mut generation: ResMut<Generation>) {
+    **generation = 0;
+}
+
+/// the board is edited as live and dead cells only
+fn reset_decay(mut grid: ResMut<LifeGrid>) {
+    if grid.decay.iter().any(|&decay| decay > 0) {
+        grid.decay.fill(0);
+    }
+}
+
+// ——> HELPERS
+
+/// creates the vertical and horizontal border meshes that fit around the board
+fn add_border_meshes(
+    meshes: &mut Assets<Mesh>,
+    board: &Board,
+    width: f32,
+) -> (Handle<Mesh>, Handle<Mesh>) {
+    let border_vert = meshes.add(Rectangle::new(width, board.pixel_size().y + 2.0 * width));
+    let border_horiz = meshes.add(Rectangle::new(board.pixel_size().x + 2.0 * width, width));
+    (border_vert, border_horiz)
+}
+
+/// the z coordinate of a cell, within the layers from `CELL_DEAD_Z` to `CURSOR_CELL_Z`
+fn cell_depth(alive: bool, under_cursor: bool) -> f32 {
+    match (alive, under_cursor) {
+        (_, true) => CURSOR_CELL_Z,
+        (true, false) => CELL_ALIVE_Z,
+        (false, false) => CELL_DEAD_Z,
+    }
+}
+
+/// the border is tinted while the board wraps around, if the wrap indicator is shown
+fn border_material_key(board: &Board, border: Border, show_wrap_indicator: bool) -> MaterialKey {
+    // the left and right sides are connected by wrapping along the x axis
+    let wraps = if border.vertical {
+        board.wrap_x
+    } else {
+        board.wrap_y
+    };
+    if wraps && show_wrap_indicator {
+        MaterialKey::BorderWrap
+    } else {
+        MaterialKey::Border
+    }
+}
+
+/// there are no edges to show on an unbounded board
+fn border_visibility(show_border: bool, unbounded: bool) -> Visibility {
+    if show_border && !unbounded {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+/// a square outline with the given outer size, centered on the origin
+fn hollow_rectangle(size: Vec2, width: f32) -> Mesh {
+    let outer = size * 0.5;
+    let inner = (outer - width).max(Vec2::ZERO);
+    let corners = [
+        vec2(-1.0, -1.0),
+        vec2(1.0, -1.0),
+        vec2(1.0, 1.0),
+        vec2(-1.0, 1.0),
+    ];
+    // outer corners first, then the inner ones, counterclockwise from the bottom left
+    let positions = corners
+        .iter()
+        .map(|corner| corner * outer)
+        .chain(corners.iter().map(|corner| corner * inner))
+        .map(|pos| [pos.x, pos.y, 0.0])
+        .collect::<Vec<_>>();
+    let uvs = positions
+        .iter()
+        .map(|[x, y, _]| [x / size.x + 0.5, 0.5 - y / size.y])
+        .collect::<Vec<_>>();
+    // two triangles for each side
+    let indices = (0..4u32)
+        .flat_map(|i| {
+            let next = (i + 1) % 4;
+            [i, next, next + 4, i, next + 4, i + 4]
+        })
+        .collect();
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; 8])
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    .with_inserted_indices(Indices::U32(indices))
+}
+
+/// Sets the pattern's live cells alive, with the top left corner of the pattern at `top_left`.
+///
+/// Patterns are stored with their rows going downwards, so they get flipped to match the board's
+/// y axis. Cells that fall outside of the board wrap around the edges that wrap and are skipped
+/// beyond the ones that don't.
+fn stamp_pattern(pattern: &Pattern, top_left: IVec2, board: &Board, grid: &mut LifeGrid) {
+    for cell_coord in stamp_cell_coords(pattern, top_left, board) {
+        grid.set_alive(board.cell_coord_to_idx(cell_coord), true);
+    }
+}
+
+/// the cells on the board the live cells of the pattern land on, see `stamp_pattern`
+fn stamp_cell_coords<'a>(
+    pattern: &'a Pattern,
+    top_left: IVec2,
+    board: &'a Board,
+) -> impl Iterator<Item = UVec2> + 'a {
+    pattern
+        .live_cells
+        .iter()
+        .map(move |cell| top_left + ivec2(cell.x as i32, -(cell.y as i32)))
+        .filter_map(|cell_coord| board.wrapped_cell_coord(cell_coord))
+}
+
+/// pointer events that paint cells, see `cells_set_life_on`
+trait PaintEvent: Event {
+    fn button(&self) -> PointerButton;
+
+    /// whether the event starts a new click / drag stroke
+    fn starts_stroke(&self) -> bool;
+}
+
+impl PaintEvent for Pointer<Down> {
+    fn button(&self) -> PointerButton {
+        self.button
+    }
+
+    fn starts_stroke(&self) -> bool {
+        true
+    }
+}
+
+impl PaintEvent for Pointer<DragOver> {
+    fn button(&self) -> PointerButton {
+        self.button
+    }
+
+    fn starts_stroke(&self) -> bool {
+        false
+    }
+}
+
+/// transformations of the whole board, for designing symmetric patterns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BoardTransform {
+    FlipHorizontal,
+    FlipVertical,
+    /// by 90° counterclockwise
+    Rotate,
+}
+
+impl BoardTransform {
+    /// where the cell at `cell_coord` ends up after the transformation
+    fn map_cell_coord(self, board: &Board, cell_coord: UVec2) -> UVec2 {
+        let max = board.size() - 1;
+        match self {
+            BoardTransform::FlipHorizontal => uvec2(max.x - cell_coord.x, cell_coord.y),
+            BoardTransform::FlipVertical => uvec2(cell_coord.x, max.y - cell_coord.y),
+            // only square boards can be rotated in place
+            BoardTransform::Rotate => uvec2(max.y - cell_coord.y, cell_coord.x),
+        }
+    }
+
+    /// `cells` are indexed by `Board::cell_coord_to_idx`
+    fn apply(self, board: &Board, cells: &[bool]) -> Vec<bool> {
+        let mut transformed = vec![false; cells.len()];
+        for (idx, _) in cells.iter().enumerate().filter(|(_, alive)| **alive) {
+            let cell_coord = self.map_cell_coord(board, board.idx_to_cell_coord(idx));
+            transformed[board.cell_coord_to_idx(cell_coord)] = true;
+        }
+        transformed
+    }
+}
+
+// ——> COMPONENTS
+
+#[derive(Component)]
+#[require(
+    CurrentAlive,
+    Age,
+    LastChanged,
+    Decay,
+    Fade,
+    CellInteraction,
+    UnderCursor,
+    FutureAlive,
+    Mesh2d
+)]
+struct Cell;
+
+/// the cell's index into the `LifeGrid`
+#[derive(Component, Debug, Clone, Copy, Deref)]
+struct CellIdx(usize);
+
+/// mirrors the cell's life status in the `LifeGrid`, see `sync_cell_entities`
+#[derive(Component, Debug, Default, PartialEq, Eq, DerefMut, Deref)]
+struct CurrentAlive(bool);
+
+/// the time left for the cell to fade after it changed, see `ShowFades`
+#[derive(Component, Debug, Default, DerefMut, Deref)]
+struct Fade(Option<Timer>);
+
+/// mirrors the amount of generations the cell has been continuously alive for
+#[derive(Component, Debug, Default, PartialEq, Eq, DerefMut, Deref)]
+struct Age(u32);
+
+/// mirrors the amount of generations since the cell last changed, `None` if it hasn't changed
+/// since the board was last set up
+#[derive(Component, Debug, Default, PartialEq, Eq, DerefMut, Deref)]
+struct LastChanged(Option<u64>);
+
+/// mirrors how many generations ago the cell died while it's decaying, see `Ruleset::states`
+#[derive(Component, Debug, Default, PartialEq, Eq, DerefMut, Deref)]
+struct Decay(u8);
+
+/// the cell's life status in the next generation while it's previewed, see `ShowFuture`
+#[derive(Component, Debug, Default, PartialEq, Eq, DerefMut, Deref)]
+struct FutureAlive(Option<bool>);
+
+/// whether the keyboard cursor is on the cell, see `CursorCell`
+#[derive(Component, Debug, Default, PartialEq, Eq, DerefMut, Deref)]
+struct UnderCursor(bool);
+
+/// How the pointer is interacting with a cell, which determines the cell's material.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum CellInteraction {
+    #[default]
+    Idle,
+    Hovered,
+    /// stays clicked until the button is released or the pointer leaves the cell
+    Clicked,
+}
+
+/// pointer events that change a cell's `CellInteraction`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CellPointerEvent {
+    Over,
+    Out,
+    Press,
+    Release,
+}
+
+impl CellInteraction {
+    fn transition(self, event: CellPointerEvent) -> CellInteraction {
+        match (self, event) {
+            (_, CellPointerEvent::Out) => CellInteraction::Idle,
+            (_, CellPointerEvent::Press) => CellInteraction::Clicked,
+            // hovering never clobbers the clicked highlight
+            (CellInteraction::Clicked, CellPointerEvent::Over) => CellInteraction::Clicked,
+            (_, CellPointerEvent::Over | CellPointerEvent::Release) => CellInteraction::Hovered,
+        }
+    }
+
+    /// the key of the material in `MeshAndMats` a cell in this state should use
+    fn material_key(self, alive: bool) -> MaterialKey {
+        match (self, alive) {
+            (CellInteraction::Idle, true) => MaterialKey::CellAlive,
+            (CellInteraction::Idle, false) => MaterialKey::CellDead,
+            (CellInteraction::Hovered, true) => MaterialKey::CellHoveredAlive,
+            (CellInteraction::Hovered, false) => MaterialKey::CellHoveredDead,
+            (CellInteraction::Clicked, _) => MaterialKey::CellClicked,
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+#[require(Mesh2d)]
+struct Border {
+    /// the left or right side of the board
+    vertical: bool,
+}
+
+/// A board next to the main one that runs another ruleset on the same starting cells.
+///
+/// It can't be edited, its cells mirror the main board while it's set up.
+#[derive(Component, Clone)]
+struct ComparedBoard {
+    board: Board,
+    rules: Ruleset,
+    grid: LifeGrid,
+    /// indexed by `Board::cell_coord_to_idx`
+    cell_entities: Vec<Entity>,
+}
+
+/// marks the text showing the coordinate of the cell under the cursor
+#[derive(Component)]
+struct CursorCoordText;
+
+/// marks the text showing the status of the board, see `update_status_text`
+#[derive(Component)]
+struct StatusText;
+
+/// marks the scrollable column of `Thumbnail`s, newest first
+#[derive(Component)]
+struct ThumbnailGallery;
+
+/// A downsampled image of the board in the gallery, clicking it restores the board.
+#[derive(Component)]
+struct Thumbnail {
+    board: BoardSave,
+    /// the generation the board was captured at
+    generation: u64,
+}
+
+/// marks the text showing the progress of loading the board
+#[derive(Component)]
+struct LoadingText;
+
+// ——> RESOURCES
+
+/// The boards whose cells are still being spawned, only exists while loading.
+#[derive(Resource)]
+struct BoardLoading {
+    /// the main board without a ruleset comes first, followed by the compared boards
+    boards: VecDeque<(Board, Option<Ruleset>)>,
+    /// the cells of the first board in `boards` spawned so far
+    cell_entities: Vec<Entity>,
+    /// amount of cells spawned over all of the boards
+    spawned: usize,
+    total: usize,
+}
+
+impl BoardLoading {
+    /// the fraction of the cells that are spawned
+    fn progress(&self) -> f32 {
+        if self.total == 0 {
+            return 1.0;
+        }
+        self.spawned as f32 / self.total as f32
+    }
+
+    fn is_done(&self) -> bool {
+        self.boards.is_empty()
+    }
+}
+
+/// The cell under the cursor that's moved with the keyboard, only exists while the keyboard
+/// cursor is shown.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+pub struct CursorCell(pub UVec2);
+
+/// the coordinate of the cell currently under the pointer
+#[derive(Resource, Debug, Default, Clone, Copy, DerefMut, Deref)]
+struct HoveredCell(Option<UVec2>);
+
+/// Picks the cells from the cursor's position on the board instead of by casting rays against
+/// every cell's mesh, see `Config::cursor_picking`.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct CursorPicking {
+    /// the cell a mouse button was pressed on
+    pressed: Option<UVec2>,
+    /// the cell last painted / selected during the current stroke
+    last_picked: Option<UVec2>,
+}
+
+/// whether the grid overlay is drawn between the cells
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+struct ShowGrid(bool);
+
+/// how live cells are drawn
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum RenderMode {
+    #[default]
+    Filled,
+    /// only the outline of the cells, overlapping structures read more clearly at high zoom
+    Outline,
+}
+
+/// Pauses the simulation when the window loses focus, to not waste CPU in the background.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PauseOnFocusLoss {
+    pub enabled: bool,
+    /// whether the simulation resumes once the window is focused again
+    pub resume_on_focus: bool,
+    /// whether the simulation is currently paused because of a focus loss
+    paused: bool,
+}
+
+impl Default for PauseOnFocusLoss {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            resume_on_focus: true,
+            paused: false,
+        }
+    }
+}
+
+impl PauseOnFocusLoss {
+    /// returns the state to switch to when the focus of the window changes
+    fn focus_changed(&mut self, focused: bool, state: GameState) -> Option<GameState> {
+        if !focused {
+            self.paused = self.enabled && state == GameState::Running;
+            return self.paused.then_some(GameState::Paused);
+        }
+        // only resume if the simulation wasn't paused by hand in the meantime
+        let resume =
+            std::mem::take(&mut self.paused) && self.resume_on_focus && state == GameState::Paused;
+        resume.then_some(GameState::Running)
+    }
+}
+
+/// Rulesets of the boards that are run next to the main one, see `ComparedBoard`.
+///
+/// Insert it before adding the `LifePlugin` to override the rulesets from the `Config`.
+#[derive(Resource, Debug, Default, Clone, PartialEq, Eq, Deref, DerefMut)]
+pub struct ComparedRulesets(pub Vec<Ruleset>);
+
+/// the pattern stamped when the board is first set up, see `Config::startup_pattern`
+#[derive(Resource, Debug, Clone)]
+struct StartupPattern {
+    /// a built-in pattern name or a pattern file
+    source: Option<String>,
+    autorun: bool,
+}
+
+/// the seed used for randomizing the board, so interesting soups can be reproduced
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+pub struct RngSeed(pub u64);
+
+/// the board the simulation was last started from, see `save_session`
+#[derive(Resource, Debug, Clone)]
+struct SessionStart {
+    /// the session without any generations
+    session: SessionSave,
+    /// the generation the simulation started at
+    generation: u64,
+}
+
+impl SessionStart {
+    /// the session that has run until `generation`
+    fn session_at(&self, generation: u64) -> SessionSave {
+        SessionSave {
+            generations: generation.saturating_sub(self.generation),
+            ..self.session.clone()
+        }
+    }
+}
+
+/// the generation a replayed session stops at
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Deref)]
+struct ReplayTarget(u64);
+
+/// An unbounded board, the cells of the `LifeGrid` only show a window of it.
+///
+/// Anything derived from the grid, like the population, is limited to that window.
+#[derive(Resource, Debug, Clone)]
+struct UnboundedWorld {
+    life: SparseLife,
+    /// the coordinate of the bottom left cell of the window
+    origin: IVec2,
+}
+
+impl UnboundedWorld {
+    /// the cells in the window
+    fn window(&self, board: &Board) -> Vec<bool> {
+        self.life.window(self.origin, board.size())
+    }
+
+    /// writes the cells of the grid back into the window
+    fn store(&mut self, board: &Board, grid: &LifeGrid) {
+        self.life
+            .store_window(self.origin, board.size(), &grid.cells);
+    }
+}
+
+/// whether the live neighbours of the hovered cell are logged, to follow the rules by hand
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+struct ShowNeighbourCount(bool);
+
+/// whether alt-clicking a cell logs its full state, see `probe_cell`
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+struct CellProbe(bool);
+
+/// the corners of the selected rectangle of cells, in the order they were selected in
+#[derive(Resource, Debug, Default, Clone, Copy, DerefMut, Deref)]
+struct CellSelection(Option<(UVec2, UVec2)>);
+
+impl CellSelection {
+    /// the bottom left and top right corners of the selection
+    fn bounds(&self) -> Option<(UVec2, UVec2)> {
+        self.map(|(start, end)| (start.min(end), start.max(end)))
+    }
+}
+
+/// the pattern last copied from the selection
+#[derive(Resource, Debug, Default, Clone, DerefMut, Deref)]
+struct Clipboard(Option<Pattern>);
+
+/// the orientation of the patterns when they are stamped onto the board
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct StampOrientation {
+    /// clockwise, applied after flipping
+    quarter_turns: u8,
+    /// mirrored horizontally
+    flipped: bool,
+}
+
+impl StampOrientation {
+    fn apply(&self, pattern: &Pattern) -> Pattern {
+        let pattern = if self.flipped {
+            pattern.flipped(Flip::Horizontal)
+        } else {
+            pattern.clone()
+        };
+        pattern.rotated(self.quarter_turns)
+    }
+}
+
+/// mirrors painted cells across the axes of the board, for drawing symmetric soups
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Symmetry {
+    #[default]
+    None,
+    /// mirrored left to right
+    Horizontal,
+    /// mirrored top to bottom
+    Vertical,
+    /// mirrored along both axes
+    Quad,
+}
+
+impl Symmetry {
+    fn next(self) -> Self {
+        match self {
+            Symmetry::None => Symmetry::Horizontal,
+            Symmetry::Horizontal => Symmetry::Vertical,
+            Symmetry::Vertical => Symmetry::Quad,
+            Symmetry::Quad => Symmetry::None,
+        }
+    }
+
+    /// The cell and its mirror images, a cell on the center row / column of an odd sized board
+    /// is its own mirror image and isn't repeated.
+    fn mirror_cell_coords(self, board: &Board, cell_coord: UVec2) -> Vec<UVec2> {
+        let flip_horizontal = |c| BoardTransform::FlipHorizontal.map_cell_coord(board, c);
+        let flip_vertical = |c| BoardTransform::FlipVertical.map_cell_coord(board, c);
+        let mut cell_coords = match self {
+            Symmetry::None => vec![cell_coord],
+            Symmetry::Horizontal => vec![cell_coord, flip_horizontal(cell_coord)],
+            Symmetry::Vertical => vec![cell_coord, flip_vertical(cell_coord)],
+            Symmetry::Quad => vec![
+                cell_coord,
+                flip_horizontal(cell_coord),
+                flip_vertical(cell_coord),
+                flip_vertical(flip_horizontal(cell_coord)),
+            ],
+        };
+        cell_coords.sort_by_key(|c| (c.y, c.x));
+        cell_coords.dedup();
+        cell_coords
+    }
+}
+
+/// A pattern that follows the hovered cell until it's stamped with a click, only exists while a
+/// stamp is previewed.
+#[derive(Resource, Debug, Clone)]
+struct StampPreview {
+    /// what is stamped, for the logs
+    description: String,
+    /// the orientation is applied when stamping, so it can be changed during the preview
+    pattern: Pattern,
+}
+
+/// the bottom left and top right corners of the gliders last found on the board
+#[derive(Resource, Debug, Clone)]
+struct GliderHighlights {
+    matches: Vec<(UVec2, UVec2)>,
+    /// the highlights are removed once it finishes
+    timer: Timer,
+}
+
+/// The times of the most recent taps in seconds, oldest first.
+#[derive(Resource, Debug, Default, Clone, Deref)]
+struct TapTempo(VecDeque<f64>);
+
+impl TapTempo {
+    /// records a tap, returning the average tempo of the recent taps in beats per minute
+    fn tap(&mut self, secs: f64) -> Option<f64> {
+        // a long pause starts a new tempo
+        if self
+            .back()
+            .is_some_and(|&last| secs - last > 60.0 / MIN_TAP_TEMPO_BPM)
+        {
+            self.0.clear();
+        }
+        if self.len() == TAP_TEMPO_TAPS {
+            self.0.pop_front();
+        }
+        self.0.push_back(secs);
+
+        let (&first, &last) = (self.front()?, self.back()?);
+        let interval = (last - first) / (self.len() - 1) as f64;
+        (interval > 0.0).then(|| (60.0 / interval).clamp(MIN_TAP_TEMPO_BPM, MAX_TAP_TEMPO_BPM))
+    }
+}
+
+/// Noisy rules, every generation each cell has a chance to flip against the rules.
+///
+/// The flips only depend on the `RngSeed` and the generation, so noisy runs can be reproduced.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct Noise {
+    /// the chance of a cell flipping each generation, between 0 and 1
+    pub flip_chance: f32,
+}
+
+impl Noise {
+    /// flips the cells of the generation after `generation`
+    fn apply(&self, cells: &mut [bool], seed: RngSeed, generation: u64) {
+        if self.flip_chance <= 0.0 {
+            return;
+        }
+        let mut hasher = DefaultHasher::new();
+        (*seed, generation).hash(&mut hasher);
+        let mut rng = fastrand::Rng::with_seed(hasher.finish());
+        for alive in cells.iter_mut() {
+            if rng.f32() < self.flip_chance {
+                *alive = !*alive;
+            }
+        }
+    }
+}
+
+/// percentage of the cells that are alive after randomizing
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+struct SeedDensity(u32);
+
+impl Default for SeedDensity {
+    fn default() -> Self {
+        Self(SEED_DENSITY)
+    }
+}
+
+/// the cells that get randomized, see `LifeGrid::randomize`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SeedRegion {
+    Board,
+    /// the cells between the bottom left and top right corners
+    Rect(UVec2, UVec2),
+    /// the cells inside the ellipse inscribed in the rectangle between the corners
+    Ellipse(UVec2, UVec2),
+}
+
+impl SeedRegion {
+    pub fn contains(self, cell_coord: UVec2) -> bool {
+        match self {
+            SeedRegion::Board => true,
+            SeedRegion::Rect(min, max) => {
+                cell_coord.cmpge(min).all() && cell_coord.cmple(max).all()
+            }
+            SeedRegion::Ellipse(min, max) => {
+                // measured between the centers of the cells
+                let center = (min + max).as_vec2() * 0.5;
+                let radii = (max - min + 1).as_vec2() * 0.5;
+                ((cell_coord.as_vec2() - center) / radii).length_squared() <= 1.0
+            }
+        }
+    }
+}
+
+/// radius of the square of cells painted at once, 0 paints a single cell
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+struct BrushSize(u32);
+
+/// whether cells fade between being dead and alive instead of changing instantly
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+struct ShowFades(bool);
+
+/// whether cells are colored by their recent activity instead of their life status
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+struct ShowHeatmap(bool);
+
+/// Whether dead cells are hidden, instead of drawn in the background color. Less cells are
+/// drawn, but they are still picked, see `update_hidden_cell_picking`.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+struct HideDeadCells(bool);
+
+/// Whether live cells and the cell under the keyboard cursor are drawn over the dead cells, see
+/// `update_cell_depth`. It's off by default, as moving the cells between the layers updates the
+/// transforms of all the cells that change every generation.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+struct LayerCells(bool);
+
+/// Whether the cells that change in the next generation are tinted, it's only previewed while
+/// the game isn't running.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+struct ShowFuture(bool);
+
+/// whether the sides of the border are tinted while the board wraps around them
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+struct ShowWrapIndicator(bool);
+
+impl Default for ShowWrapIndicator {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// whether the border is drawn around the boards
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+struct ShowBorder(bool);
+
+impl Default for ShowBorder {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// width of the border around the boards in pixels
+#[derive(Resource, Debug, Clone, Copy, PartialEq, DerefMut, Deref)]
+struct BorderWidth(f32);
+
+impl Default for BorderWidth {
+    fn default() -> Self {
+        Self(BORDER_WIDTH_PX)
+    }
+}
+
+/// the population of the most recent generations, oldest first
+#[derive(Resource, Debug, Default, Clone, DerefMut, Deref)]
+struct PopulationHistory(VecDeque<u32>);
+
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+struct ShowPopulationGraph(bool);
+
+/// whether the status of the board is shown in the top right corner
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+struct ShowStatus(bool);
+
+/// the amount of generations the board has advanced since it was last set up
+#[derive(Resource, Debug, Default, Clone, Copy, DerefMut, Deref)]
+pub struct Generation(pub u64);
+
+/// Sent for every cell painted in setup, by clicking, dragging or with the keyboard cursor.
+///
+/// Part of the public API so other plugins can react to the edits, e.g. by playing a sound from
+/// a system reading an `EventReader<CellToggled>`. Changing many cells at once, like clearing,
+/// randomizing, stamping or undoing, doesn't send it.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellToggled {
+    /// the cell of the main board, see `Board::cell_coord_to_idx`
+    pub cell_coord: UVec2,
+    /// whether the cell was painted alive or dead
+    pub alive: bool,
+}
+
+/// Whether every generation step is checked against a plain recount of the neighbours, logging
+/// the cells where the two disagree.
+///
+/// Off by default since it steps the board a second time. Insert it before adding the
+/// `LifePlugin` to catch mistakes in the stepping, e.g. on the GPU. Unbounded boards and noisy
+/// rules aren't checked.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+pub struct DebugVerify(pub bool);
+
+/// the amount of cells that are currently alive
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+pub struct Population(pub u32);
+
+/// Handles of the meshes and materials shared by all the cells.
+///
+/// The cells change color in two ways:
+/// - applying the `Theme` mutates the material assets in place, see `recolor`. Every cell
+///   sharing a material changes at once without touching the cells, and only the materials whose
+///   color changed are mutated, so the others aren't extracted for rendering again.
+/// - a cell changing its state swaps its material handle, see `handle_cell_color_main`. Only
+///   handles that differ are reassigned, so the cells keeping theirs aren't marked as changed.
+///
+/// Mutating a material in place recolors every cell sharing it, colors of single cells have to
+/// come from swapping handles.
+#[derive(Resource, Clone)]
+struct MeshAndMats {
+    /// indexed by `MeshKey`
+    meshes: [Handle<Mesh>; MeshKey::ALL.len()],
+    /// indexed by `MaterialKey`
+    materials: [Handle<ColorMaterial>; MaterialKey::ALL.len()],
+    /// materials of live cells, from the youngest to the oldest
+    age_materials: Vec<Handle<ColorMaterial>>,
+    /// materials of the heatmap mode, from the most to the least recently changed cells
+    heat_materials: Vec<Handle<ColorMaterial>>,
+    /// materials of fading cells, from dead to alive
+    fade_materials: Vec<Handle<ColorMaterial>>,
+    /// materials of decaying cells, from the ones that just died to the ones about to be dead
+    decay_materials: Vec<Handle<ColorMaterial>>,
+}
+
+impl MeshAndMats {
+    fn mesh(&self, key: MeshKey) -> &Handle<Mesh> {
+        &self.meshes[key as usize]
+    }
+
+    fn material(&self, key: MaterialKey) -> &Handle<ColorMaterial> {
+        &self.materials[key as usize]
+    }
+
+    fn age_material(&self, age: u32) -> &Handle<ColorMaterial> {
+        let bucket = (age / AGE_BUCKET_GENERATIONS) as usize;
+        &self.age_materials[bucket.min(AGE_BUCKETS - 1)]
+    }
+
+    /// `generations_since_change` is `None` for cells that haven't changed since the board was
+    /// last set up
+    fn heat_material(&self, generations_since_change: Option<u64>) -> &Handle<ColorMaterial> {
+        let heat = generations_since_change.unwrap_or(u64::MAX);
+        &self.heat_materials[heat.min(HEATMAP_FADE_GENERATIONS as u64) as usize]
+    }
+
+    /// `decay` is the amount of generations since the cell died, at least 1
+    fn decay_material(&self, decay: u8) -> &Handle<ColorMaterial> {
+        let step = decay.saturating_sub(1) as usize;
+        &self.decay_materials[step.min(DECAY_STEPS - 1)]
+    }
+
+    /// sets the colors of all the cell materials from the theme, leaving the materials that
+    /// already have their color untouched
+    fn recolor(&self, theme: &Theme, materials: &mut Assets<ColorMaterial>) {
+        let mut set_color = |handle: &Handle<ColorMaterial>, color: Color| {
+            // `get_mut` marks the material as modified even if the color stays the same
+            if materials
+                .get(handle)
+                .is_some_and(|material| material.color != color)
+            {
+                if let Some(material) = materials.get_mut(handle) {
+                    material.color = color;
+                }
+            }
+        };
+        for key in MaterialKey::ALL {
+            set_color(self.material(key), key.color(theme));
+        }
+        // each gradient goes from the first color to the second one
+        for (gradient, from, to) in [
+            (&self.age_materials, theme.cell_alive, theme.cell_old),
+            (&self.heat_materials, theme.heatmap_hot, theme.cell_dead),
+            (&self.fade_materials, theme.cell_dead, theme.cell_alive),
+            (&self.decay_materials, theme.cell_decaying, theme.cell_dead),
+        ] {
+            for (i, material) in gradient.iter().enumerate() {
+                let t = i as f32 / (gradient.len() - 1) as f32;
+                set_color(material, from.mix(&to, t));
+            }
+        }
+    }
+
+    /// `alive_amount` goes from 0 for dead cells to 1 for live ones
+    fn fade_material(&self, alive_amount: f32) -> &Handle<ColorMaterial> {
+        let step = (alive_amount.clamp(0.0, 1.0) * (CELL_FADE_STEPS - 1) as f32).round();
+        &self.fade_materials[step as usize]
+    }
+}
+
+/// the meshes in `MeshAndMats`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MeshKey {
+    Cell,
+    /// the mesh of live cells in `RenderMode::Outline`
+    CellOutline,
+    BorderVert,
+    BorderHoriz,
+}
+
+impl MeshKey {
+    const ALL: [MeshKey; 4] = [
+        MeshKey::Cell,
+        MeshKey::CellOutline,
+        MeshKey::BorderVert,
+        MeshKey::BorderHoriz,
+    ];
+}
+
+/// the single color materials in `MeshAndMats`, the gradients have their own lookups
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MaterialKey {
+    Border,
+    /// the border of edges that wrap around, see `ShowWrapIndicator`
+    BorderWrap,
+    CursorAlive,
+    CursorDead,
+    /// dead cells that are born next generation, see `ShowFuture`
+    CellBorn,
+    /// live cells that die next generation, see `ShowFuture`
+    CellDying,
+    CellAlive,
+    CellDead,
+    CellClicked,
+    CellHoveredAlive,
+    CellHoveredDead,
+}
+
+impl MaterialKey {
+    const ALL: [MaterialKey; 11] = [
+        MaterialKey::Border,
+        MaterialKey::BorderWrap,
+        MaterialKey::CursorAlive,
+        MaterialKey::CursorDead,
+        MaterialKey::CellBorn,
+        MaterialKey::CellDying,
+        MaterialKey::CellAlive,
+        MaterialKey::CellDead,
+        MaterialKey::CellClicked,
+        MaterialKey::CellHoveredAlive,
+        MaterialKey::CellHoveredDead,
+    ];
+
+    /// the color of the material, the cells take theirs from the theme
+    fn color(self, theme: &Theme) -> Color {
+        match self {
+            MaterialKey::Border => BORDER_COLOR,
+            MaterialKey::BorderWrap => BORDER_WRAP_COLOR,
+            MaterialKey::CursorAlive => CURSOR_CELL_ALIVE_COLOR,
+            MaterialKey::CursorDead => CURSOR_CELL_DEAD_COLOR,
+            MaterialKey::CellBorn => CELL_BORN_COLOR,
+            MaterialKey::CellDying => CELL_DYING_COLOR,
+            MaterialKey::CellAlive => theme.cell_alive,
+            MaterialKey::CellDead => theme.cell_dead,
+            MaterialKey::CellClicked => theme.cell_clicked,
+            MaterialKey::CellHoveredAlive => theme.cell_hovered_alive,
+            MaterialKey::CellHoveredDead => theme.cell_hovered_dead,
+        }
+    }
+}
+
+/// The CSV file the population of each generation is appended to while running, see
+/// `Config::population_csv`.
+#[derive(Resource, Debug)]
+struct PopulationLog {
+    path: PathBuf,
+    /// opened once the first generation is logged
+    writer: Option<BufWriter<File>>,
+}
+
+/// The most generations the fixed timestep advances in a single frame.
+///
+/// When stepping the board takes longer than the update interval, the pending steps pile up
+/// faster than they are run and every frame takes longer than the last. The steps past the cap are
+/// dropped instead, so the simulation falls behind its set rate but the window stays responsive.
+/// A higher cap keeps up with the rate through longer hitches, at the cost of longer frames.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, DerefMut, Deref)]
+pub struct MaxStepsPerFrame(pub u32);
+
+impl Default for MaxStepsPerFrame {
+    fn default() -> Self {
+        Self(MAX_STEPS_PER_FRAME)
+    }
+}
+
+/// the generations the fixed timestep advanced in the current frame, see `MaxStepsPerFrame`
+#[derive(Resource, Debug, Default, Clone, Copy, DerefMut, Deref)]
+struct StepsThisFrame(u32);
+
+/// Stops the simulation once the population reaches a limit.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PopulationLimits {
+    /// whether the simulation stops once all of the cells died
+    pub stop_on_extinction: bool,
+    /// the simulation stops once more cells than this are alive, e.g. when a soup explodes
+    pub max_population: Option<u32>,
+}
+
+impl Default for PopulationLimits {
+    fn default() -> Self {
+        Self {
+            stop_on_extinction: true,
+            max_population: None,
+        }
+    }
+}
+
+/// Keeps hashes of the most recent generations to detect when the board stops changing.
+#[derive(Resource, Debug, Default)]
+struct StasisDetector {
+    enabled: bool,
+    /// oldest generation first
+    history: VecDeque<u64>,
+    /// the generation the board started repeating at and the period, once it stabilized
+    stabilized: Option<(u64, usize)>,
+}
+
+impl StasisDetector {
+    /// records the hash of a new generation, returning the period if it repeats a recent one
+    fn record(&mut self, hash: u64) -> Option<usize> {
+        let period = self
+            .history
+            .iter()
+            .rev()
+            .position(|&recent| recent == hash)
+            .map(|i| i + 1);
+        if self.history.len() == STASIS_HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back(hash);
+        period
+    }
+}
+
+/// Snapshots of the board taken before each edit in setup, to support undo / redo.
+#[derive(Resource, Debug, Default)]
+struct EditHistory {
+    /// most recent edit last
+    undo: VecDeque<Vec<bool>>,
+    redo: Vec<Vec<bool>>,
+    /// cells changed during a click / drag stroke are recorded as a single edit
+    stroke_in_progress: bool,
+    /// Indices of the cells changed during the current stroke, which it doesn't change again.
+    ///
+    /// Dragging with both buttons held would otherwise flip cells back and forth as the drag
+    /// passes over them again.
+    stroke_cells: HashSet<usize>,
+    /// whether the board was edited since it was last saved or loaded, see
+    /// `save_recovery_on_exit`
+    unsaved: bool,
+}
+
+impl EditHistory {
+    /// records the state of the board before an edit
+    fn record(&mut self, cells: Vec<bool>) {
+        self.push_undo(cells);
+        self.redo.clear();
+        self.unsaved = true;
+    }
+
+    /// returns the state of the board before the last edit
+    fn undo(&mut self, current: Vec<bool>) -> Option<Vec<bool>> {
+        let previous = self.undo.pop_back()?;
+        self.redo.push(current);
+        self.unsaved = true;
+        Some(previous)
+    }
+
+    /// returns the state of the board before the last undo
+    fn redo(&mut self, current: Vec<bool>) -> Option<Vec<bool>> {
+        let next = self.redo.pop()?;
+        self.push_undo(current);
+        self.unsaved = true;
+        Some(next)
+    }
+
+    /// forgets the recorded edits, the board stays unsaved
+    fn clear(&mut self) {
+        *self = EditHistory {
+            unsaved: self.unsaved,
+            ..default()
+        };
+    }
+
+    fn push_undo(&mut self, cells: Vec<bool>) {
+        if self.undo.len() == EDIT_HISTORY_LEN {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(cells);
+    }
+}
+
+/// A generation of the board, taken right before it was stepped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct GenerationSnapshot {
+    generation: u64,
+    cells: Vec<bool>,
+    /// the cells of each of the boards comparing other rulesets
+    compared_cells: Vec<Vec<bool>>,
+}
+
+/// The most recent generations, oldest first, to step backwards through.
+///
+/// The rules can't be run backwards, so only the last `GENERATION_HISTORY_LEN` generations can
+/// be restored.
+#[derive(Resource, Debug, Default, Deref, DerefMut)]
+struct GenerationSnapshots(VecDeque<GenerationSnapshot>);
+
+impl GenerationSnapshots {
+    fn record(&mut self, snapshot: GenerationSnapshot) {
+        if self.len() == GENERATION_HISTORY_LEN {
+            self.pop_front();
+        }
+        self.push_back(snapshot);
+    }
+}
+
+/// entities of all the cells, indexed by `Board::cell_coord_to_idx`
+#[derive(Resource, Debug, Clone, Deref)]
+struct CellEntities(Vec<Entity>);
+
+// the grid's patterns and seeding, the simulation itself is in `board`
+impl LifeGrid {
+    /// the cells between the bottom left and top right corners as a pattern, see `stamp_pattern`
+    fn copy_region(&self, board: &Board, min: UVec2, max: UVec2) -> Pattern {
+        let live_cells = (min.y..=max.y)
+            .rev()
+            .flat_map(|y| (min.x..=max.x).map(move |x| uvec2(x, y)))
+            .filter(|&cell_coord| self.cells[board.cell_coord_to_idx(cell_coord)])
+            // patterns have their rows going downwards
+            .map(|cell_coord| uvec2(cell_coord.x - min.x, max.y - cell_coord.y))
+            .collect();
+        Pattern {
+            width: max.x - min.x + 1,
+            height: max.y - min.y + 1,
+            live_cells,
+        }
+    }
+
+    /// Finds the places where the pattern lies on the board surrounded by dead cells, returned as
+    /// the bottom left and top right corners of the pattern. The pattern has to fit on the board,
+    /// only the surrounding cells wrap around the edges of a wrapping board.
+    fn find_pattern(&self, board: &Board, pattern: &Pattern) -> Vec<(UVec2, UVec2)> {
+        let (width, height) = (pattern.width as i32, pattern.height as i32);
+        // offsets from the pattern's bottom left corner, including a margin of dead cells
+        let offsets = || (-1..=height).flat_map(move |y| (-1..=width).map(move |x| ivec2(x, y)));
+        let expected = offsets()
+            .map(|offset| {
+                // patterns have their rows going downwards
+                let cell = ivec2(offset.x, height - 1 - offset.y);
+                cell.cmpge(IVec2::ZERO).all() && pattern.live_cells.contains(&cell.as_uvec2())
+            })
+            .collect::<Vec<_>>();
+        let is_alive = |cell_coord| {
+            board
+                .wrapped_cell_coord(cell_coord)
+                .is_some_and(|cell_coord| self.cells[board.cell_coord_to_idx(cell_coord)])
+        };
+
+        let mut matches = Vec::new();
+        for y in 0..=board.height as i32 - height {
+            for x in 0..=board.width as i32 - width {
+                let bottom_left = ivec2(x, y);
+                if offsets()
+                    .zip(&expected)
+                    .all(|(offset, &alive)| is_alive(bottom_left + offset) == alive)
+                {
+                    let top_right = bottom_left + ivec2(width - 1, height - 1);
+                    matches.push((bottom_left.as_uvec2(), top_right.as_uvec2()));
+                }
+            }
+        }
+        matches
+    }
+
+    /// Fills the region with random cells and kills the ones outside of it, `density` percent
+    /// of the region ends up alive on average. The same seed always gives the same cells.
+    fn randomize(
+        &mut self,
+        board: &Board,
+        seed: RngSeed,
+        density: SeedDensity,
+        region: SeedRegion,
+    ) {
+        let mut rng = fastrand::Rng::with_seed(*seed);
+        for idx in 0..self.cells.len() {
+            let alive = rng.u32(0..100) < *density && region.contains(board.idx_to_cell_coord(idx));
+            self.set_alive(idx, alive);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use crate::{board::seeded_test_boards, theme::CellColor};
+
+    use super::*;
+
+    #[test]
+    fn stasis_detector_finds_period() {
+        let mut detector = StasisDetector::default();
+        // a period 3 oscillator, after some chaos
+        let hashes = [10, 11, 12, 1, 2, 3, 1, 2, 3];
+        let periods = hashes.map(|hash| detector.record(hash));
+        assert_eq!(
+            [
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(3),
+                Some(3),
+                Some(3)
+            ],
+            periods
+        );
+        assert_eq!(STASIS_HISTORY_LEN, detector.history.len());
+
+        // periods longer than the history aren't detected
+        let mut detector = StasisDetector::default();
+        for hash in (0..STASIS_HISTORY_LEN as u64 + 1).cycle().take(20) {
+            assert_eq!(None, detector.record(hash));
+        }
+        // but a still life is
+        assert_eq!(None, detector.record(0));
+        assert_eq!(Some(1), detector.record(0));
+    }
+
+    #[test]
+    fn edit_history_undoes_and_redoes() {
+        let mut history = EditHistory::default();
+        assert_eq!(None, history.undo(vec![true]));
+
+        history.record(vec![false, false]);
+        history.record(vec![true, false]);
+        let current = vec![true, true];
+        assert_eq!(Some(vec![true, false]), history.undo(current.clone()));
+        assert_eq!(Some(vec![false, false]), history.undo(vec![true, false]));
+        assert_eq!(None, history.undo(vec![false, false]));
+        assert_eq!(Some(vec![true, false]), history.redo(vec![false, false]));
+        assert_eq!(Some(current), history.redo(vec![true, false]));
+        assert_eq!(None, history.redo(vec![true, true]));
+
+        // a new edit discards the redo history
+        history.undo(vec![true, true]);
+        history.record(vec![true, false]);
+        assert_eq!(None, history.redo(vec![false, true]));
+
+        // the history is bounded
+        for _ in 0..EDIT_HISTORY_LEN * 2 {
+            history.record(vec![]);
+        }
+        assert_eq!(EDIT_HISTORY_LEN, history.undo.len());
+
+        // forgetting the edits doesn't make them saved
+        assert!(history.unsaved);
+        history.clear();
+        assert!(history.undo.is_empty() && history.unsaved);
+    }
+
+    #[test]
+    fn cell_interaction_transitions() {
+        use CellInteraction::*;
+        use CellPointerEvent::*;
+
+        // hover, click, release, leave
+        let states = [Over, Press, Release, Out]
+            .iter()
+            .scan(Idle, |state, &event| {
+                *state = state.transition(event);
+                Some(*state)
+            });
+        assert_eq!(
+            vec![Hovered, Clicked, Hovered, Idle],
+            states.collect::<Vec<_>>()
+        );
+
+        // the clicked highlight persists until release or leaving the cell
+        assert_eq!(Clicked, Clicked.transition(Over));
+        assert_eq!(Clicked, Clicked.transition(Press));
+        assert_eq!(Idle, Clicked.transition(Out));
+
+        assert_eq!(MaterialKey::CellClicked, Clicked.material_key(false));
+        assert_eq!(MaterialKey::CellHoveredAlive, Hovered.material_key(true));
+        assert_eq!(MaterialKey::CellDead, Idle.material_key(false));
+    }
+
+    #[test]
+    fn mesh_and_material_keys_index_in_order() {
+        for (i, key) in MeshKey::ALL.into_iter().enumerate() {
+            assert_eq!(i, key as usize);
+        }
+        for (i, key) in MaterialKey::ALL.into_iter().enumerate() {
+            assert_eq!(i, key as usize);
+        }
+    }
+
+    #[test]
+    fn recoloring_skips_unchanged_materials() {
+        let mut world = World::new();
+        world.init_resource::<Events<AssetEvent<ColorMaterial>>>();
+        let mut materials = Assets::<ColorMaterial>::default();
+        let mut material = || materials.add(ColorMaterial::default());
+        let mesh_n_mats = MeshAndMats {
+            meshes: default(),
+            materials: MaterialKey::ALL.map(|_| material()),
+            age_materials: (0..AGE_BUCKETS).map(|_| material()).collect(),
+            heat_materials: (0..=HEATMAP_FADE_GENERATIONS).map(|_| material()).collect(),
+            fade_materials: (0..CELL_FADE_STEPS).map(|_| material()).collect(),
+            decay_materials: (0..DECAY_STEPS).map(|_| material()).collect(),
+        };
+        world.insert_resource(materials);
+        // recolors the materials, returning the ones that were modified
+        let mut recolor = |theme: &Theme| {
+            mesh_n_mats.recolor(theme, &mut world.resource_mut::<Assets<ColorMaterial>>());
+            world
+                .run_system_once(Assets::<ColorMaterial>::asset_events)
+                .unwrap();
+            world
+                .resource_mut::<Events<AssetEvent<ColorMaterial>>>()
+                .drain()
+                .filter_map(|event| match event {
+                    AssetEvent::Modified { id } => Some(id),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut theme = Theme::OCEAN;
+        assert!(!recolor(&theme).is_empty());
+        assert!(recolor(&theme).is_empty());
+
+        theme.set_color(CellColor::Dead, Color::BLACK);
+        let modified = recolor(&theme);
+        let cell_dead = mesh_n_mats.material(MaterialKey::CellDead).id();
+        let cell_alive = mesh_n_mats.material(MaterialKey::CellAlive).id();
+        assert!(modified.contains(&cell_dead));
+        assert!(!modified.contains(&cell_alive));
+    }
+
+    #[test]
+    fn symmetry_mirrors_cells() {
+        let mut board = Board {
+            center: Vec2::ZERO,
+            cell_size: Vec2::splat(8.0),
+            cell_scale: Vec2::ONE,
+            width: 4,
+            height: 4,
+            wrap_x: false,
+            wrap_y: false,
+        };
+        assert_eq!(
+            vec![uvec2(1, 0), uvec2(2, 0)],
+            Symmetry::Horizontal.mirror_cell_coords(&board, uvec2(1, 0))
+        );
+        assert_eq!(
+            vec![uvec2(1, 0), uvec2(2, 0), uvec2(1, 3), uvec2(2, 3)],
+            Symmetry::Quad.mirror_cell_coords(&board, uvec2(2, 3))
+        );
+
+        // the center row / column of an odd sized board mirrors onto itself
+        (board.width, board.height) = (5, 5);
+        assert_eq!(
+            vec![uvec2(2, 1)],
+            Symmetry::Horizontal.mirror_cell_coords(&board, uvec2(2, 1))
+        );
+        assert_eq!(
+            vec![uvec2(1, 2), uvec2(3, 2)],
+            Symmetry::Quad.mirror_cell_coords(&board, uvec2(1, 2))
+        );
+        assert_eq!(
+            vec![uvec2(2, 2)],
+            Symmetry::Quad.mirror_cell_coords(&board, uvec2(2, 2))
+        );
+        assert_eq!(
+            vec![uvec2(0, 0)],
+            Symmetry::None.mirror_cell_coords(&board, uvec2(0, 0))
+        );
+    }
+
+    #[test]
+    fn board_transforms() {
+        let board = Board {
+            width: 3,
+            height: 3,
+            ..default()
+        };
+        // an L shape isn't symmetric under any of the transforms
+        let cells_of = |live_cells: &[UVec2]| {
+            let mut cells = vec![false; 9];
+            for &cell in live_cells {
+                cells[board.cell_coord_to_idx(cell)] = true;
+            }
+            cells
+        };
+        let l_shape = cells_of(&[uvec2(0, 0), uvec2(1, 0), uvec2(0, 1), uvec2(0, 2)]);
+
+        let flipped_h = cells_of(&[uvec2(2, 0), uvec2(1, 0), uvec2(2, 1), uvec2(2, 2)]);
+        assert_eq!(
+            flipped_h,
+            BoardTransform::FlipHorizontal.apply(&board, &l_shape)
+        );
+        let flipped_v = cells_of(&[uvec2(0, 2), uvec2(1, 2), uvec2(0, 1), uvec2(0, 0)]);
+        assert_eq!(
+            flipped_v,
+            BoardTransform::FlipVertical.apply(&board, &l_shape)
+        );
+        let rotated = cells_of(&[uvec2(2, 0), uvec2(2, 1), uvec2(1, 0), uvec2(0, 0)]);
+        assert_eq!(rotated, BoardTransform::Rotate.apply(&board, &l_shape));
+
+        // four rotations are the identity
+        let rotated_4 = (0..4).fold(l_shape.clone(), |cells, _| {
+            BoardTransform::Rotate.apply(&board, &cells)
+        });
+        assert_eq!(l_shape, rotated_4);
+    }
+
+    #[test]
+    fn generation_advances_each_fixed_update() {
+        let mut app = App::new();
+        app.init_resource::<Generation>()
+            .add_systems(GenerationStep, advance_generation)
+            .add_systems(FixedUpdate, run_generation_step);
+
+        for _ in 0..3 {
+            app.world_mut().run_schedule(FixedUpdate);
+        }
+        assert_eq!(3, **app.world().resource::<Generation>());
+
+        app.world_mut().run_system_once(reset_generation).unwrap();
+        assert_eq!(0, **app.world().resource::<Generation>());
+    }
+
+    #[test]
+    fn plugin_builder_configures_the_board() {
+        let mut app = App::new();
+        app.add_plugins(LifePlugin::new());
+        let board = app.world().resource::<Board>();
+        assert_eq!(UVec2::splat(BOARD_SIZE), board.size());
+        assert!(board.wrap_x && board.wrap_y);
+        assert_eq!(Ruleset::default(), *app.world().resource::<Ruleset>());
+
+        let mut app = App::new();
+        app.insert_resource(Config {
+            seed: Some(3),
+            update_interval_ms: 200,
+            ..default()
+        })
+        .add_plugins(
+            LifePlugin::new()
+                .board_dimensions(uvec2(160, 90))
+                .interval_ms(50)
+                .rule("B36/S23")
+                .wrap(false),
+        );
+        let world = app.world();
+        let board = world.resource::<Board>();
+        assert_eq!(uvec2(160, 90), board.size());
+        assert!(!board.wrap_x && !board.wrap_y);
+        assert_eq!(
+            Duration::from_millis(50),
+            world.resource::<Time<Fixed>>().timestep()
+        );
+        assert_eq!(
+            Ruleset::from_rulestring("B36/S23").unwrap(),
+            *world.resource::<Ruleset>()
+        );
+        // the settings without builder methods still come from the config
+        assert_eq!(3, **world.resource::<RngSeed>());
+    }
+
+    #[test]
+    #[should_panic = "board size"]
+    fn plugin_builder_checks_the_board_size() {
+        LifePlugin::new().board_size(MAX_BOARD_SIZE + 1);
+    }
+
+    #[test]
+    fn recount_matches_stepping() {
+        for (rulestring, board, rules, cells) in seeded_test_boards() {
+            let mut next = vec![false; board.cell_count()];
+            step_board(&cells, &mut next, &board, &rules);
+            assert_eq!(next, recount_next(&cells, &board, &rules), "{rulestring}");
+        }
+    }
+
+    #[test]
+    fn verification_reports_wrong_cells() {
+        let mut world = World::new();
+        let board = Board {
+            width: 6,
+            height: 5,
+            ..default()
+        };
+        let mut grid = LifeGrid::new(board.cell_count());
+        // a blinker
+        for x in 1..4 {
+            grid.cells[board.cell_coord_to_idx(uvec2(x, 2))] = true;
+        }
+        step_board(&grid.cells, &mut grid.next, &board, &Ruleset::default());
+        world.insert_resource(board);
+        world.insert_resource(grid);
+        world.init_resource::<Ruleset>();
+        world.init_resource::<Noise>();
+        world.init_resource::<Generation>();
+        assert!(world
+            .run_system_once(verify_future_life)
+            .unwrap()
+            .is_empty());
+
+        let idx = board.cell_coord_to_idx(uvec2(5, 0));
+        world.resource_mut::<LifeGrid>().next[idx] = true;
+        assert_eq!(
+            vec![uvec2(5, 0)],
+            world.run_system_once(verify_future_life).unwrap()
+        );
+    }
+
+    #[test]
+    fn painting_sends_toggled_cells() {
+        let mut world = World::new();
+        let board = Board {
+            width: 8,
+            height: 8,
+            ..default()
+        };
+        world.insert_resource(board);
+        world.insert_resource(LifeGrid::new(board.cell_count()));
+        world.init_resource::<BrushSize>();
+        world.insert_resource(Symmetry::Horizontal);
+        world.init_resource::<EditHistory>();
+        world.init_resource::<Events<CellToggled>>();
+        world.insert_resource(CursorCell(uvec2(1, 1)));
+        world.run_system_once(paint_cursor_cell).unwrap();
+
+        let mut toggled = world
+            .resource_mut::<Events<CellToggled>>()
+            .drain()
+            .map(|toggled| (toggled.cell_coord, toggled.alive))
+            .collect::<Vec<_>>();
+        toggled.sort_by_key(|(cell_coord, _)| (cell_coord.x, cell_coord.y));
+        assert_eq!(vec![(uvec2(1, 1), true), (uvec2(6, 1), true)], toggled);
+
+        // painting again kills both cells
+        world.run_system_once(paint_cursor_cell).unwrap();
+        assert_eq!(2, world.resource::<Events<CellToggled>>().len());
+    }
+
+    #[test]
+    fn strokes_change_cells_once() {
+        let mut world = World::new();
+        let board = Board {
+            width: 8,
+            height: 8,
+            ..default()
+        };
+        world.insert_resource(board);
+        world.insert_resource(LifeGrid::new(board.cell_count()));
+        world.init_resource::<EditHistory>();
+        world.init_resource::<Events<CellToggled>>();
+        let paint = |paint_alive| {
+            move |board: Res<Board>,
+                  mut history: ResMut<EditHistory>,
+                  mut grid: ResMut<LifeGrid>,
+                  mut toggled: EventWriter<CellToggled>| {
+                paint_stroke(
+                    &board,
+                    (&mut history, &mut grid, &mut toggled),
+                    uvec2(2, 2),
+                    paint_alive,
+                    (BrushSize(0), Symmetry::None),
+                );
+            }
+        };
+        let idx = board.cell_coord_to_idx(uvec2(2, 2));
+
+        // erasing the cell painted earlier in the same stroke doesn't change it back
+        world.run_system_once(paint(true)).unwrap();
+        world.run_system_once(paint(false)).unwrap();
+        assert!(world.resource::<LifeGrid>().cells[idx]);
+        assert_eq!(1, world.resource::<Events<CellToggled>>().len());
+
+        world.run_system_once(end_edit_stroke).unwrap();
+        world.run_system_once(paint(false)).unwrap();
+        assert!(!world.resource::<LifeGrid>().cells[idx]);
+        assert_eq!(2, world.resource::<EditHistory>().undo.len());
+    }
+
+    #[test]
+    fn keyboard_cursor_stays_on_the_board() {
+        let mut world = World::new();
+        let mut board = Board {
+            width: 4,
+            height: 3,
+            wrap_x: false,
+            wrap_y: false,
+            ..default()
+        };
+        world.insert_resource(board);
+        world.init_resource::<KeyBindings>();
+        world.insert_resource(CursorCell(uvec2(0, 2)));
+        let press = |world: &mut World, key| {
+            let mut keyboard_input = ButtonInput::<KeyCode>::default();
+            keyboard_input.press(key);
+            world.insert_resource(keyboard_input);
+            world.run_system_once(move_cursor_cell).unwrap();
+            **world.resource::<CursorCell>()
+        };
+
+        assert_eq!(uvec2(1, 2), press(&mut world, KeyCode::ArrowRight));
+        assert_eq!(uvec2(1, 2), press(&mut world, KeyCode::ArrowUp));
+        board.wrap_x = true;
+        board.wrap_y = true;
+        world.insert_resource(board);
+        assert_eq!(uvec2(1, 0), press(&mut world, KeyCode::ArrowUp));
+        assert_eq!(uvec2(1, 2), press(&mut world, KeyCode::ArrowDown));
+    }
+
+    #[test]
+    fn seed_density_stays_within_bounds() {
+        let mut world = World::new();
+        world.init_resource::<KeyBindings>();
+        let press = |world: &mut World, density, action| {
+            world.insert_resource(SeedDensity(density));
+            let key = world.resource::<KeyBindings>().key(action).unwrap();
+            let mut keyboard_input = ButtonInput::<KeyCode>::default();
+            keyboard_input.press(key);
+            world.insert_resource(keyboard_input);
+            world.run_system_once(handle_seed_density_kbd).unwrap();
+            **world.resource::<SeedDensity>()
+        };
+
+        assert_eq!(40, press(&mut world, 50, Action::DecreaseDensity));
+        // a density from the config file below the step doesn't go up
+        assert_eq!(1, press(&mut world, 5, Action::DecreaseDensity));
+        assert_eq!(1, press(&mut world, 1, Action::DecreaseDensity));
+        assert_eq!(100, press(&mut world, 95, Action::IncreaseDensity));
+    }
+
+    #[test]
+    fn large_boards_are_spawned_in_chunks() {
+        let mut world = World::new();
+        let board = Board {
+            width: 10,
+            height: 10,
+            ..default()
+        };
+        world.insert_resource(MeshAndMats {
+            meshes: default(),
+            materials: default(),
+            age_materials: Vec::new(),
+            heat_materials: Vec::new(),
+            fade_materials: Vec::new(),
+            decay_materials: Vec::new(),
+        });
+        world.insert_resource(board);
+        world.insert_resource(ComparedRulesets(vec![Ruleset::default()]));
+        world.init_resource::<BorderWidth>();
+        world.init_resource::<ShowBorder>();
+        world.init_resource::<ShowWrapIndicator>();
+
+        let mut loading = start_board_loading(&mut world);
+        spawn_board_chunk(&mut world, &mut loading, 60);
+        assert!(!world.contains_resource::<CellEntities>());
+        assert_eq!(0.3, loading.progress());
+        // the chunk continues onto the compared board
+        spawn_board_chunk(&mut world, &mut loading, 60);
+        assert_eq!(100, world.resource::<CellEntities>().len());
+        let mut compared_query = world.query::<&ComparedBoard>();
+        assert_eq!(0, compared_query.iter(&world).count());
+        spawn_board_chunk(&mut world, &mut loading, 60);
+        assert!(!loading.is_done());
+        spawn_board_chunk(&mut world, &mut loading, 60);
+        assert!(loading.is_done());
+        assert_eq!(100, compared_query.single(&world).cell_entities.len());
+
+        let mut cell_query = world.query_filtered::<&CellIdx, With<Cell>>();
+        assert_eq!(200, cell_query.iter(&world).count());
+        let main_cells = &world.resource::<CellEntities>().0;
+        for (idx, &entity) in main_cells.iter().enumerate() {
+            assert_eq!(idx, world.get::<CellIdx>(entity).unwrap().0);
+        }
+        let mut border_query = world.query_filtered::<(), With<Border>>();
+        assert_eq!(8, border_query.iter(&world).count());
+    }
+
+    #[test]
+    fn board_is_collected_from_the_cells() {
+        let mut world = World::new();
+        let board = Board {
+            width: 4,
+            height: 3,
+            ..default()
+        };
+        world.insert_resource(board);
+        let live_cells = [uvec2(0, 0), uvec2(3, 0), uvec2(1, 2)];
+        let cell_entities = (0..board.cell_count())
+            .map(|idx| {
+                let cell_coord = board.idx_to_cell_coord(idx);
+                world
+                    .spawn((
+                        Cell,
+                        CellIdx(idx),
+                        CurrentAlive(live_cells.contains(&cell_coord)),
+                    ))
+                    .id()
+            })
+            .collect();
+        world.insert_resource(CellEntities(cell_entities));
+        // the cells of a compared board aren't collected, even though they are alive
+        let compared_entities = (0..board.cell_count())
+            .map(|idx| world.spawn((Cell, CellIdx(idx), CurrentAlive(true))).id())
+            .collect();
+        world.spawn(ComparedBoard {
+            board,
+            rules: Ruleset::default(),
+            grid: LifeGrid::new(board.cell_count()),
+            cell_entities: compared_entities,
+        });
+
+        let collected = world
+            .run_system_once(|cells: BoardCells, board: Res<Board>| collect_board(&cells, &board))
+            .unwrap();
+        let mut expected = vec![false; board.cell_count()];
+        for cell_coord in live_cells {
+            expected[board.cell_coord_to_idx(cell_coord)] = true;
+        }
+        assert_eq!(expected, collected);
+    }
+
+    #[test]
+    fn applied_boards_are_collected() {
+        let mut world = World::new();
+        let board = Board {
+            width: 5,
+            height: 4,
+            ..default()
+        };
+        world.insert_resource(board);
+        world.insert_resource(LifeGrid::new(board.cell_count()));
+        world.init_resource::<EditHistory>();
+        world.init_resource::<Generation>();
+        world.init_resource::<ShowFades>();
+        world.init_resource::<Time<Fixed>>();
+        let cell_entities = (0..board.cell_count())
+            .map(|idx| world.spawn((Cell, CellIdx(idx))).id())
+            .collect();
+        world.insert_resource(CellEntities(cell_entities));
+
+        let mut rng = fastrand::Rng::with_seed(3);
+        let cells = (0..board.cell_count())
+            .map(|_| rng.bool())
+            .collect::<Vec<_>>();
+        let apply = |cells: Vec<bool>| {
+            move |mut writer: BoardWriter, board: Res<Board>| {
+                apply_board(&cells, &mut writer, &board)
+            }
+        };
+        world
+            .run_system_once(apply(cells.clone()))
+            .unwrap()
+            .unwrap();
+        world.run_system_once(sync_cell_entities).unwrap();
+        let collected = world
+            .run_system_once(|cells: BoardCells, board: Res<Board>| collect_board(&cells, &board))
+            .unwrap();
+        assert_eq!(cells, collected);
+        assert_eq!(1, world.resource::<EditHistory>().undo.len());
+
+        // grids of another size are rejected without touching the board
+        assert_eq!(
+            Err(CellCountMismatch {
+                expected: 20,
+                found: 16
+            }),
+            world.run_system_once(apply(vec![true; 16])).unwrap()
+        );
+        assert_eq!(cells, world.resource::<LifeGrid>().cells);
+        assert_eq!(1, world.resource::<EditHistory>().undo.len());
+    }
+
+    #[test]
+    fn cells_are_layered_by_life_status() {
+        let mut world = World::new();
+        let board = Board::default();
+        let cell = world
+            .spawn((
+                Cell,
+                Transform::from_translation(board.cell_coord_to_translation(UVec2::ZERO)),
+            ))
+            .id();
+        world.init_resource::<LayerCells>();
+        let depth = |world: &mut World| {
+            world.run_system_once(update_cell_depth).unwrap();
+            world.get::<Transform>(cell).unwrap().translation.z
+        };
+        // the cells aren't layered by default
+        **world.get_mut::<CurrentAlive>(cell).unwrap() = true;
+        assert_eq!(CELL_DEAD_Z, depth(&mut world));
+
+        **world.resource_mut::<LayerCells>() = true;
+        assert_eq!(CELL_ALIVE_Z, depth(&mut world));
+        **world.get_mut::<CurrentAlive>(cell).unwrap() = false;
+        assert_eq!(CELL_DEAD_Z, depth(&mut world));
+
+        **world.get_mut::<CurrentAlive>(cell).unwrap() = true;
+        assert_eq!(CELL_ALIVE_Z, depth(&mut world));
+        **world.get_mut::<UnderCursor>(cell).unwrap() = true;
+        assert_eq!(CURSOR_CELL_Z, depth(&mut world));
+        **world.get_mut::<CurrentAlive>(cell).unwrap() = false;
+        assert_eq!(CURSOR_CELL_Z, depth(&mut world));
+        **world.get_mut::<UnderCursor>(cell).unwrap() = false;
+        assert_eq!(CELL_DEAD_Z, depth(&mut world));
+
+        // turning the layers off moves the cells back
+        **world.get_mut::<CurrentAlive>(cell).unwrap() = true;
+        assert_eq!(CELL_ALIVE_Z, depth(&mut world));
+        **world.resource_mut::<LayerCells>() = false;
+        assert_eq!(CELL_DEAD_Z, depth(&mut world));
+    }
+
+    #[test]
+    fn next_generation_is_previewed() {
+        let mut world = World::new();
+        let board = Board {
+            width: 5,
+            height: 5,
+            ..default()
+        };
+        let idx = |x, y| board.cell_coord_to_idx(uvec2(x, y));
+        let mut grid = LifeGrid::new(board.cell_count());
+        // a horizontal blinker
+        for x in 1..=3 {
+            grid.cells[idx(x, 2)] = true;
+        }
+        world.insert_resource(grid);
+        world.insert_resource(board);
+        world.init_resource::<Ruleset>();
+        world.insert_resource(ShowFuture(true));
+        world.insert_resource(State::new(GameState::Paused));
+        let entities = (0..board.cell_count())
+            .map(|_| world.spawn(Cell).id())
+            .collect::<Vec<_>>();
+        world.insert_resource(CellEntities(entities.clone()));
+        let future =
+            |world: &mut World, x, y| **world.get::<FutureAlive>(entities[idx(x, y)]).unwrap();
+
+        world.run_system_once(preview_future_life).unwrap();
+        assert_eq!(Some(false), future(&mut world, 1, 2));
+        assert_eq!(Some(true), future(&mut world, 2, 2));
+        assert_eq!(Some(true), future(&mut world, 2, 1));
+        assert_eq!(Some(false), future(&mut world, 0, 0));
+
+        // nothing is previewed while running
+        world.insert_resource(State::new(GameState::Running));
+        world.run_system_once(preview_future_life).unwrap();
+        assert_eq!(None, future(&mut world, 2, 1));
+    }
+
+    #[test]
+    fn dead_cells_can_be_hidden() {
+        let mut world = World::new();
+        world.init_resource::<HideDeadCells>();
+        world.init_resource::<ShowHeatmap>();
+        let cell = world.spawn((Cell, Visibility::default())).id();
+        let visibility = |world: &mut World| {
+            world.run_system_once(update_cell_visibility).unwrap();
+            *world.get::<Visibility>(cell).unwrap()
+        };
+        assert_eq!(Visibility::Inherited, visibility(&mut world));
+
+        **world.resource_mut::<HideDeadCells>() = true;
+        assert_eq!(Visibility::Hidden, visibility(&mut world));
+        // hovered cells are still shown
+        *world.get_mut::<CellInteraction>(cell).unwrap() = CellInteraction::Hovered;
+        assert_eq!(Visibility::Inherited, visibility(&mut world));
+        *world.get_mut::<CellInteraction>(cell).unwrap() = CellInteraction::Idle;
+        **world.get_mut::<CurrentAlive>(cell).unwrap() = true;
+        assert_eq!(Visibility::Inherited, visibility(&mut world));
+        **world.get_mut::<CurrentAlive>(cell).unwrap() = false;
+        assert_eq!(Visibility::Hidden, visibility(&mut world));
+        **world.resource_mut::<ShowHeatmap>() = true;
+        assert_eq!(Visibility::Inherited, visibility(&mut world));
+    }
+
+    #[test]
+    fn running_stops_at_population_limits() {
+        let mut world = World::new();
+        let mut grid = LifeGrid::new(4);
+        grid.cells = vec![true, true, true, false];
+        world.insert_resource(grid);
+        world.init_resource::<Generation>();
+        world.init_resource::<PopulationLimits>();
+        let stops = |world: &mut World| {
+            world.insert_resource(NextState::<GameState>::Unchanged);
+            world.run_system_once(stop_at_population_limits).unwrap();
+            matches!(
+                *world.resource::<NextState<GameState>>(),
+                NextState::Pending(GameState::Paused)
+            )
+        };
+        assert!(!stops(&mut world));
+
+        world.resource_mut::<PopulationLimits>().max_population = Some(2);
+        assert!(stops(&mut world));
+        world.resource_mut::<LifeGrid>().cells.fill(false);
+        assert!(stops(&mut world));
+        world.resource_mut::<PopulationLimits>().stop_on_extinction = false;
+        assert!(!stops(&mut world));
+    }
+
+    #[test]
+    fn tap_tempo_averages_recent_taps() {
+        let mut tempo = TapTempo::default();
+        assert_eq!(None, tempo.tap(10.0));
+        assert_eq!(Some(120.0), tempo.tap(10.5));
+        // 100 bpm, until the taps at 120 bpm have left the average
+        for beat in 1..TAP_TEMPO_TAPS {
+            assert!(tempo.tap(10.5 + beat as f64 * 0.6).unwrap() < 120.0);
+        }
+        assert_eq!(TAP_TEMPO_TAPS, tempo.len());
+        assert_eq!(100, tempo.tap(10.5 + 5.0 * 0.6).unwrap().round() as u32);
+
+        // a long pause starts over, too fast taps are clamped
+        assert_eq!(None, tempo.tap(20.0));
+        assert_eq!(1, tempo.len());
+        assert_eq!(Some(MAX_TAP_TEMPO_BPM), tempo.tap(20.05));
+    }
+
+    #[test]
+    fn population_tracks_changes() {
+        let mut world = World::new();
+        world.init_resource::<Population>();
+        world.init_resource::<Generation>();
+        world.init_resource::<Ruleset>();
+        let mut grid = LifeGrid::new(4);
+        grid.cells = vec![true, true, false, false];
+        grid.next = vec![false, true, true, true];
+        world.insert_resource(grid);
+        world.run_system_once(recount_population).unwrap();
+        assert_eq!(2, **world.resource::<Population>());
+
+        world.run_system_once(update_cell_current_life).unwrap();
+        world.run_system_once(recount_population).unwrap();
+        assert_eq!(3, **world.resource::<Population>());
+    }
+
+    #[test]
+    fn stamps_wrap_around_the_edges() {
+        let mut board = Board {
+            center: Vec2::ZERO,
+            cell_size: Vec2::splat(8.0),
+            cell_scale: Vec2::ONE,
+            width: 8,
+            height: 6,
+            wrap_x: true,
+            wrap_y: true,
+        };
+        let block = Pattern::from_cells(&(0..9).map(|i| uvec2(i % 3, i / 3)).collect::<Vec<_>>());
+        // the block is centered on (0, 0)
+        let mut grid = LifeGrid::new(board.cell_count());
+        stamp_pattern(&block, ivec2(-1, 1), &board, &mut grid);
+        assert_eq!(9, grid.population());
+        for corner in [uvec2(0, 0), uvec2(7, 0), uvec2(0, 5), uvec2(7, 5)] {
+            assert!(grid.cells[board.cell_coord_to_idx(corner)], "{corner}");
+        }
+
+        // only the quarter of the block on the board is stamped without wrapping
+        (board.wrap_x, board.wrap_y) = (false, false);
+        let mut grid = LifeGrid::new(board.cell_count());
+        stamp_pattern(&block, ivec2(-1, 1), &board, &mut grid);
+        assert_eq!(4, grid.population());
+        assert!(!grid.cells[board.cell_coord_to_idx(uvec2(7, 5))]);
+
+        // or only the half that wraps vertically
+        board.wrap_y = true;
+        let mut grid = LifeGrid::new(board.cell_count());
+        stamp_pattern(&block, ivec2(-1, 1), &board, &mut grid);
+        assert_eq!(6, grid.population());
+        assert!(grid.cells[board.cell_coord_to_idx(uvec2(0, 5))]);
+    }
+
+    #[test]
+    fn copied_regions_paste() {
+        let board = Board {
+            center: Vec2::ZERO,
+            cell_size: Vec2::splat(8.0),
+            cell_scale: Vec2::ONE,
+            width: 8,
+            height: 8,
+            wrap_x: false,
+            wrap_y: false,
+        };
+        let mut grid = LifeGrid::new(board.cell_count());
+        let glider = Pattern::from_cells(pattern::GLIDER);
+        stamp_pattern(&glider, ivec2(1, 5), &board, &mut grid);
+
+        // the stamped glider spans from (1, 3) to (3, 5)
+        let selection = CellSelection(Some((uvec2(3, 3), uvec2(1, 5))));
+        let (min, max) = selection.bounds().unwrap();
+        assert_eq!((uvec2(1, 3), uvec2(3, 5)), (min, max));
+        assert_eq!(glider, grid.copy_region(&board, min, max));
+
+        // pasting the copy elsewhere gives the same cells there
+        let copy = grid.copy_region(&board, min, max);
+        stamp_pattern(&copy, ivec2(4, 2), &board, &mut grid);
+        assert_eq!(glider, grid.copy_region(&board, uvec2(4, 0), uvec2(6, 2)));
+    }
+
+    #[test]
+    fn fixed_steps_are_capped_per_frame() {
+        let mut world = World::new();
+        world.insert_resource(MaxStepsPerFrame(3));
+        world.init_resource::<StepsThisFrame>();
+        world.init_resource::<Generation>();
+        let mut schedule = Schedule::new(GenerationStep);
+        schedule.add_systems(advance_generation);
+        world.add_schedule(schedule);
+
+        for _ in 0..5 {
+            world.run_system_once(run_fixed_generation_step).unwrap();
+        }
+        assert_eq!(3, **world.resource::<Generation>());
+        // the next frame steps again
+        world.run_system_once(reset_steps_this_frame).unwrap();
+        world.run_system_once(run_fixed_generation_step).unwrap();
+        assert_eq!(4, **world.resource::<Generation>());
+    }
+
+    #[test]
+    fn replayed_sessions_match_the_recording() {
+        let mut world = World::new();
+        let board = Board {
+            center: Vec2::ZERO,
+            cell_size: Vec2::splat(8.0),
+            cell_scale: Vec2::ONE,
+            width: 16,
+            height: 16,
+            wrap_x: true,
+            wrap_y: true,
+        };
+        world.insert_resource(board);
+        let mut grid = LifeGrid::new(board.cell_count());
+        grid.randomize(
+            &board,
+            RngSeed(7),
+            SeedDensity::default(),
+            SeedRegion::Board,
+        );
+        world.insert_resource(grid);
+        world.insert_resource(Ruleset::from_rulestring("B36/S23").unwrap());
+        world.init_resource::<Generation>();
+        world.init_resource::<EditHistory>();
+        world.init_resource::<NextState<GameState>>();
+        world.init_resource::<Noise>();
+        world.init_resource::<RngSeed>();
+        let mut schedule = Schedule::new(GenerationStep);
+        schedule.add_systems(
+            (
+                update_cell_future_life,
+                update_cell_current_life,
+                advance_generation,
+            )
+                .chain(),
+        );
+        world.add_schedule(schedule);
+
+        // record a short run
+        world.run_system_once(record_session).unwrap();
+        for _ in 0..12 {
+            world.run_schedule(GenerationStep);
+        }
+        let session = world
+            .resource::<SessionStart>()
+            .session_at(**world.resource::<Generation>());
+        let session = SessionSave::from_bytes(&session.to_bytes()).unwrap();
+        assert_eq!(12, session.generations);
+        let recorded = world.resource::<LifeGrid>().cells.clone();
+
+        // replay it on a board that has been changed since
+        world.resource_mut::<LifeGrid>().randomize(
+            &board,
+            RngSeed(8),
+            SeedDensity::default(),
+            SeedRegion::Board,
+        );
+        world.resource_mut::<Board>().wrap_x = false;
+        *world.resource_mut::<Ruleset>() = Ruleset::default();
+        world
+            .run_system_once_with(Some(session), start_replay)
+            .unwrap();
+        while !world.run_system_once(replay_finished).unwrap() {
+            world.run_schedule(GenerationStep);
+        }
+
+        assert_eq!(12, **world.resource::<Generation>());
+        assert_eq!(recorded, world.resource::<LifeGrid>().cells);
+    }
+
+    #[test]
+    fn stepping_back_restores_earlier_generations() {
+        let mut world = World::new();
+        let board = Board {
+            center: Vec2::ZERO,
+            cell_size: Vec2::splat(8.0),
+            cell_scale: Vec2::ONE,
+            width: 16,
+            height: 8,
+            wrap_x: true,
+            wrap_y: true,
+        };
+        world.insert_resource(board);
+        let mut grid = LifeGrid::new(board.cell_count());
+        grid.randomize(
+            &board,
+            RngSeed(3),
+            SeedDensity::default(),
+            SeedRegion::Board,
+        );
+        let start = grid.cells.clone();
+        world.insert_resource(grid);
+        world.init_resource::<Ruleset>();
+        world.init_resource::<Generation>();
+        world.init_resource::<GenerationSnapshots>();
+        world.init_resource::<EditHistory>();
+        world.init_resource::<PopulationHistory>();
+        world.init_resource::<StasisDetector>();
+        world.insert_resource(State::new(GameState::Paused));
+        // noisy generations are reproduced as well
+        world.insert_resource(Noise { flip_chance: 0.01 });
+        world.insert_resource(RngSeed(3));
+        let mut schedule = Schedule::new(GenerationStep);
+        schedule.add_systems(
+            (
+                record_generation_snapshot,
+                update_cell_future_life,
+                update_cell_current_life,
+                advance_generation,
+            )
+                .chain(),
+        );
+        world.add_schedule(schedule);
+
+        let mut generations = vec![start];
+        for _ in 0..GENERATION_HISTORY_LEN + 2 {
+            world.run_schedule(GenerationStep);
+            generations.push(world.resource::<LifeGrid>().cells.clone());
+        }
+        // only the most recent generations are kept
+        for generation in (2..=GENERATION_HISTORY_LEN + 1).rev() {
+            world.run_system_once(step_back).unwrap();
+            assert_eq!(generation as u64, **world.resource::<Generation>());
+            assert_eq!(generations[generation], world.resource::<LifeGrid>().cells);
+        }
+        world.run_system_once(step_back).unwrap();
+        assert_eq!(2, **world.resource::<Generation>());
+
+        // the restored generation steps like it did before
+        world.run_schedule(GenerationStep);
+        assert_eq!(generations[3], world.resource::<LifeGrid>().cells);
+    }
+
+    #[test]
+    fn gliders_are_found_in_every_phase() {
+        let board = Board {
+            center: Vec2::ZERO,
+            cell_size: Vec2::splat(8.0),
+            cell_scale: Vec2::ONE,
+            width: 24,
+            height: 16,
+            wrap_x: false,
+            wrap_y: false,
+        };
+        let mut grid = LifeGrid::new(board.cell_count());
+        let glider = Pattern::from_cells(pattern::GLIDER);
+        stamp_pattern(&glider, ivec2(2, 13), &board, &mut grid);
+        stamp_pattern(&glider.rotated(1), ivec2(14, 8), &board, &mut grid);
+        // a glider touching another cell isn't a glider
+        stamp_pattern(&glider.rotated(2), ivec2(14, 4), &board, &mut grid);
+        grid.cells[board.cell_coord_to_idx(uvec2(17, 2))] = true;
+
+        let find_gliders = |grid: &LifeGrid| {
+            let mut matches = pattern::glider_templates()
+                .iter()
+                .flat_map(|template| grid.find_pattern(&board, template))
+                .collect::<Vec<_>>();
+            matches.sort_by_key(|(min, _)| (min.x, min.y));
+            matches
+        };
+        assert_eq!(
+            vec![(uvec2(2, 11), uvec2(4, 13)), (uvec2(14, 6), uvec2(16, 8))],
+            find_gliders(&grid)
+        );
+
+        // every phase of the first glider is found, it moves a cell down and to the right every
+        // 4 generations
+        let rules = Ruleset::default();
+        for _ in 0..4 {
+            grid.compute_next(&board, &rules);
+            grid.swap();
+            assert!(find_gliders(&grid)
+                .iter()
+                .any(|(min, _)| { (2..=3).contains(&min.x) && (10..=11).contains(&min.y) }));
+        }
+        assert!(find_gliders(&grid).contains(&(uvec2(3, 10), uvec2(5, 12))));
+    }
+
+    #[test]
+    fn compared_boards_use_their_rules() {
+        let board = Board {
+            center: Vec2::ZERO,
+            cell_size: Vec2::splat(8.0),
+            cell_scale: Vec2::ONE,
+            width: 5,
+            height: 5,
+            wrap_x: false,
+            wrap_y: false,
+        };
+        let mut grid = LifeGrid::new(board.cell_count());
+        stamp_pattern(
+            &Pattern::from_cells(pattern::BLINKER),
+            ivec2(1, 2),
+            &board,
+            &mut grid,
+        );
+        let mut world = World::new();
+        world.init_resource::<Generation>();
+        let compared = |rules| ComparedBoard {
+            board,
+            rules,
+            grid: grid.clone(),
+            cell_entities: vec![],
+        };
+        let conway = world.spawn(compared(Ruleset::default())).id();
+        let lonely = world
+            .spawn(compared(Ruleset::from_rulestring("B/S").unwrap()))
+            .id();
+        world.run_system_once(step_compared_boards).unwrap();
+
+        let blinker_population = grid.cells.iter().filter(|alive| **alive).count();
+        let conway_grid = &world.get::<ComparedBoard>(conway).unwrap().grid;
+        assert_eq!(blinker_population, conway_grid.population() as usize);
+        assert_ne!(grid.cells, conway_grid.cells);
+        assert_eq!(
+            0,
+            world
+                .get::<ComparedBoard>(lonely)
+                .unwrap()
+                .grid
+                .population()
+        );
+    }
+
+    #[test]
+    fn focus_loss_pauses() {
+        let mut pause = PauseOnFocusLoss::default();
+        assert_eq!(None, pause.focus_changed(false, GameState::Running));
+
+        pause.enabled = true;
+        assert_eq!(
+            Some(GameState::Paused),
+            pause.focus_changed(false, GameState::Running)
+        );
+        assert_eq!(
+            Some(GameState::Running),
+            pause.focus_changed(true, GameState::Paused)
+        );
+
+        // a board that isn't running stays as it is
+        assert_eq!(None, pause.focus_changed(false, GameState::Setup));
+        assert_eq!(None, pause.focus_changed(true, GameState::Setup));
+
+        pause.resume_on_focus = false;
+        assert_eq!(
+            Some(GameState::Paused),
+            pause.focus_changed(false, GameState::Running)
+        );
+        assert_eq!(None, pause.focus_changed(true, GameState::Paused));
+    }
+
+    #[test]
+    fn same_seed_gives_same_soup() {
+        let board = Board {
+            width: 32,
+            height: 32,
+            ..default()
+        };
+        let randomized = |seed, density, region| {
+            let mut grid = LifeGrid::new(board.cell_count());
+            grid.randomize(&board, RngSeed(seed), SeedDensity(density), region);
+            grid.cells
+        };
+        let soup = randomized(42, 50, SeedRegion::Board);
+        assert_eq!(soup, randomized(42, 50, SeedRegion::Board));
+        assert_ne!(soup, randomized(43, 50, SeedRegion::Board));
+
+        // only the cells in the region are seeded
+        let (min, max) = (uvec2(4, 8), uvec2(11, 15));
+        let rect = randomized(42, 100, SeedRegion::Rect(min, max));
+        assert_eq!(64, rect.iter().filter(|alive| **alive).count());
+        assert!(rect[board.cell_coord_to_idx(min)] && rect[board.cell_coord_to_idx(max)]);
+        let ellipse = randomized(42, 100, SeedRegion::Ellipse(min, max));
+        let population = ellipse.iter().filter(|alive| **alive).count();
+        assert!((40..64).contains(&population));
+        assert!(!ellipse[board.cell_coord_to_idx(min)]);
+        assert!(ellipse[board.cell_coord_to_idx(uvec2(7, 8))]);
+        assert!(ellipse
+            .iter()
+            .zip(&rect)
+            .all(|(&in_ellipse, &in_rect)| in_rect || !in_ellipse));
+
+        let sparse = randomized(42, 10, SeedRegion::Board);
+        let dense = randomized(42, 90, SeedRegion::Board);
+        assert!(
+            sparse.iter().filter(|alive| **alive).count()
+                < dense.iter().filter(|alive| **alive).count()
+        );
     }
 }
@@ -1,30 +1,41 @@
-use bevy::{
-    diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
-    prelude::*,
-    window::WindowResolution,
+use bevy::prelude::*;
+use conway_gol_bevy::{
+    camera::CamPlugin,
+    config::{self, Config, ConfigFile, CONFIG_FILE_PATH},
+    diagnostics::DiagnosticsOverlayPlugin,
+    life::LifePlugin,
+    state::GameState,
 };
-use conway_gol_bevy::{camera::CamPlugin, life::LifePlugin, state::GameState};
 
 fn main() {
-    App::new()
-        .add_plugins(
-            DefaultPlugins
-                .set(ImagePlugin::default_nearest())
-                .set(WindowPlugin {
-                    primary_window: Some(Window {
-                        resizable: true,
-                        focused: true,
-                        present_mode: bevy::window::PresentMode::AutoNoVsync,
-                        mode: bevy::window::WindowMode::Windowed,
-                        resolution: WindowResolution::new(1000., 1000.),
-                        ..default()
-                    }),
-                    ..default()
-                }),
-        )
-        .add_plugins(MeshPickingPlugin)
-        .add_plugins((FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin::default()))
+    // the arguments override the config file
+    let config = ConfigFile::read(CONFIG_FILE_PATH)
+        .and_then(|file| file.unwrap_or_default().apply(Config::default()))
+        .and_then(|config| config.with_args(std::env::args().skip(1)));
+    let config = match config {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("error: {e}\n{}", config::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let mut app = App::new();
+    app.add_plugins(
+        DefaultPlugins
+            .set(ImagePlugin::default_nearest())
+            .set(WindowPlugin {
+                primary_window: Some(config.primary_window()),
+                ..default()
+            }),
+    );
+    // the cells are picked from the cursor's position instead, see `Config::cursor_picking`
+    if !config.cursor_picking {
+        app.add_plugins(MeshPickingPlugin);
+    }
+    app.add_plugins(DiagnosticsOverlayPlugin)
         .init_state::<GameState>()
-        .add_plugins((CamPlugin, LifePlugin))
+        .insert_resource(config)
+        .add_plugins((CamPlugin, LifePlugin::new()))
         .run();
 }
@@ -0,0 +1,568 @@
+use std::{error::Error, fmt};
+
+use bevy::math::{uvec2, UVec2};
+
+use crate::prelude::MAX_BOARD_SIZE;
+
+/// A pattern of live cells.
+///
+/// Coordinates are relative to the top left corner of the pattern, with rows going downwards
+/// like in pattern files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    pub width: u32,
+    pub height: u32,
+    pub live_cells: Vec<UVec2>,
+}
+
+impl Pattern {
+    /// creates a pattern just big enough to fit all of the live cells
+    pub fn from_cells(live_cells: &[UVec2]) -> Pattern {
+        let size = live_cells
+            .iter()
+            .fold(UVec2::ZERO, |size, cell| size.max(*cell + UVec2::ONE));
+        Pattern {
+            width: size.x,
+            height: size.y,
+            live_cells: live_cells.to_vec(),
+        }
+    }
+
+    /// rotates the pattern clockwise by `quarter_turns` * 90°
+    pub fn rotated(&self, quarter_turns: u8) -> Pattern {
+        let mut pattern = self.clone();
+        for _ in 0..quarter_turns % 4 {
+            pattern = Pattern {
+                width: pattern.height,
+                height: pattern.width,
+                live_cells: pattern
+                    .live_cells
+                    .iter()
+                    .map(|cell| uvec2(pattern.height - 1 - cell.y, cell.x))
+                    .collect(),
+            };
+        }
+        pattern
+    }
+
+    /// mirrors the pattern along the given axis
+    pub fn flipped(&self, flip: Flip) -> Pattern {
+        let live_cells = self
+            .live_cells
+            .iter()
+            .map(|cell| match flip {
+                Flip::Horizontal => uvec2(self.width - 1 - cell.x, cell.y),
+                Flip::Vertical => uvec2(cell.x, self.height - 1 - cell.y),
+            })
+            .collect();
+        Pattern {
+            live_cells,
+            ..*self
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flip {
+    /// swaps left and right
+    Horizontal,
+    /// swaps top and bottom
+    Vertical,
+}
+
+// ——> LIBRARY
+
+pub const GLIDER: &[UVec2] = &[
+    uvec2(1, 0),
+    uvec2(2, 1),
+    uvec2(0, 2),
+    uvec2(1, 2),
+    uvec2(2, 2),
+];
+
+/// the 4 phases of a glider moving down and to the right, `GLIDER` first
+pub const GLIDER_PHASES: [&[UVec2]; 4] = [
+    GLIDER,
+    &[
+        uvec2(0, 0),
+        uvec2(2, 0),
+        uvec2(1, 1),
+        uvec2(2, 1),
+        uvec2(1, 2),
+    ],
+    &[
+        uvec2(2, 0),
+        uvec2(0, 1),
+        uvec2(2, 1),
+        uvec2(1, 2),
+        uvec2(2, 2),
+    ],
+    &[
+        uvec2(0, 0),
+        uvec2(1, 1),
+        uvec2(2, 1),
+        uvec2(0, 2),
+        uvec2(1, 2),
+    ],
+];
+
+pub const BLINKER: &[UVec2] = &[uvec2(0, 0), uvec2(1, 0), uvec2(2, 0)];
+
+/// lightweight spaceship
+pub const LWSS: &[UVec2] = &[
+    uvec2(1, 0),
+    uvec2(4, 0),
+    uvec2(0, 1),
+    uvec2(0, 2),
+    uvec2(4, 2),
+    uvec2(0, 3),
+    uvec2(1, 3),
+    uvec2(2, 3),
+    uvec2(3, 3),
+];
+
+pub const GOSPER_GLIDER_GUN: &[UVec2] = &[
+    uvec2(24, 0),
+    uvec2(22, 1),
+    uvec2(24, 1),
+    uvec2(12, 2),
+    uvec2(13, 2),
+    uvec2(20, 2),
+    uvec2(21, 2),
+    uvec2(34, 2),
+    uvec2(35, 2),
+    uvec2(11, 3),
+    uvec2(15, 3),
+    uvec2(20, 3),
+    uvec2(21, 3),
+    uvec2(34, 3),
+    uvec2(35, 3),
+    uvec2(0, 4),
+    uvec2(1, 4),
+    uvec2(10, 4),
+    uvec2(16, 4),
+    uvec2(20, 4),
+    uvec2(21, 4),
+    uvec2(0, 5),
+    uvec2(1, 5),
+    uvec2(10, 5),
+    uvec2(14, 5),
+    uvec2(16, 5),
+    uvec2(17, 5),
+    uvec2(22, 5),
+    uvec2(24, 5),
+    uvec2(10, 6),
+    uvec2(16, 6),
+    uvec2(24, 6),
+    uvec2(11, 7),
+    uvec2(15, 7),
+    uvec2(12, 8),
+    uvec2(13, 8),
+];
+
+/// built-in patterns that can be stamped onto the board
+pub const LIBRARY: [(&str, &[UVec2]); 4] = [
+    ("glider", GLIDER),
+    ("blinker", BLINKER),
+    ("lightweight spaceship", LWSS),
+    ("gosper glider gun", GOSPER_GLIDER_GUN),
+];
+
+/// The built-in pattern with this name, ignoring case and allowing `_` or `-` in place of
+/// spaces, e.g. `gosper_glider_gun`.
+pub fn library_pattern(name: &str) -> Option<Pattern> {
+    let name = name.to_lowercase().replace(['_', '-'], " ");
+    LIBRARY
+        .iter()
+        .find(|(library_name, _)| *library_name == name)
+        .map(|(_, cells)| Pattern::from_cells(cells))
+}
+
+/// Every distinct phase of a glider moving in each of the 4 diagonal directions.
+///
+/// Mirrored gliders are included for completeness, though they only repeat other phases.
+pub fn glider_templates() -> Vec<Pattern> {
+    let mut templates: Vec<Pattern> = Vec::new();
+    for phase in GLIDER_PHASES {
+        let phase = Pattern::from_cells(phase);
+        for mirrored in [phase.clone(), phase.flipped(Flip::Horizontal)] {
+            for quarter_turns in 0..4 {
+                let mut template = mirrored.rotated(quarter_turns);
+                template.live_cells.sort_by_key(|cell| (cell.y, cell.x));
+                if !templates.contains(&template) {
+                    templates.push(template);
+                }
+            }
+        }
+    }
+    templates
+}
+
+/// Parses a pattern in the RLE format.
+///
+/// `#` comment lines are skipped, the `x = .., y = ..` header is required, any other header
+/// entries (like `rule`) are ignored.
+pub fn parse_rle(rle: &str) -> Result<Pattern, RleError> {
+    let mut lines = rle
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+    let header = lines
+        .next()
+        .ok_or_else(|| RleError::InvalidHeader(String::new()))?;
+    let (width, height) = parse_rle_header(header)?;
+
+    let mut live_cells = Vec::new();
+    let mut pos = UVec2::ZERO;
+    let mut run_count: Option<u32> = None;
+    for c in lines.flat_map(str::chars) {
+        match c {
+            '0'..='9' => {
+                let digit = c.to_digit(10).unwrap_or_default();
+                run_count = run_count
+                    .unwrap_or(0)
+                    .checked_mul(10)
+                    .and_then(|count| count.checked_add(digit));
+                if run_count.is_none() {
+                    return Err(RleError::Overflow);
+                }
+                continue;
+            }
+            // dead cells
+            'b' | '.' => {
+                pos.x = pos
+                    .x
+                    .checked_add(run_count.unwrap_or(1))
+                    .ok_or(RleError::Overflow)?;
+            }
+            // end of row(s)
+            '$' => {
+                let y = pos
+                    .y
+                    .checked_add(run_count.unwrap_or(1))
+                    .ok_or(RleError::Overflow)?;
+                pos = uvec2(0, y);
+            }
+            '!' => break,
+            c if c.is_whitespace() => continue,
+            // any other state counts as alive
+            c if c.is_ascii_alphabetic() => {
+                let end = pos.x.saturating_add(run_count.unwrap_or(1));
+                // the first cell of the run that doesn't fit is reported
+                if pos.y >= height {
+                    return Err(RleError::OutOfBounds(pos));
+                }
+                if end > width {
+                    return Err(RleError::OutOfBounds(uvec2(pos.x.max(width), pos.y)));
+                }
+                live_cells.extend((pos.x..end).map(|x| uvec2(x, pos.y)));
+                pos.x = end;
+            }
+            c => return Err(RleError::InvalidChar(c)),
+        }
+        run_count = None;
+    }
+
+    Ok(Pattern {
+        width,
+        height,
+        live_cells,
+    })
+}
+
+fn parse_rle_header(header: &str) -> Result<(u32, u32), RleError> {
+    let invalid = || RleError::InvalidHeader(header.to_owned());
+
+    let (mut width, mut height) = (None, None);
+    for entry in header.split(',') {
+        let (key, value) = entry.split_once('=').ok_or_else(invalid)?;
+        let target = match key.trim() {
+            "x" => &mut width,
+            "y" => &mut height,
+            _ => continue,
+        };
+        *target = Some(value.trim().parse::<u32>().map_err(|_| invalid())?);
+    }
+
+    let (width, height) = width.zip(height).ok_or_else(invalid)?;
+    // no board is larger, so the pattern couldn't be stamped anyway
+    if width > MAX_BOARD_SIZE || height > MAX_BOARD_SIZE {
+        return Err(RleError::TooLarge(uvec2(width, height)));
+    }
+    Ok((width, height))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RleError {
+    /// the `x = .., y = ..` header line is missing or malformed
+    InvalidHeader(String),
+    /// an unexpected character in the pattern data
+    InvalidChar(char),
+    /// a live cell lies outside of the size given in the header
+    OutOfBounds(UVec2),
+    /// a run of cells reaches past the largest coordinate
+    Overflow,
+    /// the size given in the header is larger than `MAX_BOARD_SIZE`
+    TooLarge(UVec2),
+}
+
+impl fmt::Display for RleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RleError::InvalidHeader(header) => write!(f, "invalid RLE header '{header}'"),
+            RleError::InvalidChar(c) => write!(f, "unexpected character '{c}' in RLE data"),
+            RleError::OutOfBounds(pos) => {
+                write!(f, "live cell {pos} lies outside of the pattern's size")
+            }
+            RleError::Overflow => write!(f, "a run of cells in the RLE data is too long"),
+            RleError::TooLarge(size) => write!(
+                f,
+                "the pattern's size {}x{} is larger than the largest board",
+                size.x, size.y
+            ),
+        }
+    }
+}
+
+impl Error for RleError {}
+
+/// Parses a pattern in the plaintext (`.cells`) format.
+///
+/// Lines starting with `!` are comments, every other line is a row of `.` for dead cells and
+/// `O` for live ones. Rows can be shorter than the widest one, the rest of their cells are dead.
+pub fn parse_plaintext(plaintext: &str) -> Result<Pattern, PlaintextError> {
+    let mut rows = plaintext
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.starts_with('!'))
+        .collect::<Vec<_>>();
+    // a file ending in blank lines doesn't end in rows of dead cells
+    while rows.last().is_some_and(|row| row.is_empty()) {
+        rows.pop();
+    }
+
+    let mut live_cells = Vec::new();
+    let mut width = 0;
+    for (y, row) in rows.iter().enumerate() {
+        for (x, c) in row.chars().enumerate() {
+            match c {
+                '.' => {}
+                // `*` is a common alternative for live cells
+                'O' | 'o' | '*' => live_cells.push(uvec2(x as u32, y as u32)),
+                c => return Err(PlaintextError::InvalidChar(c)),
+            }
+        }
+        width = width.max(row.chars().count() as u32);
+    }
+
+    Ok(Pattern {
+        width,
+        height: rows.len() as u32,
+        live_cells,
+    })
+}
+
+/// writes the pattern in the plaintext (`.cells`) format, see `parse_plaintext`
+pub fn to_plaintext(pattern: &Pattern) -> String {
+    let mut rows = vec![vec!['.'; pattern.width as usize]; pattern.height as usize];
+    for cell in &pattern.live_cells {
+        if let Some(c) = rows
+            .get_mut(cell.y as usize)
+            .and_then(|row| row.get_mut(cell.x as usize))
+        {
+            *c = 'O';
+        }
+    }
+    rows.into_iter()
+        .map(|row| row.into_iter().chain(['\n']).collect::<String>())
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlaintextError {
+    /// a character other than `.` and `O` in a row
+    InvalidChar(char),
+}
+
+impl fmt::Display for PlaintextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlaintextError::InvalidChar(c) => {
+                write!(f, "unexpected character '{c}' in plaintext pattern")
+            }
+        }
+    }
+}
+
+impl Error for PlaintextError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn library_patterns_are_found_by_name() {
+        assert_eq!(
+            Some(Pattern::from_cells(GOSPER_GLIDER_GUN)),
+            library_pattern("Gosper_Glider-gun")
+        );
+        assert_eq!(Some(Pattern::from_cells(GLIDER)), library_pattern("glider"));
+        assert_eq!(None, library_pattern("gosper_gun"));
+    }
+
+    #[test]
+    fn glider_templates_are_distinct() {
+        // 4 phases in each of the 4 directions
+        let templates = glider_templates();
+        assert_eq!(16, templates.len());
+        assert!(templates.iter().all(|template| (
+            template.width,
+            template.height,
+            template.live_cells.len()
+        ) == (3, 3, 5)));
+    }
+
+    #[test]
+    fn rle_parses() {
+        let glider =
+            parse_rle("#N Glider\n#C a comment\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!").unwrap();
+        assert_eq!(3, glider.width);
+        assert_eq!(3, glider.height);
+        assert_eq!(
+            vec![
+                uvec2(1, 0),
+                uvec2(2, 1),
+                uvec2(0, 2),
+                uvec2(1, 2),
+                uvec2(2, 2)
+            ],
+            glider.live_cells
+        );
+
+        assert_eq!(Pattern::from_cells(GLIDER), glider);
+        assert_eq!(
+            Pattern::from_cells(GOSPER_GLIDER_GUN),
+            parse_rle(
+                "x = 36, y = 9\n24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$\
+                 2o8bo3bob2o4bobo$10bo5bo7bo$11bo3bo$12b2o!"
+            )
+            .unwrap()
+        );
+
+        // runs continuing on the next line, blank rows and text after the terminator
+        let pattern = parse_rle("x = 12, y = 4\n2o10b2$b\n11o! ignored").unwrap();
+        let expected = [uvec2(0, 0), uvec2(1, 0)]
+            .into_iter()
+            .chain((1..12).map(|x| uvec2(x, 2)))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, pattern.live_cells);
+    }
+
+    #[test]
+    fn plaintext_round_trips() {
+        let glider = Pattern::from_cells(GLIDER);
+        let plaintext = to_plaintext(&glider);
+        assert_eq!(".O.\n..O\nOOO\n", plaintext);
+        let mut parsed = parse_plaintext(&plaintext).unwrap();
+        parsed.live_cells.sort_by_key(|cell| (cell.y, cell.x));
+        let mut expected = glider.clone();
+        expected.live_cells.sort_by_key(|cell| (cell.y, cell.x));
+        assert_eq!(expected, parsed);
+
+        // comments, short rows, a blank row and trailing blank lines
+        let commented = "!Name: Two blinkers\r\n!a comment\n\nOOO\n\n..O..\n..O\n..O\n\n";
+        let pattern = parse_plaintext(commented).unwrap();
+        assert_eq!((5, 6), (pattern.width, pattern.height));
+        assert_eq!(
+            vec![
+                uvec2(0, 1),
+                uvec2(1, 1),
+                uvec2(2, 1),
+                uvec2(2, 3),
+                uvec2(2, 4),
+                uvec2(2, 5)
+            ],
+            pattern.live_cells
+        );
+        assert_eq!(pattern, parse_plaintext(&to_plaintext(&pattern)).unwrap());
+
+        assert_eq!(
+            Err(PlaintextError::InvalidChar('x')),
+            parse_plaintext(".O.\n.xO")
+        );
+    }
+
+    #[test]
+    fn patterns_rotate_and_flip() {
+        // o.
+        // o.
+        // oo
+        let l_shape = Pattern::from_cells(&[uvec2(0, 0), uvec2(0, 1), uvec2(0, 2), uvec2(1, 2)]);
+        let sorted = |mut pattern: Pattern| {
+            pattern.live_cells.sort_by_key(|cell| (cell.y, cell.x));
+            pattern
+        };
+
+        // ooo
+        // o..
+        let rotated = l_shape.rotated(1);
+        assert_eq!((3, 2), (rotated.width, rotated.height));
+        assert_eq!(
+            vec![uvec2(0, 0), uvec2(1, 0), uvec2(2, 0), uvec2(0, 1)],
+            sorted(rotated).live_cells
+        );
+        assert_eq!(sorted(l_shape.clone()), sorted(l_shape.rotated(4)));
+
+        // .o
+        // .o
+        // oo
+        assert_eq!(
+            vec![uvec2(1, 0), uvec2(1, 1), uvec2(0, 2), uvec2(1, 2)],
+            sorted(l_shape.flipped(Flip::Horizontal)).live_cells
+        );
+        // oo
+        // o.
+        // o.
+        assert_eq!(
+            vec![uvec2(0, 0), uvec2(1, 0), uvec2(0, 1), uvec2(0, 2)],
+            sorted(l_shape.flipped(Flip::Vertical)).live_cells
+        );
+        assert_eq!(
+            sorted(l_shape.rotated(2)),
+            sorted(l_shape.flipped(Flip::Horizontal).flipped(Flip::Vertical))
+        );
+    }
+
+    #[test]
+    fn malformed_rle_fails() {
+        assert_eq!(
+            Err(RleError::InvalidHeader("bo$2bo$3o!".to_owned())),
+            parse_rle("bo$2bo$3o!")
+        );
+        assert_eq!(
+            Err(RleError::InvalidHeader("x = 3".to_owned())),
+            parse_rle("x = 3\nbo$2bo$3o!")
+        );
+        assert_eq!(
+            Err(RleError::InvalidChar('?')),
+            parse_rle("x = 3, y = 3\nbo$2?o$3o!")
+        );
+        assert_eq!(
+            Err(RleError::OutOfBounds(uvec2(3, 2))),
+            parse_rle("x = 3, y = 3\nbo$2bo$4o!")
+        );
+        assert_eq!(
+            Err(RleError::Overflow),
+            parse_rle("x = 3, y = 3\n4294967295b2bo!")
+        );
+        assert_eq!(
+            Err(RleError::Overflow),
+            parse_rle("x = 3, y = 3\n99999999999b!")
+        );
+        assert_eq!(
+            Err(RleError::TooLarge(uvec2(4_000_000_000, 1))),
+            parse_rle("x = 4000000000, y = 1\n4000000000o!")
+        );
+    }
+}
@@ -0,0 +1,418 @@
+use std::{error::Error, fmt};
+
+use bevy::{
+    math::{ivec2, IVec2},
+    prelude::*,
+};
+
+/// Life-like cellular automaton rules in B/S notation, or Generations rules with a third part
+/// for the amount of cell states.
+///
+/// Both arrays are indexed by the amount of live neighbours (0 - 8), counts above the size of
+/// the neighbourhood never occur.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ruleset {
+    /// a dead cell with this many live neighbours is born
+    pub birth: [bool; 9],
+    /// a live cell with this many live neighbours survives
+    pub survive: [bool; 9],
+    pub neighbourhood: Neighbourhood,
+    /// The amount of states a cell can be in, 2 (dead and alive) for life-like rules.
+    ///
+    /// Live cells that don't survive pass through the `states - 2` decaying states, one per
+    /// generation, before they are dead. Decaying cells aren't live neighbours and can't be born.
+    pub states: u8,
+}
+
+impl Ruleset {
+    /// the named rules that can be cycled through at runtime
+    pub const PRESETS: [(&'static str, Ruleset); 5] = [
+        ("Conway", Ruleset::life_like(&[3], &[2, 3])),
+        ("HighLife", Ruleset::life_like(&[3, 6], &[2, 3])),
+        (
+            "Day & Night",
+            Ruleset::life_like(&[3, 6, 7, 8], &[3, 4, 6, 7, 8]),
+        ),
+        ("Seeds", Ruleset::life_like(&[2], &[])),
+        (
+            "Replicator",
+            Ruleset::life_like(&[1, 3, 5, 7], &[1, 3, 5, 7]),
+        ),
+    ];
+
+    /// Rules of the Moore neighbourhood with these birth and survival neighbour counts.
+    ///
+    /// # Panics
+    ///
+    /// if a count is above 8
+    pub const fn life_like(birth_counts: &[usize], survive_counts: &[usize]) -> Ruleset {
+        let mut birth = [false; 9];
+        let mut survive = [false; 9];
+        let mut i = 0;
+        while i < birth_counts.len() {
+            birth[birth_counts[i]] = true;
+            i += 1;
+        }
+        i = 0;
+        while i < survive_counts.len() {
+            survive[survive_counts[i]] = true;
+            i += 1;
+        }
+        Ruleset {
+            birth,
+            survive,
+            neighbourhood: Neighbourhood::Moore,
+            states: 2,
+        }
+    }
+
+    /// the name of the preset with these rules, if there is one
+    pub fn preset_name(&self) -> Option<&'static str> {
+        Ruleset::PRESETS
+            .iter()
+            .find(|(_, preset)| preset == self)
+            .map(|&(name, _)| name)
+    }
+
+    /// the preset following these rules, or the first one for other rules
+    pub fn next_preset(&self) -> (&'static str, Ruleset) {
+        let idx = Ruleset::PRESETS
+            .iter()
+            .position(|(_, preset)| preset == self)
+            .map_or(0, |idx| (idx + 1) % Ruleset::PRESETS.len());
+        Ruleset::PRESETS[idx]
+    }
+
+    /// Parses a standard rulestring like `"B3/S23"` (Conway) or `"B36/S23"` (HighLife).
+    ///
+    /// The `B` and `S` parts may come in any order and are case insensitive, either
+    /// of them can be empty (e.g. `"B2/S"` for Seeds). A trailing `V` selects the von Neumann
+    /// neighbourhood (e.g. `"B2/S013V"`), like in Golly. A third part sets the amount of states
+    /// of Generations rules, with an optional `C` (e.g. `"B2/S/3"` or `"B2/S/C3"` for Brian's
+    /// Brain).
+    pub fn from_rulestring(rulestring: &str) -> Result<Ruleset, RuleParseError> {
+        let trimmed = rulestring.trim();
+        let (trimmed, neighbourhood) = match trimmed.strip_suffix(['V', 'v']) {
+            Some(trimmed) => (trimmed, Neighbourhood::VonNeumann),
+            None => (trimmed, Neighbourhood::Moore),
+        };
+        let mut parts = trimmed.split('/');
+        let (Some(first), Some(second), third, None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(RuleParseError::Malformed(rulestring.to_owned()));
+        };
+        let states = match third {
+            Some(third) => parse_states(third)?,
+            None => 2,
+        };
+
+        let mut birth = None;
+        let mut survive = None;
+        for part in [first, second] {
+            let mut chars = part.trim().chars();
+            let target = match chars.next().map(|c| c.to_ascii_uppercase()) {
+                Some('B') => &mut birth,
+                Some('S') => &mut survive,
+                _ => return Err(RuleParseError::Malformed(rulestring.to_owned())),
+            };
+            if target.is_some() {
+                return Err(RuleParseError::Malformed(rulestring.to_owned()));
+            }
+            *target = Some(parse_counts(chars, neighbourhood)?);
+        }
+
+        match (birth, survive) {
+            (Some(birth), Some(survive)) => Ok(Ruleset {
+                birth,
+                survive,
+                neighbourhood,
+                states,
+            }),
+            _ => Err(RuleParseError::Malformed(rulestring.to_owned())),
+        }
+    }
+}
+
+/// Formats the rules as the rulestring `Ruleset::from_rulestring` parses, like `B36/S23`.
+impl fmt::Display for Ruleset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = |counts: [bool; 9]| {
+            (0..9)
+                .filter(|&n| counts[n as usize])
+                .filter_map(|n| char::from_digit(n, 10))
+                .collect::<String>()
+        };
+        write!(f, "B{}/S{}", digits(self.birth), digits(self.survive))?;
+        if self.states > 2 {
+            write!(f, "/{}", self.states)?;
+        }
+        if self.neighbourhood == Neighbourhood::VonNeumann {
+            write!(f, "V")?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_counts(
+    chars: impl Iterator<Item = char>,
+    neighbourhood: Neighbourhood,
+) -> Result<[bool; 9], RuleParseError> {
+    let mut counts = [false; 9];
+    for c in chars {
+        match c.to_digit(10) {
+            Some(n) if n as usize <= neighbourhood.size() => counts[n as usize] = true,
+            _ => return Err(RuleParseError::InvalidCount(c)),
+        }
+    }
+    Ok(counts)
+}
+
+/// parses the amount of states of Generations rules, like `3` or `C3`
+fn parse_states(part: &str) -> Result<u8, RuleParseError> {
+    let part = part.trim();
+    let digits = part.strip_prefix(['C', 'c']).unwrap_or(part);
+    digits
+        .parse()
+        .ok()
+        .filter(|&states| states >= 2)
+        .ok_or_else(|| RuleParseError::InvalidStates(part.to_owned()))
+}
+
+/// The cells that count as the neighbours of a cell.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Neighbourhood {
+    /// the 8 surrounding cells
+    #[default]
+    Moore,
+    /// the 4 orthogonally adjacent cells
+    VonNeumann,
+}
+
+impl Neighbourhood {
+    /// the offsets of all 8 surrounding cells, row by row starting at the bottom left
+    pub const MOORE_OFFSETS: [IVec2; 8] = [
+        ivec2(-1, -1),
+        ivec2(0, -1),
+        ivec2(1, -1),
+        ivec2(-1, 0),
+        ivec2(1, 0),
+        ivec2(-1, 1),
+        ivec2(0, 1),
+        ivec2(1, 1),
+    ];
+
+    /// the amount of neighbours
+    pub fn size(self) -> usize {
+        match self {
+            Neighbourhood::Moore => 8,
+            Neighbourhood::VonNeumann => 4,
+        }
+    }
+
+    /// whether the cell at the offset from one of `MOORE_OFFSETS` is a neighbour
+    pub fn includes(self, offset: IVec2) -> bool {
+        match self {
+            Neighbourhood::Moore => true,
+            Neighbourhood::VonNeumann => offset.x == 0 || offset.y == 0,
+        }
+    }
+
+    /// the offsets of the neighbours
+    pub fn offsets(self) -> impl Iterator<Item = IVec2> {
+        Neighbourhood::MOORE_OFFSETS
+            .into_iter()
+            .filter(move |&offset| self.includes(offset))
+    }
+
+    pub fn toggled(self) -> Neighbourhood {
+        match self {
+            Neighbourhood::Moore => Neighbourhood::VonNeumann,
+            Neighbourhood::VonNeumann => Neighbourhood::Moore,
+        }
+    }
+}
+
+impl Default for Ruleset {
+    /// Conway's Game of Life (B3/S23)
+    fn default() -> Self {
+        Ruleset::life_like(&[3], &[2, 3])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleParseError {
+    /// the rulestring isn't of the form `B<counts>/S<counts>`
+    Malformed(String),
+    /// a neighbour count that isn't a digit between 0 and the size of the neighbourhood
+    InvalidCount(char),
+    /// an amount of states that isn't a number between 2 and 255
+    InvalidStates(String),
+}
+
+impl fmt::Display for RuleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleParseError::Malformed(rulestring) => {
+                write!(
+                    f,
+                    "malformed rulestring '{rulestring}', expected e.g. 'B3/S23'"
+                )
+            }
+            RuleParseError::InvalidCount(c) => {
+                write!(
+                    f,
+                    "invalid neighbour count '{c}', expected a digit from 0 to 8 (4 for von \
+                     Neumann rules)"
+                )
+            }
+            RuleParseError::InvalidStates(states) => {
+                write!(
+                    f,
+                    "invalid amount of states '{states}', expected a number from 2 to 255"
+                )
+            }
+        }
+    }
+}
+
+impl Error for RuleParseError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn counts(ns: &[usize]) -> [bool; 9] {
+        let mut res = [false; 9];
+        for &n in ns {
+            res[n] = true;
+        }
+        res
+    }
+
+    #[test]
+    fn rulestrings_parse() {
+        assert_eq!(
+            Ruleset::default(),
+            Ruleset::from_rulestring("B3/S23").unwrap()
+        );
+        assert_eq!(
+            Ruleset::default(),
+            Ruleset::from_rulestring("s23/b3").unwrap()
+        );
+
+        let highlife = Ruleset::from_rulestring("B36/S23").unwrap();
+        assert_eq!(counts(&[3, 6]), highlife.birth);
+        assert_eq!(counts(&[2, 3]), highlife.survive);
+
+        let seeds = Ruleset::from_rulestring("B2/S").unwrap();
+        assert_eq!(counts(&[2]), seeds.birth);
+        assert_eq!(counts(&[]), seeds.survive);
+        assert_eq!(Neighbourhood::Moore, seeds.neighbourhood);
+
+        let von_neumann = Ruleset::from_rulestring("B2/S013V").unwrap();
+        assert_eq!(counts(&[2]), von_neumann.birth);
+        assert_eq!(counts(&[0, 1, 3]), von_neumann.survive);
+        assert_eq!(Neighbourhood::VonNeumann, von_neumann.neighbourhood);
+        assert_eq!(2, von_neumann.states);
+
+        let brians_brain = Ruleset::from_rulestring("B2/S/3").unwrap();
+        assert_eq!(counts(&[2]), brians_brain.birth);
+        assert_eq!(counts(&[]), brians_brain.survive);
+        assert_eq!(3, brians_brain.states);
+        assert_eq!(brians_brain, Ruleset::from_rulestring("b2/s/c3").unwrap());
+        assert_eq!(
+            Ruleset::default(),
+            Ruleset::from_rulestring("B3/S23/C2").unwrap()
+        );
+        // only 4 neighbours can be alive
+        assert_eq!(
+            Err(RuleParseError::InvalidCount('5')),
+            Ruleset::from_rulestring("B5/S2v")
+        );
+    }
+
+    #[test]
+    fn rulesets_format_as_rulestrings() {
+        for rulestring in [
+            "B3/S23",
+            "B2/S",
+            "B/S012345678",
+            "B2/S013V",
+            "B2/S/3",
+            "B2/S/5V",
+        ] {
+            let ruleset = Ruleset::from_rulestring(rulestring).unwrap();
+            assert_eq!(rulestring, ruleset.to_string());
+        }
+        assert_eq!(
+            "B36/S23",
+            Ruleset::from_rulestring("s32/b63/C2").unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn presets_cycle() {
+        for ((name, preset), rulestring) in Ruleset::PRESETS.into_iter().zip([
+            "B3/S23",
+            "B36/S23",
+            "B3678/S34678",
+            "B2/S",
+            "B1357/S1357",
+        ]) {
+            assert_eq!(Ruleset::from_rulestring(rulestring).unwrap(), preset);
+            assert_eq!(Some(name), preset.preset_name());
+        }
+        assert_eq!(Ruleset::PRESETS[0].1, Ruleset::default());
+
+        let mut rules = Ruleset::default();
+        for &(name, preset) in Ruleset::PRESETS.iter().skip(1) {
+            assert_eq!((name, preset), rules.next_preset());
+            rules = preset;
+        }
+        assert_eq!(Ruleset::PRESETS[0], rules.next_preset());
+
+        let brians_brain = Ruleset::from_rulestring("B2/S/3").unwrap();
+        assert_eq!(None, brians_brain.preset_name());
+        assert_eq!(Ruleset::PRESETS[0], brians_brain.next_preset());
+    }
+
+    #[test]
+    fn neighbourhoods_include_their_cells() {
+        assert_eq!(8, Neighbourhood::Moore.offsets().count());
+        let von_neumann = Neighbourhood::VonNeumann.offsets().collect::<Vec<_>>();
+        assert_eq!(
+            vec![ivec2(0, -1), ivec2(-1, 0), ivec2(1, 0), ivec2(0, 1)],
+            von_neumann
+        );
+        assert_eq!(Neighbourhood::VonNeumann.size(), von_neumann.len());
+        assert_eq!(
+            Neighbourhood::Moore,
+            Neighbourhood::Moore.toggled().toggled()
+        );
+    }
+
+    #[test]
+    fn malformed_rulestrings_fail() {
+        for malformed in ["", "B3", "B3S23", "B3/S23/3/4", "B3/B23", "X3/S23", "3/23"] {
+            assert_eq!(
+                Err(RuleParseError::Malformed(malformed.to_owned())),
+                Ruleset::from_rulestring(malformed),
+            );
+        }
+        assert_eq!(
+            Err(RuleParseError::InvalidCount('9')),
+            Ruleset::from_rulestring("B39/S23")
+        );
+        assert_eq!(
+            Err(RuleParseError::InvalidCount('x')),
+            Ruleset::from_rulestring("B3/S2x")
+        );
+        for states in ["1", "C256", "C", ""] {
+            assert_eq!(
+                Err(RuleParseError::InvalidStates(states.to_owned())),
+                Ruleset::from_rulestring(&format!("B2/S/{states}"))
+            );
+        }
+    }
+}
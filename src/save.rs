@@ -0,0 +1,454 @@
+use std::{
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::rules::{Neighbourhood, Ruleset};
+
+/// identifies board save files, bump the version if the layout changes
+const MAGIC: &[u8; 4] = b"GOL2";
+/// saves of square boards, with a single size instead of the width and height
+const MAGIC_V1: &[u8; 4] = b"GOL1";
+pub const SAVE_FILE_EXTENSION: &str = "gol";
+/// identifies session files, bump the version if the layout changes
+const SESSION_MAGIC: &[u8; 4] = b"GOS3";
+/// sessions from before the rules had an amount of states, which always have 2 of them
+const SESSION_MAGIC_V2: &[u8; 4] = b"GOS2";
+/// sessions from before the rules had a neighbourhood, which always use the Moore one
+const SESSION_MAGIC_V1: &[u8; 4] = b"GOS1";
+pub const SESSION_FILE_EXTENSION: &str = "gols";
+/// where the board gets saved when the game quits with unsaved edits
+pub const RECOVERY_SAVE_PATH: &str = "recovery.gol";
+/// patterns in the plaintext format, see `pattern::parse_plaintext`
+pub const PLAINTEXT_FILE_EXTENSION: &str = "cells";
+
+/// A snapshot of the board's cells.
+///
+/// Saved as the magic bytes, followed by the board's width and height as little endian `u32`s
+/// and the cells packed into bits (least significant bit first).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardSave {
+    /// the amount of cells along the x axis
+    pub width: u32,
+    /// the amount of cells along the y axis
+    pub height: u32,
+    /// life status of every cell, in row-major order (see `Board::cell_coord_to_idx`)
+    pub cells: Vec<bool>,
+}
+
+impl BoardSave {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(MAGIC.len() + 8 + self.cells.len().div_ceil(8));
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&self.width.to_le_bytes());
+        bytes.extend_from_slice(&self.height.to_le_bytes());
+        bytes.extend(self.cells.chunks(8).map(|chunk| {
+            chunk
+                .iter()
+                .enumerate()
+                .fold(0u8, |byte, (i, &alive)| byte | ((alive as u8) << i))
+        }));
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<BoardSave, SaveError> {
+        let (width, height, packed_cells) = if let Some(payload) = bytes.strip_prefix(MAGIC) {
+            let (width, payload) = payload
+                .split_first_chunk::<4>()
+                .ok_or(SaveError::InvalidFormat)?;
+            let (height, packed_cells) = payload
+                .split_first_chunk::<4>()
+                .ok_or(SaveError::InvalidFormat)?;
+            (
+                u32::from_le_bytes(*width),
+                u32::from_le_bytes(*height),
+                packed_cells,
+            )
+        } else {
+            let payload = bytes
+                .strip_prefix(MAGIC_V1)
+                .ok_or(SaveError::InvalidFormat)?;
+            let (size, packed_cells) = payload
+                .split_first_chunk::<4>()
+                .ok_or(SaveError::InvalidFormat)?;
+            let size = u32::from_le_bytes(*size);
+            (size, size, packed_cells)
+        };
+
+        let cell_count = width as usize * height as usize;
+        if packed_cells.len() != cell_count.div_ceil(8) {
+            return Err(SaveError::InvalidFormat);
+        }
+        let cells = (0..cell_count)
+            .map(|i| packed_cells[i / 8] & (1 << (i % 8)) != 0)
+            .collect();
+
+        Ok(BoardSave {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn write_to(&self, path: impl AsRef<Path>) -> Result<(), SaveError> {
+        Ok(fs::write(path, self.to_bytes())?)
+    }
+
+    pub fn read_from(path: impl AsRef<Path>) -> Result<BoardSave, SaveError> {
+        BoardSave::from_bytes(&fs::read(path)?)
+    }
+}
+
+/// A recorded run: the board it started from and the amount of generations it ran for.
+///
+/// Saved as the magic bytes, followed by the wrapping as a single byte (0 for a bounded board, 1
+/// for wrapping on both axes, 2 for only horizontally and 3 for only vertically), the birth and
+/// survival counts of the rules as little endian `u16` bitmasks, the neighbourhood as a single
+/// byte (0 for Moore, 1 for von Neumann), the amount of cell states as a single byte, the
+/// generations as a little endian `u64` and the starting board as a `BoardSave`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionSave {
+    pub board: BoardSave,
+    /// whether the left and right edges of the board wrap around
+    pub wrap_x: bool,
+    /// whether the top and bottom edges of the board wrap around
+    pub wrap_y: bool,
+    pub rules: Ruleset,
+    pub generations: u64,
+}
+
+impl SessionSave {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mask = |counts: &[bool; 9]| {
+            counts
+                .iter()
+                .enumerate()
+                .fold(0u16, |mask, (n, &set)| mask | ((set as u16) << n))
+        };
+        let mut bytes = SESSION_MAGIC.to_vec();
+        bytes.push(match (self.wrap_x, self.wrap_y) {
+            (false, false) => 0,
+            (true, true) => 1,
+            (true, false) => 2,
+            (false, true) => 3,
+        });
+        bytes.extend_from_slice(&mask(&self.rules.birth).to_le_bytes());
+        bytes.extend_from_slice(&mask(&self.rules.survive).to_le_bytes());
+        bytes.push(match self.rules.neighbourhood {
+            Neighbourhood::Moore => 0,
+            Neighbourhood::VonNeumann => 1,
+        });
+        bytes.push(self.rules.states);
+        bytes.extend_from_slice(&self.generations.to_le_bytes());
+        bytes.extend(self.board.to_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<SessionSave, SaveError> {
+        let (payload, version) = [SESSION_MAGIC, SESSION_MAGIC_V2, SESSION_MAGIC_V1]
+            .into_iter()
+            .zip([3, 2, 1])
+            .find_map(|(magic, version)| Some((bytes.strip_prefix(magic)?, version)))
+            .ok_or(SaveError::InvalidFormat)?;
+        let ([wrap], payload) = payload
+            .split_first_chunk::<1>()
+            .ok_or(SaveError::InvalidFormat)?;
+        let (birth, payload) = payload
+            .split_first_chunk::<2>()
+            .ok_or(SaveError::InvalidFormat)?;
+        let (survive, mut payload) = payload
+            .split_first_chunk::<2>()
+            .ok_or(SaveError::InvalidFormat)?;
+        let mut neighbourhood = Neighbourhood::Moore;
+        if version >= 2 {
+            let ([byte], rest) = payload
+                .split_first_chunk::<1>()
+                .ok_or(SaveError::InvalidFormat)?;
+            neighbourhood = match byte {
+                0 => Neighbourhood::Moore,
+                1 => Neighbourhood::VonNeumann,
+                _ => return Err(SaveError::InvalidFormat),
+            };
+            payload = rest;
+        }
+        let mut states = 2;
+        if version >= 3 {
+            let ([byte], rest) = payload
+                .split_first_chunk::<1>()
+                .ok_or(SaveError::InvalidFormat)?;
+            if *byte < 2 {
+                return Err(SaveError::InvalidFormat);
+            }
+            states = *byte;
+            payload = rest;
+        }
+        let (generations, board) = payload
+            .split_first_chunk::<8>()
+            .ok_or(SaveError::InvalidFormat)?;
+
+        let counts = |mask: &[u8; 2]| {
+            let mask = u16::from_le_bytes(*mask);
+            if mask >> 9 != 0 {
+                return Err(SaveError::InvalidFormat);
+            }
+            Ok(std::array::from_fn(|n| mask & (1 << n) != 0))
+        };
+        let (wrap_x, wrap_y) = match wrap {
+            0 => (false, false),
+            1 => (true, true),
+            2 => (true, false),
+            3 => (false, true),
+            _ => return Err(SaveError::InvalidFormat),
+        };
+
+        Ok(SessionSave {
+            board: BoardSave::from_bytes(board)?,
+            wrap_x,
+            wrap_y,
+            rules: Ruleset {
+                birth: counts(birth)?,
+                survive: counts(survive)?,
+                neighbourhood,
+                states,
+            },
+            generations: u64::from_le_bytes(*generations),
+        })
+    }
+
+    pub fn write_to(&self, path: impl AsRef<Path>) -> Result<(), SaveError> {
+        Ok(fs::write(path, self.to_bytes())?)
+    }
+
+    pub fn read_from(path: impl AsRef<Path>) -> Result<SessionSave, SaveError> {
+        SessionSave::from_bytes(&fs::read(path)?)
+    }
+}
+
+/// returns a new save file path in the working directory, e.g. `board_1712345678901.gol`
+pub fn timestamped_save_path() -> PathBuf {
+    PathBuf::from(format!("board_{}.{SAVE_FILE_EXTENSION}", unix_millis()))
+}
+
+/// returns a new session file path in the working directory, e.g. `session_1712345678901.gols`
+pub fn timestamped_session_path() -> PathBuf {
+    PathBuf::from(format!(
+        "session_{}.{SESSION_FILE_EXTENSION}",
+        unix_millis()
+    ))
+}
+
+/// returns a new plaintext pattern path in the working directory, e.g. `pattern_1712345678901.cells`
+pub fn timestamped_plaintext_path() -> PathBuf {
+    PathBuf::from(format!(
+        "pattern_{}.{PLAINTEXT_FILE_EXTENSION}",
+        unix_millis()
+    ))
+}
+
+/// milliseconds since the unix epoch, used to name files
+pub(crate) fn unix_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
+/// finds the most recent save file created by `timestamped_save_path` in `dir`
+pub fn latest_save_path(dir: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
+    latest_timestamped_path(dir, "board_", SAVE_FILE_EXTENSION)
+}
+
+/// finds the most recent session file created by `timestamped_session_path` in `dir`
+pub fn latest_session_path(dir: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
+    latest_timestamped_path(dir, "session_", SESSION_FILE_EXTENSION)
+}
+
+/// Finds the most recently modified plaintext pattern in `dir`, patterns written by hand don't
+/// have to be named like the ones from `timestamped_plaintext_path`.
+pub fn latest_plaintext_path(dir: impl AsRef<Path>) -> io::Result<Option<PathBuf>> {
+    let mut latest = None;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path
+            .extension()
+            .is_none_or(|ext| ext != PLAINTEXT_FILE_EXTENSION)
+        {
+            continue;
+        }
+        let modified = entry.metadata()?.modified()?;
+        if latest.as_ref().is_none_or(|(latest, _)| modified > *latest) {
+            latest = Some((modified, path));
+        }
+    }
+    Ok(latest.map(|(_, path)| path))
+}
+
+fn latest_timestamped_path(
+    dir: impl AsRef<Path>,
+    prefix: &str,
+    extension: &str,
+) -> io::Result<Option<PathBuf>> {
+    let mut latest = None;
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let timestamp = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(prefix))
+            .and_then(|name| name.strip_suffix(&format!(".{extension}")))
+            .and_then(|millis| millis.parse::<u128>().ok());
+        if let Some(timestamp) = timestamp {
+            if latest
+                .as_ref()
+                .is_none_or(|(latest, _)| timestamp > *latest)
+            {
+                latest = Some((timestamp, path));
+            }
+        }
+    }
+    Ok(latest.map(|(_, path)| path))
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    /// the data isn't a valid board save
+    InvalidFormat,
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(e) => write!(f, "io error: {e}"),
+            SaveError::InvalidFormat => write!(f, "not a valid save file"),
+        }
+    }
+}
+
+impl Error for SaveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SaveError::Io(e) => Some(e),
+            SaveError::InvalidFormat => None,
+        }
+    }
+}
+
+impl From<io::Error> for SaveError {
+    fn from(e: io::Error) -> Self {
+        SaveError::Io(e)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn save_round_trips() {
+        // 5x4 board with a glider, 20 cells don't fill the last byte
+        let cells = (0..20).map(|i| [1, 7, 10, 11, 12].contains(&i)).collect();
+        let save = BoardSave {
+            width: 5,
+            height: 4,
+            cells,
+        };
+
+        let path = std::env::temp_dir().join(format!("gol_save_test.{SAVE_FILE_EXTENSION}"));
+        save.write_to(&path).unwrap();
+        let bytes = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(MAGIC.len() + 8 + 3, bytes.len());
+        assert_eq!(save, BoardSave::from_bytes(&bytes).unwrap());
+
+        // the first version only saved square boards
+        let mut v1_bytes = MAGIC_V1.to_vec();
+        v1_bytes.extend_from_slice(&2u32.to_le_bytes());
+        v1_bytes.push(0b1001);
+        let v1_save = BoardSave::from_bytes(&v1_bytes).unwrap();
+        assert_eq!((2, 2), (v1_save.width, v1_save.height));
+        assert_eq!(vec![true, false, false, true], v1_save.cells);
+
+        assert!(matches!(
+            BoardSave::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(SaveError::InvalidFormat)
+        ));
+        assert!(matches!(
+            BoardSave::from_bytes(b"GOL0"),
+            Err(SaveError::InvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn session_round_trips() {
+        let session = SessionSave {
+            board: BoardSave {
+                width: 3,
+                height: 3,
+                cells: vec![false, true, false, false, true, false, false, true, false],
+            },
+            wrap_x: true,
+            wrap_y: false,
+            rules: Ruleset::from_rulestring("B2/S013/4V").unwrap(),
+            generations: 1234,
+        };
+        let bytes = session.to_bytes();
+        assert_eq!(session, SessionSave::from_bytes(&bytes).unwrap());
+
+        // the second version is missing the states byte after the neighbourhood
+        let mut v2_bytes = SESSION_MAGIC_V2.to_vec();
+        v2_bytes.extend_from_slice(&bytes[4..10]);
+        v2_bytes.extend_from_slice(&bytes[11..]);
+        let v2_session = SessionSave::from_bytes(&v2_bytes).unwrap();
+        assert_eq!(Neighbourhood::VonNeumann, v2_session.rules.neighbourhood);
+        assert_eq!(2, v2_session.rules.states);
+        assert_eq!(session.generations, v2_session.generations);
+
+        // the first version is missing the neighbourhood byte too
+        let mut v1_bytes = SESSION_MAGIC_V1.to_vec();
+        v1_bytes.extend_from_slice(&bytes[4..9]);
+        v1_bytes.extend_from_slice(&bytes[11..]);
+        let v1_session = SessionSave::from_bytes(&v1_bytes).unwrap();
+        assert_eq!(Neighbourhood::Moore, v1_session.rules.neighbourhood);
+        assert_eq!(session.generations, v1_session.generations);
+
+        // a plain board save isn't a session
+        assert!(matches!(
+            SessionSave::from_bytes(&session.board.to_bytes()),
+            Err(SaveError::InvalidFormat)
+        ));
+        assert!(matches!(
+            SessionSave::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(SaveError::InvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn latest_save_is_loaded() {
+        let dir = std::env::temp_dir().join("gol_latest_save_test");
+        fs::create_dir_all(&dir).unwrap();
+        let older = BoardSave {
+            width: 2,
+            height: 2,
+            cells: vec![true, false, false, true],
+        };
+        let newer = BoardSave {
+            width: 3,
+            height: 3,
+            cells: vec![false, true, false, false, true, false, false, true, false],
+        };
+        older.write_to(dir.join("board_999.gol")).unwrap();
+        newer.write_to(dir.join("board_1000.gol")).unwrap();
+        fs::write(dir.join("board_2000.txt"), "not a save").unwrap();
+
+        let latest = latest_save_path(&dir).unwrap().unwrap();
+        assert_eq!(dir.join("board_1000.gol"), latest);
+        assert_eq!(newer, BoardSave::read_from(latest).unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
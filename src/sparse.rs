@@ -0,0 +1,128 @@
+use bevy::{
+    math::{ivec2, IVec2, UVec2},
+    utils::{HashMap, HashSet},
+};
+
+use crate::rules::Ruleset;
+
+/// An unbounded board that only stores its live cells.
+///
+/// Rules that give birth to cells without any live neighbours (B0) would fill the whole plane,
+/// so the birth of such cells is ignored.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SparseLife {
+    live: HashSet<IVec2>,
+}
+
+impl SparseLife {
+    pub fn is_alive(&self, cell_coord: IVec2) -> bool {
+        self.live.contains(&cell_coord)
+    }
+
+    pub fn population(&self) -> usize {
+        self.live.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.live.clear();
+    }
+
+    /// advances by one generation, only the live cells and their neighbours can change
+    pub fn step(&mut self, rules: &Ruleset) {
+        let mut neighbour_counts: HashMap<IVec2, usize> = HashMap::default();
+        for &cell_coord in &self.live {
+            for offset in rules.neighbourhood.offsets() {
+                *neighbour_counts.entry(cell_coord + offset).or_default() += 1;
+            }
+        }
+
+        let survivors = self
+            .live
+            .iter()
+            .filter(|cell_coord| {
+                rules.survive[neighbour_counts
+                    .get(*cell_coord)
+                    .copied()
+                    .unwrap_or_default()]
+            })
+            .copied();
+        let births = neighbour_counts
+            .iter()
+            .filter(|(cell_coord, &count)| !self.live.contains(*cell_coord) && rules.birth[count])
+            .map(|(cell_coord, _)| *cell_coord);
+        self.live = survivors.chain(births).collect();
+    }
+
+    /// Returns the cells of the window with its bottom left corner at `origin`, indexed like the
+    /// cells of a `Board` of the same size.
+    pub fn window(&self, origin: IVec2, size: UVec2) -> Vec<bool> {
+        window_coords(origin, size)
+            .map(|cell_coord| self.is_alive(cell_coord))
+            .collect()
+    }
+
+    /// replaces the cells of the window with its bottom left corner at `origin`, see `window`
+    pub fn store_window(&mut self, origin: IVec2, size: UVec2, cells: &[bool]) {
+        for (cell_coord, &alive) in window_coords(origin, size).zip(cells) {
+            if alive {
+                self.live.insert(cell_coord);
+            } else {
+                self.live.remove(&cell_coord);
+            }
+        }
+    }
+}
+
+/// the coordinates of a window in the order of its cell indices
+fn window_coords(origin: IVec2, size: UVec2) -> impl Iterator<Item = IVec2> {
+    let size = size.as_ivec2();
+    (0..size.y).flat_map(move |y| (0..size.x).map(move |x| origin + ivec2(x, y)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn life_of(live_cells: &[IVec2]) -> SparseLife {
+        SparseLife {
+            live: live_cells.iter().copied().collect(),
+        }
+    }
+
+    #[test]
+    fn glider_travels_far() {
+        // moves one cell right and down every 4 generations
+        let glider = [
+            ivec2(1, 2),
+            ivec2(2, 1),
+            ivec2(0, 0),
+            ivec2(1, 0),
+            ivec2(2, 0),
+        ];
+        let mut life = life_of(&glider);
+        let rules = Ruleset::default();
+        for _ in 0..4000 {
+            life.step(&rules);
+        }
+
+        let moved: Vec<_> = glider.iter().map(|&c| c + ivec2(1000, -1000)).collect();
+        assert_eq!(life_of(&moved), life);
+    }
+
+    #[test]
+    fn windows_round_trip() {
+        let mut life = life_of(&[ivec2(-5, -5), ivec2(0, 0), ivec2(1, 2)]);
+        let origin = ivec2(-1, -1);
+        let size = UVec2::new(4, 5);
+        let mut window = life.window(origin, size);
+        assert_eq!(2, window.iter().filter(|alive| **alive).count());
+        // (0, 0) is the cell (1, 1) of the window
+        assert!(window[4 + 1]);
+
+        window.fill(false);
+        window[0] = true;
+        life.store_window(origin, size, &window);
+        // the cells outside of the window are kept
+        assert_eq!(life_of(&[ivec2(-5, -5), ivec2(-1, -1)]), life);
+    }
+}
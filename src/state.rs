@@ -6,4 +6,51 @@ pub enum GameState {
     Load,
     Setup,
     Running,
+    Paused,
+}
+
+impl GameState {
+    /// the state after starting / stopping the simulation, `None` while loading
+    pub fn started_or_stopped(self) -> Option<GameState> {
+        match self {
+            GameState::Setup | GameState::Paused => Some(GameState::Running),
+            GameState::Running => Some(GameState::Paused),
+            GameState::Load => None,
+        }
+    }
+
+    /// the state for editing the board, `None` if the board can't be edited from this state
+    pub fn edited(self) -> Option<GameState> {
+        match self {
+            GameState::Running | GameState::Paused => Some(GameState::Setup),
+            GameState::Load | GameState::Setup => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn transitions_are_valid() {
+        assert_eq!(
+            Some(GameState::Running),
+            GameState::Setup.started_or_stopped()
+        );
+        assert_eq!(
+            Some(GameState::Paused),
+            GameState::Running.started_or_stopped()
+        );
+        assert_eq!(
+            Some(GameState::Running),
+            GameState::Paused.started_or_stopped()
+        );
+        assert_eq!(None, GameState::Load.started_or_stopped());
+
+        assert_eq!(Some(GameState::Setup), GameState::Running.edited());
+        assert_eq!(Some(GameState::Setup), GameState::Paused.edited());
+        assert_eq!(None, GameState::Setup.edited());
+        assert_eq!(None, GameState::Load.edited());
+    }
 }
@@ -0,0 +1,61 @@
+// Steps the board once, see `gpu::GpuStepper`. Cells are stored one per u32, in the order of
+// `Board::cell_coord_to_idx`.
+
+struct Params {
+    width: u32,
+    height: u32,
+    // bit n is set if a cell with n live neighbours is born / survives
+    birth: u32,
+    survive: u32,
+    wrap_x: u32,
+    wrap_y: u32,
+    von_neumann: u32,
+    _padding: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> current: array<u32>;
+@group(0) @binding(2) var<storage, read_write> next: array<u32>;
+
+// 1 if the cell is alive, cells beyond an edge that doesn't wrap are dead
+fn alive_at(x: i32, y: i32) -> u32 {
+    let width = i32(params.width);
+    let height = i32(params.height);
+    var wrapped_x = x;
+    var wrapped_y = y;
+    if x < 0 || x >= width {
+        if params.wrap_x == 0u {
+            return 0u;
+        }
+        wrapped_x = (x + width) % width;
+    }
+    if y < 0 || y >= height {
+        if params.wrap_y == 0u {
+            return 0u;
+        }
+        wrapped_y = (y + height) % height;
+    }
+    return current[wrapped_y * width + wrapped_x];
+}
+
+@compute @workgroup_size(8, 8)
+fn step(@builtin(global_invocation_id) id: vec3<u32>) {
+    if id.x >= params.width || id.y >= params.height {
+        return;
+    }
+    let x = i32(id.x);
+    let y = i32(id.y);
+    var live_neighbours = 0u;
+    for (var dy = -1; dy <= 1; dy++) {
+        for (var dx = -1; dx <= 1; dx++) {
+            let diagonal = dx != 0 && dy != 0;
+            if (dx == 0 && dy == 0) || (diagonal && params.von_neumann != 0u) {
+                continue;
+            }
+            live_neighbours += alive_at(x + dx, y + dy);
+        }
+    }
+    let idx = id.y * params.width + id.x;
+    let counts = select(params.birth, params.survive, current[idx] != 0u);
+    next[idx] = (counts >> live_neighbours) & 1u;
+}
@@ -0,0 +1,163 @@
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+/// Colors of the cells, the background takes the color of the dead cells.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub name: &'static str,
+    pub cell_alive: Color,
+    /// color of cells that have been alive for a long time, see `AGE_BUCKETS`
+    pub cell_old: Color,
+    pub cell_dead: Color,
+    pub cell_clicked: Color,
+    pub cell_hovered_alive: Color,
+    pub cell_hovered_dead: Color,
+    /// color of cells that just changed, in the heatmap mode
+    pub heatmap_hot: Color,
+    /// color of cells that just started decaying, they fade towards `cell_dead`
+    pub cell_decaying: Color,
+}
+
+impl Theme {
+    /// the colors from the prelude
+    pub const CLASSIC: Theme = Theme {
+        name: "classic",
+        cell_alive: CELL_ALIVE_COLOR,
+        cell_old: CELL_OLD_COLOR,
+        cell_dead: BG_COLOR,
+        cell_clicked: CELL_CLICKED_COLOR,
+        cell_hovered_alive: CELL_HOVERED_ALIVE_COLOR,
+        cell_hovered_dead: CELL_HOVERED_DEAD_COLOR,
+        heatmap_hot: HEATMAP_HOT_COLOR,
+        cell_decaying: CELL_DECAYING_COLOR,
+    };
+
+    pub const MONOCHROME: Theme = Theme {
+        name: "monochrome",
+        cell_alive: Color::srgb(1.0, 1.0, 1.0),
+        cell_old: Color::srgb(0.6, 0.6, 0.6),
+        cell_dead: Color::srgb(0.0, 0.0, 0.0),
+        cell_clicked: Color::srgb(0.8, 0.8, 0.8),
+        cell_hovered_alive: Color::srgb(0.5, 0.5, 0.5),
+        cell_hovered_dead: Color::srgb(0.25, 0.25, 0.25),
+        heatmap_hot: Color::srgb(1.0, 1.0, 1.0),
+        cell_decaying: Color::srgb(0.7, 0.7, 0.7),
+    };
+
+    pub const OCEAN: Theme = Theme {
+        name: "ocean",
+        cell_alive: Color::srgb(0.3, 0.9, 1.0),
+        cell_old: Color::srgb(0.1, 0.4, 0.8),
+        cell_dead: Color::srgb(0.0, 0.05, 0.15),
+        cell_clicked: Color::srgb(1.0, 1.0, 1.0),
+        cell_hovered_alive: Color::srgb(0.6, 1.0, 0.8),
+        cell_hovered_dead: Color::srgb(0.1, 0.3, 0.4),
+        heatmap_hot: Color::srgb(0.5, 1.0, 0.9),
+        cell_decaying: Color::srgb(0.2, 0.5, 0.9),
+    };
+
+    pub const EMBER: Theme = Theme {
+        name: "ember",
+        cell_alive: Color::srgb(1.0, 0.8, 0.2),
+        cell_old: Color::srgb(0.8, 0.2, 0.1),
+        cell_dead: Color::srgb(0.1, 0.02, 0.0),
+        cell_clicked: Color::srgb(1.0, 1.0, 0.8),
+        cell_hovered_alive: Color::srgb(1.0, 0.5, 0.5),
+        cell_hovered_dead: Color::srgb(0.4, 0.15, 0.05),
+        heatmap_hot: Color::srgb(1.0, 0.9, 0.6),
+        cell_decaying: Color::srgb(0.9, 0.3, 0.05),
+    };
+
+    /// the themes that can be cycled through at runtime
+    pub const PRESETS: [Theme; 4] = [
+        Theme::CLASSIC,
+        Theme::MONOCHROME,
+        Theme::OCEAN,
+        Theme::EMBER,
+    ];
+
+    /// Changes a single color, e.g. `theme.set_color(CellColor::Alive, Color::WHITE)` on the
+    /// `Theme` resource.
+    ///
+    /// Only the materials showing that color are updated, the cells in other states aren't
+    /// touched, see `life::MeshAndMats`.
+    pub fn set_color(&mut self, cell_color: CellColor, color: Color) {
+        let field = match cell_color {
+            CellColor::Alive => &mut self.cell_alive,
+            CellColor::Old => &mut self.cell_old,
+            CellColor::Dead => &mut self.cell_dead,
+            CellColor::Clicked => &mut self.cell_clicked,
+            CellColor::HoveredAlive => &mut self.cell_hovered_alive,
+            CellColor::HoveredDead => &mut self.cell_hovered_dead,
+            CellColor::HeatmapHot => &mut self.heatmap_hot,
+            CellColor::Decaying => &mut self.cell_decaying,
+        };
+        *field = color;
+    }
+
+    /// the preset called `name`
+    pub fn preset(name: &str) -> Option<Theme> {
+        Theme::PRESETS
+            .into_iter()
+            .find(|preset| preset.name == name)
+    }
+
+    /// the preset following this theme, or the first one for a custom theme
+    pub fn next(&self) -> Theme {
+        cycle_next(&Theme::PRESETS, *self)
+    }
+}
+
+/// The colors of a `Theme`, see `Theme::set_color`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellColor {
+    Alive,
+    Old,
+    /// also the color of the background
+    Dead,
+    Clicked,
+    HoveredAlive,
+    HoveredDead,
+    HeatmapHot,
+    Decaying,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::CLASSIC
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn themes_cycle() {
+        let mut theme = Theme::default();
+        assert_eq!(Theme::CLASSIC, theme);
+        for preset in Theme::PRESETS.iter().skip(1) {
+            theme = theme.next();
+            assert_eq!(*preset, theme);
+        }
+        assert_eq!(Theme::CLASSIC, theme.next());
+
+        let custom = Theme {
+            name: "custom",
+            ..Theme::OCEAN
+        };
+        assert_eq!(Theme::CLASSIC, custom.next());
+
+        assert_eq!(Some(Theme::EMBER), Theme::preset("ember"));
+        assert_eq!(None, Theme::preset("custom"));
+    }
+
+    #[test]
+    fn single_colors_are_set() {
+        let mut theme = Theme::OCEAN;
+        theme.set_color(CellColor::Alive, Color::WHITE);
+        assert_eq!(Color::WHITE, theme.cell_alive);
+        assert_eq!(Theme::OCEAN.cell_dead, theme.cell_dead);
+    }
+}